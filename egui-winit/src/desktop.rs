@@ -0,0 +1,268 @@
+//! An [`epi::desktop::Desktop`] using the file dialogs and tools of the operating system.
+//!
+//! * File dialogs: [`rfd`](https://docs.rs/rfd), with the `file_dialogs` feature.
+//!   On Linux this uses GTK 3. On Mac the dialogs are run by the main thread, i.e. the event loop.
+//! * Notifications: `notify-send` on Linux and `osascript` on Mac. Not supported on Windows.
+//! * Reveal in file manager: `xdg-open` (of the directory) on Linux, `open -R` on Mac and `explorer /select` on Windows.
+
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+
+use epi::desktop::{DesktopRequest, DesktopResponse, FileDialog, Notification, Responder};
+
+/// Carries out each request on its own thread, so that the app never blocks.
+///
+/// Every request responds within its timeout, so no thread is left waiting forever.
+pub struct SystemDesktop {
+    /// How long the user has to pick a file before the request fails.
+    ///
+    /// The dialog may stay open after that, but what the user picks is ignored.
+    /// Default: ten minutes.
+    pub dialog_timeout: Duration,
+
+    /// How long to wait for notifications to be shown and for the file manager to open.
+    /// Default: ten seconds.
+    pub command_timeout: Duration,
+}
+
+impl Default for SystemDesktop {
+    fn default() -> Self {
+        Self {
+            dialog_timeout: Duration::from_secs(10 * 60),
+            command_timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl epi::desktop::Desktop for SystemDesktop {
+    fn request(&self, request: DesktopRequest, responder: Responder) {
+        let dialog_timeout = self.dialog_timeout;
+        let command_timeout = self.command_timeout;
+        std::thread::spawn(move || {
+            let response = match request {
+                DesktopRequest::OpenFile(dialog) => file_dialog(&dialog, false, dialog_timeout),
+                DesktopRequest::SaveFile(dialog) => file_dialog(&dialog, true, dialog_timeout),
+                DesktopRequest::Notify(notification) => notify(&notification, command_timeout),
+                DesktopRequest::RevealInFileManager(path) => reveal(&path, command_timeout),
+            };
+            responder.respond(response);
+        });
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(feature = "file_dialogs")]
+fn file_dialog(dialog: &FileDialog, save: bool, timeout: Duration) -> DesktopResponse {
+    let mut rfd_dialog = rfd::AsyncFileDialog::new();
+    if let Some(title) = &dialog.title {
+        rfd_dialog = rfd_dialog.set_title(title);
+    }
+    if let Some(directory) = &dialog.directory {
+        rfd_dialog = rfd_dialog.set_directory(directory);
+    }
+    if let Some(file_name) = &dialog.file_name {
+        rfd_dialog = rfd_dialog.set_file_name(file_name);
+    }
+    for (name, extensions) in &dialog.filters {
+        let extensions: Vec<&str> = extensions.iter().map(String::as_str).collect();
+        rfd_dialog = rfd_dialog.add_filter(name, &extensions);
+    }
+
+    // `None` from rfd means the user cancelled:
+    let files: Option<Vec<rfd::FileHandle>> = if save {
+        block_on(rfd_dialog.save_file(), timeout).map(|file| file.into_iter().collect())
+    } else if dialog.multiple {
+        block_on(rfd_dialog.pick_files(), timeout).map(Option::unwrap_or_default)
+    } else {
+        block_on(rfd_dialog.pick_file(), timeout).map(|file| file.into_iter().collect())
+    };
+    match files {
+        Some(files) => {
+            DesktopResponse::Files(files.iter().map(|file| file.path().to_owned()).collect())
+        }
+        None => DesktopResponse::Failed("The file dialog timed out".to_owned()),
+    }
+}
+
+#[cfg(not(feature = "file_dialogs"))]
+fn file_dialog(_dialog: &FileDialog, _save: bool, _timeout: Duration) -> DesktopResponse {
+    DesktopResponse::Failed("File dialogs need the `file_dialogs` feature of egui-winit".to_owned())
+}
+
+/// Run the future on this thread, or give up on it after the timeout.
+#[cfg(feature = "file_dialogs")]
+fn block_on<F: std::future::Future>(future: F, timeout: Duration) -> Option<F::Output> {
+    struct ThreadWaker(std::thread::Thread);
+
+    impl std::task::Wake for ThreadWaker {
+        fn wake(self: std::sync::Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = std::task::Waker::from(std::sync::Arc::new(ThreadWaker(std::thread::current())));
+    let mut context = std::task::Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return Some(output);
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return None;
+        }
+        std::thread::park_timeout(deadline - now);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Run the command, and kill it if it takes longer than the timeout.
+///
+/// `what` names the command in the error message.
+fn run(command: &mut Command, what: &str, timeout: Duration) -> Result<ExitStatus, String> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| format!("{}: {}", what, err))?;
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Ok(status),
+            Ok(None) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            Ok(None) => {
+                child.kill().ok();
+                child.wait().ok();
+                return Err(format!("{} timed out", what));
+            }
+            Err(err) => return Err(format!("{}: {}", what, err)),
+        }
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn done_if_success(result: Result<ExitStatus, String>, what: &str) -> DesktopResponse {
+    match result {
+        Ok(status) if status.success() => DesktopResponse::Done,
+        Ok(status) => DesktopResponse::Failed(format!("{} failed: {}", what, status)),
+        Err(err) => DesktopResponse::Failed(err),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn notify(notification: &Notification, timeout: Duration) -> DesktopResponse {
+    let result = run(
+        Command::new("notify-send")
+            .arg("--")
+            .arg(&notification.summary)
+            .arg(&notification.body),
+        "notify-send",
+        timeout,
+    );
+    done_if_success(result, "notify-send")
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+fn reveal(path: &Path, timeout: Duration) -> DesktopResponse {
+    // There is no command for selecting the file, so just open its directory:
+    let directory = if path.is_dir() {
+        path
+    } else {
+        path.parent().unwrap_or(path)
+    };
+    let result = run(Command::new("xdg-open").arg(directory), "xdg-open", timeout);
+    done_if_success(result, "xdg-open")
+}
+
+#[cfg(target_os = "macos")]
+fn notify(notification: &Notification, timeout: Duration) -> DesktopResponse {
+    let script = format!(
+        "display notification {:?} with title {:?}",
+        notification.body, notification.summary
+    );
+    let result = run(
+        Command::new("osascript").arg("-e").arg(script),
+        "osascript",
+        timeout,
+    );
+    done_if_success(result, "osascript")
+}
+
+#[cfg(target_os = "macos")]
+fn reveal(path: &Path, timeout: Duration) -> DesktopResponse {
+    let result = run(Command::new("open").arg("-R").arg(path), "open", timeout);
+    done_if_success(result, "open")
+}
+
+#[cfg(target_os = "windows")]
+fn notify(_notification: &Notification, _timeout: Duration) -> DesktopResponse {
+    DesktopResponse::Failed("Notifications are not supported on Windows".to_owned())
+}
+
+#[cfg(target_os = "windows")]
+fn reveal(path: &Path, timeout: Duration) -> DesktopResponse {
+    let mut command = Command::new("explorer");
+    command.arg(format!("/select,{}", path.display()));
+    // explorer returns a non-zero exit code even when it succeeds.
+    match run(&mut command, "explorer", timeout) {
+        Ok(_) => DesktopResponse::Done,
+        Err(err) => DesktopResponse::Failed(err),
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn commands_time_out() {
+        let timeout = Duration::from_secs(5);
+        assert!(run(&mut Command::new("true"), "true", timeout)
+            .unwrap()
+            .success());
+        assert!(!run(&mut Command::new("false"), "false", timeout)
+            .unwrap()
+            .success());
+        assert!(run(
+            &mut Command::new("no-such-command"),
+            "no-such-command",
+            timeout
+        )
+        .is_err());
+
+        let start = Instant::now();
+        let result = run(
+            Command::new("sleep").arg("10"),
+            "sleep",
+            Duration::from_millis(50),
+        );
+        assert_eq!(result, Err("sleep timed out".to_owned()));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(feature = "file_dialogs")]
+    #[test]
+    fn futures_time_out() {
+        let timeout = Duration::from_millis(50);
+        assert_eq!(block_on(std::future::ready(1), timeout), Some(1));
+        assert_eq!(block_on(std::future::pending::<()>(), timeout), None);
+    }
+
+    #[cfg(not(feature = "file_dialogs"))]
+    #[test]
+    fn file_dialogs_need_the_feature() {
+        let response = file_dialog(&FileDialog::new(), false, Duration::from_secs(1));
+        assert!(matches!(response, DesktopResponse::Failed(_)));
+    }
+}
@@ -7,6 +7,7 @@ pub fn window_builder(
     let mut window_builder = winit::window::WindowBuilder::new()
         .with_always_on_top(native_options.always_on_top)
         .with_maximized(native_options.maximized)
+        .with_fullscreen(fullscreen(native_options.fullscreen))
         .with_decorations(native_options.decorated)
         .with_resizable(native_options.resizable)
         .with_transparent(native_options.transparent)
@@ -15,20 +16,115 @@ pub fn window_builder(
     window_builder =
         window_builder_drag_and_drop(window_builder, native_options.drag_and_drop_support);
 
-    let initial_size_points = native_options.initial_window_size;
+    if let Some(min_size) = native_options.min_window_size {
+        window_builder = window_builder.with_min_inner_size(logical_size(min_size));
+    }
+    if let Some(max_size) = native_options.max_window_size {
+        window_builder = window_builder.with_max_inner_size(logical_size(max_size));
+    }
 
     if let Some(window_settings) = window_settings {
         window_builder = window_settings.initialize_window(window_builder);
-    } else if let Some(initial_size_points) = initial_size_points {
-        window_builder = window_builder.with_inner_size(winit::dpi::LogicalSize {
-            width: initial_size_points.x as f64,
-            height: initial_size_points.y as f64,
-        });
+    } else {
+        if let Some(initial_size_points) = native_options.initial_window_size {
+            window_builder = window_builder.with_inner_size(logical_size(initial_size_points));
+        }
+        if let Some(initial_pos_points) = native_options.initial_window_pos {
+            window_builder = window_builder.with_position(logical_position(initial_pos_points));
+        }
     }
 
     window_builder
 }
 
+fn logical_size(size_points: egui::Vec2) -> winit::dpi::LogicalSize<f64> {
+    winit::dpi::LogicalSize {
+        width: size_points.x as f64,
+        height: size_points.y as f64,
+    }
+}
+
+fn logical_position(pos_points: egui::Pos2) -> winit::dpi::LogicalPosition<f64> {
+    winit::dpi::LogicalPosition {
+        x: pos_points.x as f64,
+        y: pos_points.y as f64,
+    }
+}
+
+/// Borderless fullscreen on the current monitor.
+fn fullscreen(fullscreen: bool) -> Option<winit::window::Fullscreen> {
+    if fullscreen {
+        Some(winit::window::Fullscreen::Borderless(None))
+    } else {
+        None
+    }
+}
+
+/// The current position, size and state of the window.
+pub fn window_info(window: &winit::window::Window) -> epi::WindowInfo {
+    let scale_factor = window.scale_factor();
+    let size = window.inner_size().to_logical::<f32>(scale_factor);
+    epi::WindowInfo {
+        position: window.outer_position().ok().map(|pos| {
+            let pos = pos.to_logical::<f32>(scale_factor);
+            egui::pos2(pos.x, pos.y)
+        }),
+        size: egui::vec2(size.width, size.height),
+        fullscreen: window.fullscreen().is_some(),
+        maximized: window.is_maximized(),
+    }
+}
+
+/// All monitors connected to the computer.
+pub fn monitors(window: &winit::window::Window) -> Vec<epi::MonitorInfo> {
+    let current_monitor = window.current_monitor();
+    window
+        .available_monitors()
+        .map(|monitor| {
+            monitor_info(
+                monitor.name(),
+                monitor.position(),
+                monitor.size(),
+                monitor.scale_factor(),
+                current_monitor.as_ref() == Some(&monitor),
+            )
+        })
+        .collect()
+}
+
+/// Converts the physical pixels winit reports to points, like [`window_info`] does.
+fn monitor_info(
+    name: Option<String>,
+    position: winit::dpi::PhysicalPosition<i32>,
+    size: winit::dpi::PhysicalSize<u32>,
+    scale_factor: f64,
+    is_current: bool,
+) -> epi::MonitorInfo {
+    let position = position.to_logical::<f32>(scale_factor);
+    let size = size.to_logical::<f32>(scale_factor);
+    epi::MonitorInfo {
+        name,
+        position: egui::pos2(position.x, position.y),
+        size: egui::vec2(size.width, size.height),
+        scale_factor: scale_factor as f32,
+        is_current,
+    }
+}
+
+/// A window builder for an extra native window, see [`epi::Frame::show_window`].
+pub fn extra_window_builder(options: &epi::WindowOptions) -> winit::window::WindowBuilder {
+    winit::window::WindowBuilder::new()
+        .with_title(&options.title)
+        .with_always_on_top(options.always_on_top)
+        .with_decorations(options.decorated)
+        .with_resizable(options.resizable)
+        .with_inner_size(logical_size(
+            options
+                .initial_size
+                .unwrap_or(epi::WindowOptions::DEFAULT_SIZE),
+        ))
+}
+
 fn load_icon(icon_data: epi::IconData) -> Option<winit::window::Icon> {
     winit::window::Icon::from_rgba(icon_data.rgba, icon_data.width, icon_data.height).ok()
 }
@@ -62,6 +158,12 @@ pub fn handle_app_output(
         window_size,
         window_title,
         decorated,
+        fullscreen,
+        maximized,
+        minimized,
+        window_pos,
+        min_window_size,
+        max_window_size,
         drag_window,
     } = app_output;
 
@@ -69,6 +171,30 @@ pub fn handle_app_output(
         window.set_decorations(decorated);
     }
 
+    if let Some(fullscreen) = fullscreen {
+        window.set_fullscreen(self::fullscreen(fullscreen));
+    }
+
+    if let Some(maximized) = maximized {
+        window.set_maximized(maximized);
+    }
+
+    if let Some(minimized) = minimized {
+        window.set_minimized(minimized);
+    }
+
+    if let Some(window_pos) = window_pos {
+        window.set_outer_position(logical_position(window_pos));
+    }
+
+    if let Some(min_window_size) = min_window_size {
+        window.set_min_inner_size(min_window_size.size().map(logical_size));
+    }
+
+    if let Some(max_window_size) = max_window_size {
+        window.set_max_inner_size(max_window_size.size().map(logical_size));
+    }
+
     if let Some(window_size) = window_size {
         window.set_inner_size(
             winit::dpi::PhysicalSize {
@@ -187,20 +313,176 @@ impl Persistence {
 
 // ----------------------------------------------------------------------------
 
+/// Recording or replaying a session, see [`epi::NativeOptions::record_session`].
+#[cfg(feature = "recording")]
+enum Session {
+    Live,
+    Recording {
+        recorder: epi::recording::Recorder,
+        last_save: instant::Instant,
+    },
+    Replaying {
+        replayer: Box<epi::recording::Replayer>,
+        /// What the app painted the last replayed frame.
+        shapes: Vec<egui::epaint::ClippedShape>,
+    },
+}
+
+#[cfg(feature = "recording")]
+impl Session {
+    fn new(native_options: &epi::NativeOptions, egui_ctx: &egui::Context) -> Self {
+        if let Some(path) = &native_options.replay_session {
+            match epi::recording::Recording::load(path) {
+                Ok(recording) => {
+                    *egui_ctx.memory() = recording.memory.clone();
+                    return Self::Replaying {
+                        replayer: Box::new(epi::recording::Replayer::new(recording)),
+                        shapes: vec![],
+                    };
+                }
+                Err(err) => {
+                    eprintln!("Failed to load recording from {:?}: {}", path, err);
+                }
+            }
+        }
+
+        if let Some(options) = &native_options.record_session {
+            match epi::recording::Recorder::new(options.clone(), &egui_ctx.memory()) {
+                Ok(recorder) => {
+                    return Self::Recording {
+                        recorder,
+                        last_save: instant::Instant::now(),
+                    };
+                }
+                Err(err) => {
+                    eprintln!("Failed to start recording to {:?}: {}", options.path, err);
+                }
+            }
+        }
+
+        Self::Live
+    }
+
+    fn save(&mut self) {
+        if let Self::Recording {
+            recorder,
+            last_save,
+        } = self
+        {
+            if let Err(err) = recorder.flush() {
+                eprintln!(
+                    "Failed to save recording to {:?}: {}",
+                    recorder.options().path,
+                    err
+                );
+            }
+            *last_save = instant::Instant::now();
+        }
+    }
+
+    fn maybe_save(&mut self, interval: std::time::Duration) {
+        if let Self::Recording { last_save, .. } = self {
+            if instant::Instant::now() - *last_save > interval {
+                self.save();
+            }
+        }
+    }
+
+    fn on_event(&mut self, event: &winit::event::WindowEvent<'_>) {
+        use winit::event::{ElementState, KeyboardInput, VirtualKeyCode, WindowEvent};
+        if let (
+            Self::Replaying { replayer, .. },
+            WindowEvent::KeyboardInput {
+                input:
+                    KeyboardInput {
+                        state: ElementState::Pressed,
+                        virtual_keycode: Some(key),
+                        ..
+                    },
+                ..
+            },
+        ) = (self, event)
+        {
+            match key {
+                VirtualKeyCode::F9 => replayer.set_playing(!replayer.is_playing()),
+                VirtualKeyCode::F10 => replayer.step(),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Paint what [`epi::recording::Replayer::describe`] says in the top left corner.
+#[cfg(feature = "recording")]
+fn replay_inspector(
+    egui_ctx: &egui::Context,
+    replayer: &epi::recording::Replayer,
+) -> Vec<egui::epaint::ClippedShape> {
+    let description = format!("{}\nF9: play/pause   F10: step", replayer.describe());
+    let galley = egui_ctx.fonts().layout(
+        description,
+        egui::TextStyle::Monospace,
+        egui::Color32::WHITE,
+        f32::INFINITY,
+    );
+    let rect = egui::Rect::from_min_size(egui::pos2(8.0, 8.0), galley.size()).expand(4.0);
+    vec![
+        egui::epaint::ClippedShape(
+            egui::Rect::EVERYTHING,
+            egui::Shape::rect_filled(rect, 4.0, egui::Color32::from_black_alpha(200)),
+        ),
+        egui::epaint::ClippedShape(
+            egui::Rect::EVERYTHING,
+            egui::Shape::galley(egui::pos2(8.0, 8.0), galley),
+        ),
+    ]
+}
+
+/// What to paint in an extra native window, see [`epi::Frame::show_window`].
+///
+/// The integration should create a window for each new `id`,
+/// and close the windows that are no longer returned by [`EpiIntegration::update_with_windows`].
+pub struct ExtraWindowOutput {
+    pub id: egui::Id,
+    pub options: epi::WindowOptions,
+    /// Use this to tessellate the shapes and to get the font image of the window.
+    pub egui_ctx: egui::CtxRef,
+    pub shapes: Vec<egui::epaint::ClippedShape>,
+    pub needs_repaint: bool,
+}
+
 /// Everything needed to make a winit-based integration for [`epi`].
 pub struct EpiIntegration {
     frame: epi::Frame,
     persistence: crate::epi::Persistence,
     pub egui_ctx: egui::CtxRef,
     egui_winit: crate::State,
+    /// The input state of each extra native window.
+    extra_windows: std::collections::HashMap<egui::Id, crate::State>,
+    /// The main window was focused, so bring any modal window back to the front.
+    focus_modal_window: bool,
+    /// The window moved or changed scale factor, so [`epi::IntegrationInfo::monitors`] needs a refresh.
+    monitors_outdated: bool,
     pub app: Box<dyn epi::App>,
     /// When set, it is time to quit
     quit: bool,
+    #[cfg(feature = "recording")]
+    session: Session,
+    #[cfg(feature = "theme_files")]
+    theme_watcher: Option<crate::theme_watcher::ThemeWatcher>,
 }
 
 impl EpiIntegration {
+    /// Set `supports_extra_windows` if the integration paints the windows
+    /// returned by [`Self::update_with_windows`].
     pub fn new(
         integration_name: &'static str,
+        supports_extra_windows: bool,
+        #[cfg_attr(
+            not(any(feature = "recording", feature = "theme_files")),
+            allow(unused_variables)
+        )]
+        native_options: &epi::NativeOptions,
         window: &winit::window::Window,
         repaint_signal: std::sync::Arc<dyn epi::backend::RepaintSignal>,
         persistence: crate::epi::Persistence,
@@ -210,6 +492,12 @@ impl EpiIntegration {
 
         *egui_ctx.memory() = persistence.load_memory().unwrap_or_default();
 
+        #[cfg(feature = "theme_files")]
+        let theme_watcher = native_options.theme_file.as_ref().map(|path| {
+            let repaint_signal = repaint_signal.clone();
+            crate::theme_watcher::ThemeWatcher::new(path, move || repaint_signal.request_repaint())
+        });
+
         let frame = epi::Frame::new(epi::backend::FrameData {
             info: epi::IntegrationInfo {
                 name: integration_name,
@@ -217,9 +505,18 @@ impl EpiIntegration {
                 prefer_dark_mode: None, // TODO: figure out system default
                 cpu_usage: None,
                 native_pixels_per_point: Some(crate::native_pixels_per_point(window)),
+                window_info: Some(window_info(window)),
+                monitors: monitors(window),
             },
             output: Default::default(),
             repaint_signal,
+            windows: if supports_extra_windows {
+                Some(Default::default())
+            } else {
+                None
+            },
+            desktop: Some(std::sync::Arc::new(crate::desktop::SystemDesktop::default())),
+            desktop_responses: Default::default(),
         });
 
         let mut slf = Self {
@@ -227,8 +524,15 @@ impl EpiIntegration {
             persistence,
             egui_ctx,
             egui_winit: crate::State::new(window),
+            extra_windows: Default::default(),
+            focus_modal_window: false,
+            monitors_outdated: false,
             app,
             quit: false,
+            #[cfg(feature = "recording")]
+            session: Session::Live,
+            #[cfg(feature = "theme_files")]
+            theme_watcher,
         };
 
         slf.setup(window);
@@ -236,6 +540,11 @@ impl EpiIntegration {
             slf.warm_up(window);
         }
 
+        #[cfg(feature = "recording")]
+        {
+            slf.session = Session::new(native_options, &slf.egui_ctx);
+        }
+
         slf
     }
 
@@ -266,10 +575,38 @@ impl EpiIntegration {
     pub fn on_event(&mut self, event: &winit::event::WindowEvent<'_>) {
         use winit::event::WindowEvent;
         self.quit |= matches!(event, WindowEvent::CloseRequested | WindowEvent::Destroyed);
+        self.focus_modal_window |= matches!(event, WindowEvent::Focused(true));
+        self.monitors_outdated |= matches!(
+            event,
+            WindowEvent::Moved(_) | WindowEvent::ScaleFactorChanged { .. }
+        );
         self.egui_winit.on_event(&self.egui_ctx, event);
+        #[cfg(feature = "recording")]
+        self.session.on_event(event);
+    }
+
+    /// Handle an event for the extra native window with the given id.
+    pub fn on_extra_window_event(&mut self, id: egui::Id, event: &winit::event::WindowEvent<'_>) {
+        use winit::event::WindowEvent;
+        let mut frame_data = self.frame.lock();
+        let window = match frame_data
+            .windows
+            .as_mut()
+            .and_then(|windows| windows.get_mut(&id))
+        {
+            Some(window) => window,
+            None => return,
+        };
+        if matches!(event, WindowEvent::CloseRequested | WindowEvent::Destroyed) {
+            window.close_requested = true;
+        } else if let Some(state) = self.extra_windows.get_mut(&id) {
+            state.on_event(&window.egui_ctx, event);
+        }
     }
 
     /// Returns `needs_repaint` and shapes to paint.
+    ///
+    /// Any extra native windows the app shows are ignored, see [`Self::update_with_windows`].
     pub fn update(
         &mut self,
         window: &winit::window::Window,
@@ -277,15 +614,97 @@ impl EpiIntegration {
         bool,
         epi::backend::TexAllocationData,
         Vec<egui::epaint::ClippedShape>,
+    ) {
+        let (needs_repaint, tex_allocation_data, shapes, _) = self.update_with_windows(window, &[]);
+        (needs_repaint, tex_allocation_data, shapes)
+    }
+
+    /// Like [`Self::update`], but also returns what to paint in the extra native windows
+    /// shown with [`epi::Frame::show_window`].
+    ///
+    /// `extra_windows` are the windows the integration has created so far.
+    pub fn update_with_windows(
+        &mut self,
+        window: &winit::window::Window,
+        extra_windows: &[(egui::Id, &winit::window::Window)],
+    ) -> (
+        bool,
+        epi::backend::TexAllocationData,
+        Vec<egui::epaint::ClippedShape>,
+        Vec<ExtraWindowOutput>,
     ) {
         let frame_start = instant::Instant::now();
 
-        let raw_input = self.egui_winit.take_egui_input(window);
+        {
+            let info = &mut self.frame.lock().info;
+            info.window_info = Some(window_info(window));
+            if std::mem::take(&mut self.monitors_outdated) {
+                info.monitors = monitors(window);
+            }
+        }
+
+        #[cfg(feature = "theme_files")]
+        if let Some(theme_watcher) = &self.theme_watcher {
+            theme_watcher.apply_if_changed(&self.egui_ctx);
+        }
+
+        let mut raw_input = self.egui_winit.take_egui_input(window);
+        if self.begin_extra_windows(window, extra_windows) {
+            // A modal window is open, so the main window only gets the time and size.
+            raw_input.events.clear();
+            raw_input.hovered_files.clear();
+            raw_input.dropped_files.clear();
+        }
+
+        #[cfg(feature = "recording")]
+        let (raw_input, recorded_input) = match &mut self.session {
+            Session::Live => (raw_input, None),
+            Session::Recording { .. } => (raw_input.clone(), Some(raw_input)),
+            Session::Replaying { replayer, shapes } => {
+                if let Some(replayed_input) = replayer.next_input() {
+                    (replayed_input, None)
+                } else {
+                    // Paused or finished: show the last replayed frame again.
+                    let mut shapes = shapes.clone();
+                    shapes.extend(replay_inspector(&self.egui_ctx, replayer));
+                    return (false, Default::default(), shapes, vec![]);
+                }
+            }
+        };
+
         let (egui_output, shapes) = self.egui_ctx.run(raw_input, |egui_ctx| {
             self.app.update(egui_ctx, &self.frame);
         });
 
+        #[cfg(feature = "recording")]
+        let (needs_repaint, shapes) = match &mut self.session {
+            Session::Live => (egui_output.needs_repaint, shapes),
+            Session::Recording { recorder, .. } => {
+                if let Some(raw_input) = &recorded_input {
+                    if let Err(err) = recorder.record_frame(raw_input, &egui_output) {
+                        eprintln!(
+                            "Failed to record frame to {:?}: {}",
+                            recorder.options().path,
+                            err
+                        );
+                    }
+                }
+                (egui_output.needs_repaint, shapes)
+            }
+            Session::Replaying {
+                replayer,
+                shapes: replayed_shapes,
+            } => {
+                replayer.check_output(&egui_output);
+                *replayed_shapes = shapes.clone();
+                let mut shapes = shapes;
+                shapes.extend(replay_inspector(&self.egui_ctx, replayer));
+                (replayer.is_playing(), shapes)
+            }
+        };
+        #[cfg(not(feature = "recording"))]
         let needs_repaint = egui_output.needs_repaint;
+
         self.egui_winit
             .handle_output(window, &self.egui_ctx, egui_output);
 
@@ -294,20 +713,162 @@ impl EpiIntegration {
         let tex_allocation_data =
             crate::epi::handle_app_output(window, self.egui_ctx.pixels_per_point(), app_output);
 
+        let extra_window_outputs = self.end_extra_windows(extra_windows);
+
         let frame_time = (instant::Instant::now() - frame_start).as_secs_f64() as f32;
         self.frame.lock().info.cpu_usage = Some(frame_time);
 
-        (needs_repaint, tex_allocation_data, shapes)
+        (
+            needs_repaint,
+            tex_allocation_data,
+            shapes,
+            extra_window_outputs,
+        )
+    }
+
+    /// Give each extra native window its input for this frame.
+    ///
+    /// Returns `true` if a modal window is open.
+    fn begin_extra_windows(
+        &mut self,
+        window: &winit::window::Window,
+        extra_windows: &[(egui::Id, &winit::window::Window)],
+    ) -> bool {
+        let style = self.egui_ctx.style();
+        let mut frame_data = self.frame.lock();
+        let windows = match &mut frame_data.windows {
+            Some(windows) => windows,
+            None => return false,
+        };
+
+        let focus_modal_window = std::mem::take(&mut self.focus_modal_window);
+        let mut any_modal = false;
+        for (id, extra_window) in windows.iter_mut() {
+            let egui_ctx = &extra_window.egui_ctx;
+
+            // Keep up with font changes of the main window.
+            // Every window in `windows` has been run by `show_window`, so it has fonts.
+            let fonts = self.egui_ctx.fonts().definitions();
+            if egui_ctx.fonts().definitions() != fonts {
+                egui_ctx.set_fonts(fonts.clone());
+            }
+            egui_ctx.set_style(style.clone());
+
+            let native_window = extra_windows.iter().find(|(window_id, _)| window_id == id);
+            if let Some((_, native_window)) = native_window {
+                let state = self
+                    .extra_windows
+                    .entry(*id)
+                    .or_insert_with(|| crate::State::new(native_window));
+                extra_window.raw_input = state.take_egui_input(native_window);
+                if extra_window.options.modal && focus_modal_window {
+                    native_window.focus_window();
+                }
+            } else {
+                // Not created by the integration yet, so we don't know its size or scale.
+                extra_window.raw_input = epi::backend::NativeWindow::initial_input(
+                    &extra_window.options,
+                    Some(crate::native_pixels_per_point(window)),
+                );
+            }
+            extra_window.shown = false;
+            any_modal |= extra_window.options.modal;
+        }
+        any_modal
+    }
+
+    /// Close the extra native windows that weren't shown this frame, and collect what to paint in the others.
+    fn end_extra_windows(
+        &mut self,
+        extra_windows: &[(egui::Id, &winit::window::Window)],
+    ) -> Vec<ExtraWindowOutput> {
+        let mut frame_data = self.frame.lock();
+        let windows = match &mut frame_data.windows {
+            Some(windows) => windows,
+            None => return vec![],
+        };
+
+        windows.retain(|_, extra_window| extra_window.shown);
+        let states = &mut self.extra_windows;
+        states.retain(|id, _| windows.contains_key(id));
+
+        let mut outputs = vec![];
+        for (id, extra_window) in windows.iter_mut() {
+            let (egui_output, shapes) = match extra_window.output.take() {
+                Some(output) => output,
+                None => continue,
+            };
+            let needs_repaint = egui_output.needs_repaint;
+            let native_window = extra_windows.iter().find(|(window_id, _)| window_id == id);
+            if let (Some((_, native_window)), Some(state)) = (native_window, states.get_mut(id)) {
+                state.handle_output(native_window, &extra_window.egui_ctx, egui_output);
+            }
+            outputs.push(ExtraWindowOutput {
+                id: *id,
+                options: extra_window.options.clone(),
+                egui_ctx: extra_window.egui_ctx.clone(),
+                shapes,
+                needs_repaint,
+            });
+        }
+        outputs
     }
 
     pub fn maybe_autosave(&mut self, window: &winit::window::Window) {
+        #[cfg(feature = "recording")]
+        {
+            if matches!(self.session, Session::Replaying { .. }) {
+                return;
+            }
+            self.session.maybe_save(self.app.auto_save_interval());
+        }
         self.persistence
             .maybe_autosave(&mut *self.app, &self.egui_ctx, window);
     }
 
     pub fn on_exit(&mut self, window: &winit::window::Window) {
         self.app.on_exit();
+        #[cfg(feature = "recording")]
+        {
+            if matches!(self.session, Session::Replaying { .. }) {
+                return;
+            }
+            self.session.save();
+        }
         self.persistence
             .save(&mut *self.app, &self.egui_ctx, window);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::dpi::{PhysicalPosition, PhysicalSize};
+
+    #[test]
+    fn monitors_are_in_points() {
+        let monitor = monitor_info(
+            Some("HiDPI".to_owned()),
+            PhysicalPosition::new(3840, -200),
+            PhysicalSize::new(2560, 1600),
+            2.0,
+            true,
+        );
+        assert_eq!(monitor.name.as_deref(), Some("HiDPI"));
+        assert_eq!(monitor.position, egui::pos2(1920.0, -100.0));
+        assert_eq!(monitor.size, egui::vec2(1280.0, 800.0));
+        assert_eq!(monitor.scale_factor, 2.0);
+        assert!(monitor.is_current);
+
+        let monitor = monitor_info(
+            None,
+            PhysicalPosition::new(0, 0),
+            PhysicalSize::new(1920, 1080),
+            1.0,
+            false,
+        );
+        assert_eq!(monitor.position, egui::Pos2::ZERO);
+        assert_eq!(monitor.size, egui::vec2(1920.0, 1080.0));
+        assert!(!monitor.is_current);
+    }
+}
@@ -87,8 +87,12 @@ pub use winit;
 
 pub mod clipboard;
 pub mod screen_reader;
+#[cfg(feature = "theme_files")]
+pub mod theme_watcher;
 mod window_settings;
 
+#[cfg(feature = "epi")]
+pub mod desktop;
 #[cfg(feature = "epi")]
 pub mod epi;
 
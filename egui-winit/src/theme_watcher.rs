@@ -0,0 +1,85 @@
+//! Watch an [`egui::Theme`] file, and re-apply it whenever it changes.
+
+use std::path::{Path, PathBuf};
+use std::sync::{
+    atomic::{AtomicBool, Ordering::SeqCst},
+    Arc,
+};
+use std::time::SystemTime;
+
+/// How often the file is checked for changes.
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Watches a theme file written by [`egui::Theme::save`] (or by hand).
+///
+/// A background thread checks the modification time of the file,
+/// and [`Self::apply_if_changed`] loads and applies the theme once it has changed.
+/// The thread stops when the [`ThemeWatcher`] is dropped.
+pub struct ThemeWatcher {
+    path: PathBuf,
+    changed: Arc<AtomicBool>,
+}
+
+impl ThemeWatcher {
+    /// Start watching `path`.
+    ///
+    /// `on_change` is called from the background thread when the file changes,
+    /// e.g. to request a repaint so that [`Self::apply_if_changed`] is called soon.
+    pub fn new(path: impl Into<PathBuf>, on_change: impl Fn() + Send + 'static) -> Self {
+        let path = path.into();
+        // So that the first call to `apply_if_changed` loads the theme:
+        let changed = Arc::new(AtomicBool::new(true));
+
+        let watched_path = path.clone();
+        let weak_changed = Arc::downgrade(&changed);
+        std::thread::Builder::new()
+            .name("egui_theme_watcher".to_owned())
+            .spawn(move || {
+                let mut last_modified = modified(&watched_path);
+                while weak_changed.strong_count() > 0 {
+                    std::thread::sleep(POLL_INTERVAL);
+                    let modified = modified(&watched_path);
+                    if modified != last_modified {
+                        last_modified = modified;
+                        if let Some(changed) = weak_changed.upgrade() {
+                            changed.store(true, SeqCst);
+                            on_change();
+                        }
+                    }
+                }
+            })
+            .expect("Failed to spawn theme watcher thread");
+
+        Self { path, changed }
+    }
+
+    /// The watched file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// If the file has changed since the last call (or this is the first call), load it and apply it to `egui_ctx`.
+    ///
+    /// A theme that fails to load is reported on stderr, and the current theme is kept.
+    pub fn apply_if_changed(&self, egui_ctx: &egui::Context) {
+        if !self.changed.swap(false, SeqCst) {
+            return;
+        }
+        match egui::Theme::load(&self.path) {
+            Ok(theme) => {
+                if let Err(err) = egui_ctx.set_theme(&theme) {
+                    eprintln!("Failed to apply theme from {:?}: {}", self.path, err);
+                }
+            }
+            Err(err) => {
+                eprintln!("Failed to load theme from {:?}: {}", self.path, err);
+            }
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
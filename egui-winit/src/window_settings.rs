@@ -1,4 +1,4 @@
-/// Can be used to store native window settings (position and size).
+/// Can be used to store native window settings (position, size, fullscreen and maximized).
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct WindowSettings {
@@ -6,6 +6,10 @@ pub struct WindowSettings {
     inner_pos: Option<egui::Pos2>,
     /// Inner size of window in logical pixels
     inner_size_points: Option<egui::Vec2>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    fullscreen: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    maximized: bool,
 }
 
 impl WindowSettings {
@@ -22,6 +26,9 @@ impl WindowSettings {
                 inner_size_points.width as f32,
                 inner_size_points.height as f32,
             )),
+
+            fullscreen: window.fullscreen().is_some(),
+            maximized: window.is_maximized(),
         }
     }
 
@@ -43,6 +50,13 @@ impl WindowSettings {
             }
         }
 
+        if self.fullscreen {
+            window = window.with_fullscreen(Some(winit::window::Fullscreen::Borderless(None)));
+        }
+        if self.maximized {
+            window = window.with_maximized(true);
+        }
+
         if let Some(inner_size_points) = self.inner_size_points {
             window.with_inner_size(winit::dpi::LogicalSize {
                 width: inner_size_points.x as f64,
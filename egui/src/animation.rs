@@ -0,0 +1,390 @@
+//! Animating values over time, see [`crate::Context::animate_value`] and [`crate::Context::animate_keyframes`].
+//!
+//! Animations are driven by [`crate::RawInput::time`], so they are deterministic given the input.
+
+use std::sync::Arc;
+
+use crate::{Color32, Pos2, Rect, Rgba, Vec2};
+
+/// How an animation moves from one value to the next.
+///
+/// All easing functions start at `0.0` and end at `1.0`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    /// Constant speed.
+    Linear,
+    /// Start slow, then speed up (quadratic).
+    EaseIn,
+    /// Start fast, then slow down (quadratic).
+    EaseOut,
+    /// Start slow, speed up, then slow down (quadratic).
+    EaseInOut,
+    /// Like [`Self::EaseIn`], but more pronounced.
+    CubicIn,
+    /// Like [`Self::EaseOut`], but more pronounced.
+    CubicOut,
+    /// Like [`Self::EaseInOut`], but more pronounced.
+    CubicInOut,
+    /// Overshoot and bounce back, like a spring.
+    Spring {
+        /// How quickly the bouncing dies down. Higher is less bouncy.
+        damping: f32,
+        /// How many times it bounces back and forth during the animation.
+        frequency: f32,
+    },
+}
+
+impl Default for Easing {
+    fn default() -> Self {
+        Self::EaseInOut
+    }
+}
+
+impl Easing {
+    /// A spring that bounces a little.
+    pub fn spring() -> Self {
+        Self::Spring {
+            damping: 6.0,
+            frequency: 1.5,
+        }
+    }
+
+    /// Map the progress `t` of an animation (`0.0..=1.0`) to how far along the value should be.
+    ///
+    /// Can go outside of `0.0..=1.0` for [`Self::Spring`].
+    pub fn apply(self, t: f32) -> f32 {
+        if t <= 0.0 {
+            return 0.0;
+        }
+        if t >= 1.0 {
+            return 1.0;
+        }
+        match self {
+            Self::Linear => t,
+            Self::EaseIn => t * t,
+            Self::EaseOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - 2.0 * (1.0 - t) * (1.0 - t)
+                }
+            }
+            Self::CubicIn => t * t * t,
+            Self::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Self::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - 4.0 * (1.0 - t).powi(3)
+                }
+            }
+            Self::Spring { damping, frequency } => {
+                1.0 - (-damping * t).exp() * (std::f32::consts::TAU * frequency * t).cos()
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A value that can be animated.
+pub trait Animatable: Clone + PartialEq + Send + Sync + 'static {
+    /// `t = 0.0` returns `self`, `t = 1.0` returns `other`.
+    ///
+    /// `t` can be outside of `0.0..=1.0` (see [`Easing::Spring`]).
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+
+impl Animatable for f32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Animatable for Vec2 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        *self + (*other - *self) * t
+    }
+}
+
+impl Animatable for Pos2 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        *self + (*other - *self) * t
+    }
+}
+
+impl Animatable for Rect {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Rect::from_min_max(self.min.lerp(&other.min, t), self.max.lerp(&other.max, t))
+    }
+}
+
+impl Animatable for Rgba {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        *self * (1.0 - t) + *other * t
+    }
+}
+
+/// Interpolated in linear space, like [`Rgba`].
+impl Animatable for Color32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0); // Can't overshoot a color
+        Rgba::from(*self).lerp(&Rgba::from(*other), t).into()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone)]
+struct Segment<T> {
+    target: T,
+    duration: f32,
+    easing: Easing,
+}
+
+/// A sequence of values to animate through, for [`crate::Context::animate_keyframes`].
+///
+/// ```
+/// # use egui::animation::{Easing, Keyframes};
+/// let keyframes = Keyframes::new(0.0)
+///     .then(1.0, 0.5, Easing::EaseOut)
+///     .then(0.0, 0.5, Easing::spring());
+/// assert_eq!(keyframes.duration(), 1.0);
+/// assert_eq!(keyframes.value_at(0.5), 1.0);
+/// ```
+#[derive(Clone)]
+pub struct Keyframes<T> {
+    start: T,
+    segments: Vec<Segment<T>>,
+    on_complete: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl<T: Animatable> Keyframes<T> {
+    /// Start at this value.
+    pub fn new(start: T) -> Self {
+        Self {
+            start,
+            segments: vec![],
+            on_complete: None,
+        }
+    }
+
+    /// Then animate to `target` over `duration` seconds.
+    pub fn then(mut self, target: T, duration: f32, easing: Easing) -> Self {
+        self.segments.push(Segment {
+            target,
+            duration,
+            easing,
+        });
+        self
+    }
+
+    /// Called once, from within the call to [`crate::Context::animate_keyframes`]
+    /// that reaches the last value.
+    pub fn on_complete(mut self, on_complete: impl Fn() + Send + Sync + 'static) -> Self {
+        self.on_complete = Some(Arc::new(on_complete));
+        self
+    }
+
+    /// Total length in seconds.
+    pub fn duration(&self) -> f32 {
+        self.segments.iter().map(|segment| segment.duration).sum()
+    }
+
+    /// The value the animation ends at.
+    pub fn last_value(&self) -> &T {
+        self.segments
+            .last()
+            .map_or(&self.start, |segment| &segment.target)
+    }
+
+    /// The value `time` seconds after the start.
+    pub fn value_at(&self, mut time: f32) -> T {
+        let mut from = &self.start;
+        if time <= 0.0 {
+            return from.clone();
+        }
+        for segment in &self.segments {
+            if time < segment.duration {
+                return from.lerp(
+                    &segment.target,
+                    segment.easing.apply(time / segment.duration),
+                );
+            }
+            time -= segment.duration;
+            from = &segment.target;
+        }
+        from.clone()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// An animation that has been started, stored in the `AnimationManager` until it is done.
+pub(crate) struct Playing<T> {
+    keyframes: Keyframes<T>,
+    start_time: f64,
+}
+
+impl<T: Animatable> Playing<T> {
+    pub fn new(keyframes: Keyframes<T>, start_time: f64) -> Self {
+        Self {
+            keyframes,
+            start_time,
+        }
+    }
+
+    pub fn keyframes(&self) -> &Keyframes<T> {
+        &self.keyframes
+    }
+
+    /// `time_offset` is added to the time since the start,
+    /// so that we don't show the start value on the frame an animation starts.
+    pub fn value_at(&self, time: f64, time_offset: f32) -> T {
+        self.keyframes
+            .value_at((time - self.start_time) as f32 + time_offset)
+    }
+
+    /// The `AnimationManager` drops the animation once it is no longer `in_progress`,
+    /// so `on_complete` is only returned once.
+    pub fn advance(&self, time: f64, time_offset: f32) -> AnimationFrame<T> {
+        let elapsed = (time - self.start_time) as f32 + time_offset;
+        let in_progress = elapsed < self.keyframes.duration();
+        AnimationFrame {
+            value: self.keyframes.value_at(elapsed),
+            in_progress,
+            on_complete: if in_progress {
+                None
+            } else {
+                self.keyframes.on_complete.clone()
+            },
+        }
+    }
+}
+
+/// The state of an animation this frame.
+pub(crate) struct AnimationFrame<T> {
+    pub value: T,
+    pub in_progress: bool,
+    /// To be called once the lock on the `AnimationManager` is released.
+    pub on_complete: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl<T> AnimationFrame<T> {
+    /// An animation that is done.
+    pub fn settled(value: T) -> Self {
+        Self {
+            value,
+            in_progress: false,
+            on_complete: None,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CtxRef, Id, RawInput};
+
+    fn run_at(ctx: &mut CtxRef, time: f64, add_contents: impl FnOnce(&CtxRef)) -> bool {
+        let raw_input = RawInput {
+            time: Some(time),
+            predicted_dt: 0.0,
+            ..Default::default()
+        };
+        let (output, _) = ctx.run(raw_input, add_contents);
+        output.needs_repaint
+    }
+
+    #[test]
+    fn easing_end_points() {
+        for easing in [
+            Easing::Linear,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::CubicIn,
+            Easing::CubicOut,
+            Easing::CubicInOut,
+            Easing::spring(),
+        ] {
+            assert_eq!(easing.apply(0.0), 0.0);
+            assert_eq!(easing.apply(1.0), 1.0);
+        }
+    }
+
+    #[test]
+    fn animate_value_towards_new_target() {
+        let mut ctx = CtxRef::default();
+        let id = Id::new("value");
+        let mut value = 0.0;
+
+        // The first value is shown right away:
+        run_at(&mut ctx, 0.0, |ctx| {
+            value = ctx.animate_value(id, 10.0, 1.0, Easing::Linear);
+        });
+        assert_eq!(value, 10.0);
+
+        run_at(&mut ctx, 1.0, |ctx| {
+            value = ctx.animate_value(id, 20.0, 1.0, Easing::Linear);
+        });
+        assert_eq!(value, 10.0);
+
+        let repaint = run_at(&mut ctx, 1.5, |ctx| {
+            value = ctx.animate_value(id, 20.0, 1.0, Easing::Linear);
+        });
+        assert_eq!(value, 15.0);
+        assert!(repaint, "should repaint while animating");
+
+        run_at(&mut ctx, 2.5, |ctx| {
+            value = ctx.animate_value(id, 20.0, 1.0, Easing::Linear);
+        });
+        assert_eq!(value, 20.0);
+
+        // `request_repaint` repaints one extra frame, but no more than that:
+        let repaint = run_at(&mut ctx, 3.0, |ctx| {
+            value = ctx.animate_value(id, 20.0, 1.0, Easing::Linear);
+        });
+        assert!(!repaint, "should not repaint once done");
+    }
+
+    #[test]
+    fn keyframes_complete_once() {
+        let completions = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let keyframes = {
+            let completions = completions.clone();
+            Keyframes::new(Pos2::ZERO)
+                .then(crate::pos2(10.0, 0.0), 1.0, Easing::Linear)
+                .then(crate::pos2(10.0, 10.0), 1.0, Easing::Linear)
+                .on_complete(move || {
+                    completions.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+                })
+        };
+        let id = Id::new("keyframes");
+        let mut ctx = CtxRef::default();
+        let mut pos = Pos2::ZERO;
+
+        for (time, expected) in [
+            (10.0, crate::pos2(0.0, 0.0)),
+            (10.5, crate::pos2(5.0, 0.0)),
+            (11.5, crate::pos2(10.0, 5.0)),
+            (12.0, crate::pos2(10.0, 10.0)),
+            (13.0, crate::pos2(10.0, 10.0)),
+        ] {
+            run_at(&mut ctx, time, |ctx| {
+                pos = ctx.animate_keyframes(id, &keyframes);
+            });
+            assert_eq!(pos, expected);
+        }
+        assert_eq!(completions.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        ctx.restart_animation(id);
+        run_at(&mut ctx, 20.0, |ctx| {
+            pos = ctx.animate_keyframes(id, &keyframes);
+        });
+        assert_eq!(pos, Pos2::ZERO);
+    }
+}
@@ -1,8 +1,18 @@
-use crate::{emath::remap_clamp, Id, IdMap, InputState};
+use std::any::Any;
 
-#[derive(Clone, Default)]
+use crate::{
+    animation::{Animatable, AnimationFrame, Easing, Keyframes, Playing},
+    emath::remap_clamp,
+    Id, IdMap, InputState,
+};
+
+#[derive(Default)]
 pub(crate) struct AnimationManager {
     bools: IdMap<BoolAnim>,
+    /// A `Playing<T>` for each animation that is in progress.
+    playing: IdMap<Box<dyn Any + Send + Sync>>,
+    /// The last value (a `T`) of each animation that is done.
+    settled: IdMap<Box<dyn Any + Send + Sync>>,
 }
 
 #[derive(Clone, Debug)]
@@ -56,4 +66,153 @@ impl AnimationManager {
             }
         }
     }
+
+    /// See `Context::animate_value` for documentation
+    pub fn animate_value<T: Animatable>(
+        &mut self,
+        input: &InputState,
+        id: Id,
+        target: T,
+        duration: f32,
+        easing: Easing,
+    ) -> AnimationFrame<T> {
+        if let Some(playing) = self.playing_mut::<T>(id) {
+            if playing.keyframes().last_value() != &target {
+                let current = playing.value_at(input.time, input.predicted_dt);
+                *playing = Playing::new(
+                    Keyframes::new(current).then(target, duration, easing),
+                    input.time,
+                );
+            }
+            return self.advance(input, id);
+        }
+
+        match self.settled_value::<T>(id) {
+            Some(current) if current == &target => AnimationFrame::settled(target),
+            Some(current) => {
+                let keyframes = Keyframes::new(current.clone()).then(target, duration, easing);
+                self.play(input, id, keyframes)
+            }
+            None => {
+                self.settled.insert(id, Box::new(target.clone()));
+                AnimationFrame::settled(target)
+            }
+        }
+    }
+
+    /// See `Context::animate_keyframes` for documentation
+    pub fn animate_keyframes<T: Animatable>(
+        &mut self,
+        input: &InputState,
+        id: Id,
+        keyframes: &Keyframes<T>,
+    ) -> AnimationFrame<T> {
+        if self.playing_mut::<T>(id).is_some() {
+            self.advance(input, id)
+        } else if let Some(value) = self.settled_value::<T>(id) {
+            AnimationFrame::settled(value.clone())
+        } else {
+            self.play(input, id, keyframes.clone())
+        }
+    }
+
+    /// Forget the animation, so that it starts over the next time it is used.
+    pub fn restart(&mut self, id: Id) {
+        self.bools.remove(&id);
+        self.playing.remove(&id);
+        self.settled.remove(&id);
+    }
+
+    /// The animation with the given id, if it animates a `T`.
+    fn playing_mut<T: Animatable>(&mut self, id: Id) -> Option<&mut Playing<T>> {
+        self.playing.get_mut(&id)?.downcast_mut()
+    }
+
+    /// The last value of a finished animation, if it animated a `T`.
+    fn settled_value<T: Animatable>(&self, id: Id) -> Option<&T> {
+        self.settled.get(&id)?.downcast_ref()
+    }
+
+    fn play<T: Animatable>(
+        &mut self,
+        input: &InputState,
+        id: Id,
+        keyframes: Keyframes<T>,
+    ) -> AnimationFrame<T> {
+        self.settled.remove(&id);
+        self.playing
+            .insert(id, Box::new(Playing::new(keyframes, input.time)));
+        self.advance(input, id)
+    }
+
+    /// Once an animation is done, only its last value is kept (dropping the keyframes and `on_complete`).
+    fn advance<T: Animatable>(&mut self, input: &InputState, id: Id) -> AnimationFrame<T> {
+        let playing = self
+            .playing_mut::<T>(id)
+            .expect("the animation was just inserted");
+        let frame = playing.advance(input.time, input.predicted_dt);
+        if !frame.in_progress {
+            self.playing.remove(&id);
+            self.settled.insert(id, Box::new(frame.value.clone()));
+        }
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_at(time: f64) -> InputState {
+        let mut input = InputState::default();
+        input.time = time;
+        input.predicted_dt = 0.0;
+        input
+    }
+
+    #[test]
+    fn finished_animations_drop_their_keyframes() {
+        let mut manager = AnimationManager::default();
+        let id = Id::new("keyframes");
+        let keyframes = Keyframes::new(0.0_f32)
+            .then(1.0, 1.0, Easing::Linear)
+            .on_complete(|| {});
+
+        let frame = manager.animate_keyframes(&input_at(0.0), id, &keyframes);
+        assert!(frame.in_progress);
+        assert_eq!(manager.playing.len(), 1);
+
+        let frame = manager.animate_keyframes(&input_at(1.0), id, &keyframes);
+        assert!(!frame.in_progress);
+        assert!(frame.on_complete.is_some());
+        assert!(manager.playing.is_empty());
+
+        // Stays at the last value, without starting over:
+        let frame = manager.animate_keyframes(&input_at(2.0), id, &keyframes);
+        assert_eq!(frame.value, 1.0);
+        assert!(frame.on_complete.is_none());
+        assert!(manager.playing.is_empty());
+    }
+
+    #[test]
+    fn settled_value_animates_to_new_target() {
+        let mut manager = AnimationManager::default();
+        let id = Id::new("value");
+
+        let frame = manager.animate_value(&input_at(0.0), id, 0.0_f32, 1.0, Easing::Linear);
+        assert_eq!(frame.value, 0.0);
+        assert!(manager.playing.is_empty());
+
+        let frame = manager.animate_value(&input_at(1.0), id, 10.0, 1.0, Easing::Linear);
+        assert_eq!(frame.value, 0.0);
+        assert!(frame.in_progress);
+
+        let frame = manager.animate_value(&input_at(1.5), id, 10.0, 1.0, Easing::Linear);
+        assert_eq!(frame.value, 5.0);
+
+        let frame = manager.animate_value(&input_at(2.0), id, 10.0, 1.0, Easing::Linear);
+        assert_eq!(frame.value, 10.0);
+        assert!(!frame.in_progress);
+        assert!(manager.playing.is_empty());
+    }
 }
@@ -312,17 +312,20 @@ impl CollapsingHeader {
             .widget_info(|| WidgetInfo::labeled(WidgetType::CollapsingHeader, text.text()));
 
         if ui.is_rect_visible(rect) {
-            let visuals = ui
-                .style()
-                .interact_selectable(&header_response, self.selected);
+            let visuals = ui.style().interact_selectable_as(
+                WidgetType::CollapsingHeader,
+                &header_response,
+                self.selected,
+            );
 
             if ui.visuals().collapsing_header_frame || self.show_background {
                 ui.painter().add(epaint::RectShape {
                     rect: header_response.rect.expand(visuals.expansion),
                     corner_radius: visuals.corner_radius,
-                    fill: visuals.bg_fill,
+                    fill: visuals.bg_fill.into(),
                     stroke: visuals.bg_stroke,
                     // stroke: Default::default(),
+                    dash: None,
                 });
             }
 
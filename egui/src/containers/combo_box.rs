@@ -178,9 +178,9 @@ fn combo_box_dyn<'c, R>(
         if ui.is_rect_visible(rect) {
             let icon_rect = Align2::RIGHT_CENTER.align_size_within_rect(icon_size, rect);
             let visuals = if is_popup_open {
-                &ui.visuals().widgets.open
+                &ui.visuals().widgets_for(WidgetType::ComboBox).open
             } else {
-                ui.style().interact(&response)
+                ui.style().interact_as(WidgetType::ComboBox, &response)
             };
             paint_icon(ui.painter(), icon_rect.expand(visuals.expansion), visuals);
 
@@ -196,6 +196,7 @@ fn combo_box_dyn<'c, R>(
         ScrollArea::vertical()
             .max_height(ui.spacing().combo_height)
             .show(ui, menu_contents)
+            .inner
     });
 
     InnerResponse {
@@ -230,9 +231,9 @@ fn button_frame(
 
     if ui.is_rect_visible(outer_rect) {
         let visuals = if is_popup_open {
-            &ui.visuals().widgets.open
+            &ui.visuals().widgets_for(WidgetType::ComboBox).open
         } else {
-            ui.style().interact(&response)
+            ui.style().interact_as(WidgetType::ComboBox, &response)
         };
 
         ui.painter().set(
@@ -240,8 +241,9 @@ fn button_frame(
             epaint::RectShape {
                 rect: outer_rect.expand(visuals.expansion),
                 corner_radius: visuals.corner_radius,
-                fill: visuals.bg_fill,
+                fill: visuals.bg_fill.into(),
                 stroke: visuals.bg_stroke,
+                dash: None,
             },
         );
     }
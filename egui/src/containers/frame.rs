@@ -158,6 +158,28 @@ impl Frame {
         self.show_dyn(ui, Box::new(add_contents))
     }
 
+    /// Like [`Self::show`], with a [`StyleClass`] registered with [`Context::set_style_class`], e.g. `"toolbar"`.
+    ///
+    /// The [`StyleClass::fill`], [`StyleClass::stroke`] and [`StyleClass::corner_radius`] of the class
+    /// replace those of the frame, and the rest of the class applies to the contents of the frame
+    /// (see [`Ui::add_style_class`]).
+    pub fn show_with_class<R>(
+        mut self,
+        ui: &mut Ui,
+        name: &str,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        if let Some(class) = ui.ctx().style_class(name) {
+            self.fill = class.fill.unwrap_or(self.fill);
+            self.stroke = class.stroke.unwrap_or(self.stroke);
+            self.corner_radius = class.corner_radius.unwrap_or(self.corner_radius);
+        }
+        self.show(ui, |ui| {
+            ui.add_style_class(name);
+            add_contents(ui)
+        })
+    }
+
     fn show_dyn<'c, R>(
         self,
         ui: &mut Ui,
@@ -181,8 +203,9 @@ impl Frame {
         let frame_shape = Shape::Rect(epaint::RectShape {
             rect: outer_rect,
             corner_radius,
-            fill,
+            fill: fill.into(),
             stroke,
+            dash: None,
         });
 
         if shadow == Default::default() {
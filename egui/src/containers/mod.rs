@@ -6,10 +6,13 @@ pub(crate) mod area;
 pub(crate) mod collapsing_header;
 mod combo_box;
 pub(crate) mod frame;
+pub(crate) mod modal;
 pub mod panel;
 pub mod popup;
 pub(crate) mod resize;
 pub(crate) mod scroll_area;
+pub(crate) mod tab_bar;
+pub mod toasts;
 pub(crate) mod window;
 
 pub use {
@@ -17,9 +20,12 @@ pub use {
     collapsing_header::{CollapsingHeader, CollapsingResponse},
     combo_box::*,
     frame::Frame,
+    modal::Modal,
     panel::{CentralPanel, SidePanel, TopBottomPanel},
     popup::*,
     resize::Resize,
-    scroll_area::ScrollArea,
+    scroll_area::{ScrollArea, ScrollAreaOutput},
+    tab_bar::{Tab, TabBar, TabBarResponse, TabOverflow},
+    toasts::{Toast, ToastId, ToastKind, ToastSender, Toasts},
     window::Window,
 };
@@ -0,0 +1,191 @@
+//! A dialog that blocks the rest of the UI until it is closed.
+
+use crate::*;
+
+/// A dialog shown in the middle of the screen, on top of a backdrop that dims the rest of the UI.
+///
+/// While a modal is open, nothing below it can be hovered, clicked or focused,
+/// so Tab only moves the keyboard focus between the widgets of the modal.
+///
+/// Modals can be stacked: only the top-most one can be interacted with,
+/// and only the top-most one is closed by Escape or a click on the backdrop.
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// # let mut open = true;
+/// egui::Modal::new("confirm_quit").open(&mut open).show(ctx, |ui| {
+///     ui.label("Are you sure you want to quit?");
+/// });
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct Modal<'open> {
+    id: Id,
+    open: Option<&'open mut bool>,
+    close_on_escape: bool,
+    close_on_backdrop_click: bool,
+    backdrop_color: Color32,
+    frame: Option<Frame>,
+}
+
+impl<'open> Modal<'open> {
+    /// The id must be unique among all modals.
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id: Id::new(id_source),
+            open: None,
+            close_on_escape: true,
+            close_on_backdrop_click: false,
+            backdrop_color: Color32::from_black_alpha(100),
+            frame: None,
+        }
+    }
+
+    /// * If `*open == false`, the modal will not be visible.
+    /// * If the modal is closed with Escape or a click on the backdrop, `*open` will be set to `false`.
+    ///
+    /// Without this, the modal can only be closed by not calling [`Self::show`].
+    pub fn open(mut self, open: &'open mut bool) -> Self {
+        self.open = Some(open);
+        self
+    }
+
+    /// Close the modal when the user presses Escape. Requires [`Self::open`].
+    ///
+    /// Default: `true`.
+    pub fn close_on_escape(mut self, close_on_escape: bool) -> Self {
+        self.close_on_escape = close_on_escape;
+        self
+    }
+
+    /// Close the modal when the user clicks outside of it. Requires [`Self::open`].
+    ///
+    /// Default: `false`.
+    pub fn close_on_backdrop_click(mut self, close_on_backdrop_click: bool) -> Self {
+        self.close_on_backdrop_click = close_on_backdrop_click;
+        self
+    }
+
+    /// The color painted over the rest of the UI.
+    pub fn backdrop_color(mut self, backdrop_color: Color32) -> Self {
+        self.backdrop_color = backdrop_color;
+        self
+    }
+
+    /// Change the background color, margins, etc. Default: [`Frame::window`].
+    pub fn frame(mut self, frame: Frame) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+
+    /// Returns `None` if the modal is closed.
+    pub fn show<R>(
+        self,
+        ctx: &CtxRef,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        let Modal {
+            id,
+            open,
+            close_on_escape,
+            close_on_backdrop_click,
+            backdrop_color,
+            frame,
+        } = self;
+
+        if matches!(open, Some(false)) {
+            return None;
+        }
+
+        let backdrop_area = Area::new(id.with("backdrop"))
+            .order(Order::Foreground)
+            .fixed_pos(Pos2::ZERO);
+        let backdrop_layer_id = backdrop_area.layer();
+        let content_area = Area::new(id)
+            .order(Order::Foreground)
+            .anchor(Align2::CENTER_CENTER, Vec2::ZERO);
+        let content_layer_id = content_area.layer();
+
+        // Block everything below us, including widgets that are added later this frame:
+        ctx.memory().modals.register(backdrop_layer_id);
+
+        let backdrop_response = backdrop_area
+            .show(ctx, |ui| {
+                let screen_rect = ui.ctx().input().screen_rect();
+                ui.painter().rect_filled(screen_rect, 0.0, backdrop_color);
+                ui.interact(screen_rect, id.with("backdrop"), Sense::click())
+            })
+            .inner;
+
+        if backdrop_response.hovered() && ctx.input().pointer.any_pressed() {
+            // The backdrop is moved to the top when clicked, so keep the content above it:
+            ctx.memory().areas.move_to_top(content_layer_id);
+        }
+
+        let frame = frame.unwrap_or_else(|| Frame::window(&ctx.style()));
+        let inner_response = content_area.show(ctx, |ui| frame.show(ui, add_contents).inner);
+
+        if let Some(open) = open {
+            let is_top = ctx.memory().top_modal() == Some(backdrop_layer_id);
+            let escape_pressed = close_on_escape && ctx.input().key_pressed(Key::Escape);
+            let backdrop_clicked = close_on_backdrop_click && backdrop_response.clicked();
+            if is_top && (escape_pressed || backdrop_clicked) {
+                *open = false;
+            }
+        }
+
+        Some(inner_response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn run(
+        ctx: &mut CtxRef,
+        events: Vec<Event>,
+        open: &mut bool,
+        button_response: &mut Option<Response>,
+    ) {
+        let raw_input = RawInput {
+            events,
+            ..Default::default()
+        };
+        let _ = ctx.run(raw_input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                *button_response = Some(ui.button("Below"));
+            });
+            Modal::new("modal").open(open).show(ctx, |ui| {
+                ui.label("On top");
+            });
+        });
+    }
+
+    #[test]
+    fn modal_blocks_focus_and_closes_on_escape() {
+        let mut ctx = CtxRef::default();
+        let mut open = true;
+        let mut button_response = None;
+
+        run(&mut ctx, vec![], &mut open, &mut button_response);
+        let button = button_response.clone().unwrap();
+        ctx.memory().request_focus(button.id);
+        run(&mut ctx, vec![], &mut open, &mut button_response);
+        assert!(!ctx.memory().has_focus(button.id));
+        assert!(ctx.memory().is_blocked_by_modal(button.layer_id));
+
+        let escape = Event::Key {
+            key: Key::Escape,
+            pressed: true,
+            modifiers: Default::default(),
+        };
+        run(&mut ctx, vec![escape], &mut open, &mut button_response);
+        assert!(!open);
+
+        // Once the modal has been gone for a frame, nothing is blocked:
+        run(&mut ctx, vec![], &mut open, &mut button_response);
+        run(&mut ctx, vec![], &mut open, &mut button_response);
+        assert!(!ctx.memory().is_blocked_by_modal(button.layer_id));
+    }
+}
@@ -285,9 +285,43 @@ pub fn popup_below_widget<R>(
     popup_id: Id,
     widget_response: &Response,
     add_contents: impl FnOnce(&mut Ui) -> R,
+) -> Option<R> {
+    popup_below_widget_with(
+        ui,
+        popup_id,
+        widget_response,
+        PopupCloseBehavior::CloseOnClick,
+        add_contents,
+    )
+}
+
+/// When a popup shown with [`popup_below_widget_with`] closes by itself.
+///
+/// It always closes when pressing escape.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PopupCloseBehavior {
+    /// Close on any click that is not on the widget, including clicks inside the popup.
+    /// Good for menus, where picking something should close the popup.
+    CloseOnClick,
+
+    /// Close on clicks outside of both the widget and the popup.
+    /// Good for popups that take several clicks, like a calendar.
+    CloseOnClickOutside,
+}
+
+/// Like [`popup_below_widget`], but you decide when clicks close the popup.
+pub fn popup_below_widget_with<R>(
+    ui: &Ui,
+    popup_id: Id,
+    widget_response: &Response,
+    close_behavior: PopupCloseBehavior,
+    add_contents: impl FnOnce(&mut Ui) -> R,
 ) -> Option<R> {
     if ui.memory().is_popup_open(popup_id) {
-        let inner = Area::new(popup_id)
+        let InnerResponse {
+            inner,
+            response: popup_response,
+        } = Area::new(popup_id)
             .order(Order::Foreground)
             .fixed_pos(widget_response.rect.left_bottom())
             .show(ui.ctx(), |ui| {
@@ -304,10 +338,15 @@ pub fn popup_below_widget<R>(
                         .inner
                     })
                     .inner
-            })
-            .inner;
+            });
 
-        if ui.input().key_pressed(Key::Escape) || widget_response.clicked_elsewhere() {
+        let clicked_away = match close_behavior {
+            PopupCloseBehavior::CloseOnClick => widget_response.clicked_elsewhere(),
+            PopupCloseBehavior::CloseOnClickOutside => {
+                widget_response.clicked_elsewhere() && popup_response.clicked_elsewhere()
+            }
+        };
+        if ui.input().key_pressed(Key::Escape) || clicked_away {
             ui.memory().close_popup();
         }
         Some(inner)
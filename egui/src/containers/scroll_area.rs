@@ -5,7 +5,31 @@
 
 #![allow(clippy::needless_range_loop)]
 
-use crate::*;
+use crate::{animation::Easing, *};
+
+/// How long an animated scroll takes, in seconds.
+const SCROLL_ANIMATION_TIME: f32 = 0.2;
+
+/// How much velocity one point of mouse wheel scrolling adds when
+/// [`ScrollArea::scroll_momentum`] is on, in 1/seconds.
+const WHEEL_MOMENTUM_FACTOR: f32 = 8.0;
+
+/// An animated scroll along one axis, see [`ScrollArea::smooth_scrolling`] and [`Ui::scroll_to_rect`].
+#[derive(Clone, Copy, Debug)]
+struct ScrollAnimation {
+    from: f32,
+    to: f32,
+    start_time: f64,
+}
+
+impl ScrollAnimation {
+    /// Returns the offset at this time, and whether or not the animation is done.
+    fn offset_at(&self, time: f64) -> (f32, bool) {
+        let t = ((time - self.start_time) as f32 / SCROLL_ANIMATION_TIME).clamp(0.0, 1.0);
+        let offset = lerp(self.from..=self.to, Easing::EaseOut.apply(t));
+        (offset, t >= 1.0)
+    }
+}
 
 #[derive(Clone, Copy, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -27,6 +51,14 @@ pub(crate) struct State {
     /// and remains that way until the user moves the scroll_handle. Once unstuck (false)
     /// it remains false until the scroll touches the end position, which reenables stickiness.
     scroll_stuck_to_end: [bool; 2],
+
+    /// Ongoing animated scroll, if any.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    scroll_animation: [Option<ScrollAnimation>; 2],
+
+    /// The size of the visible part of the contents last frame.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    viewport_size: Vec2,
 }
 
 impl Default for State {
@@ -37,6 +69,8 @@ impl Default for State {
             vel: Vec2::ZERO,
             scroll_start_offset_from_top_left: [None; 2],
             scroll_stuck_to_end: [true; 2],
+            scroll_animation: [None; 2],
+            viewport_size: Vec2::ZERO,
         }
     }
 }
@@ -49,6 +83,58 @@ impl State {
     pub fn store(self, ctx: &Context, id: Id) {
         ctx.memory().data.insert_persisted(id, self);
     }
+
+    /// Start scrolling to `offset` along axis `d`, either right away or animated.
+    fn scroll_to(&mut self, d: usize, offset: f32, animated: bool, time: f64) {
+        if animated {
+            self.scroll_animation[d] = Some(ScrollAnimation {
+                from: self.offset[d],
+                to: offset,
+                start_time: time,
+            });
+        } else {
+            self.offset[d] = offset;
+            self.scroll_animation[d] = None;
+        }
+        self.vel[d] = 0.0;
+        self.scroll_stuck_to_end[d] = false;
+    }
+}
+
+/// What [`ScrollArea::show`] and friends return.
+///
+/// Use this to e.g. draw a minimap next to the scroll area,
+/// or add a "jump to bottom" button with [`ScrollArea::scroll_to_offset`].
+pub struct ScrollAreaOutput<R> {
+    /// What the user closure returned.
+    pub inner: R,
+
+    /// [`Id`] of the [`ScrollArea`], for use with [`ScrollArea::scroll_to_offset`]
+    /// and [`ScrollArea::scroll_to_content_rect`].
+    pub id: Id,
+
+    /// The size of the contents, which may be larger than what is visible.
+    pub content_size: Vec2,
+
+    /// Where on the screen the visible part of the contents is shown, excluding the scroll bars.
+    pub inner_rect: Rect,
+
+    /// The visible part of the contents, relative to the top left of the contents.
+    ///
+    /// `viewport.min` is the current scroll offset.
+    pub viewport: Rect,
+}
+
+impl<R> ScrollAreaOutput<R> {
+    /// The current scroll offset. Positive means scrolled down/right.
+    pub fn offset(&self) -> Vec2 {
+        self.viewport.min.to_vec2()
+    }
+
+    /// The largest possible scroll offset.
+    pub fn max_offset(&self) -> Vec2 {
+        (self.content_size - self.viewport.size()).max(Vec2::ZERO)
+    }
 }
 
 /// Add vertical and/or horizontal scrolling to a contained [`Ui`].
@@ -78,6 +164,9 @@ pub struct ScrollArea {
     /// end position until user manually changes position. It will become true
     /// again once scroll handle makes contact with end.
     stick_to_end: [bool; 2],
+
+    smooth_scrolling: bool,
+    scroll_momentum: bool,
 }
 
 impl ScrollArea {
@@ -115,6 +204,8 @@ impl ScrollArea {
             offset_y: None,
             scrolling_enabled: true,
             stick_to_end: [false; 2],
+            smooth_scrolling: false,
+            scroll_momentum: false,
         }
     }
 
@@ -247,6 +338,72 @@ impl ScrollArea {
         self.stick_to_end[1] = true;
         self
     }
+
+    /// If `true`, each step of the mouse wheel scrolls smoothly instead of jumping.
+    ///
+    /// Default: `false`.
+    pub fn smooth_scrolling(mut self, smooth_scrolling: bool) -> Self {
+        self.smooth_scrolling = smooth_scrolling;
+        self
+    }
+
+    /// If `true`, the mouse wheel gives the contents a push,
+    /// and they keep scrolling for a while after the wheel stops.
+    ///
+    /// Dragging the contents with a touch screen always has momentum.
+    ///
+    /// Default: `false`.
+    pub fn scroll_momentum(mut self, scroll_momentum: bool) -> Self {
+        self.scroll_momentum = scroll_momentum;
+        self
+    }
+
+    /// Scroll the [`ScrollArea`] with the given [`Id`] to `offset`,
+    /// where [`Vec2::ZERO`] is the top left of the contents.
+    ///
+    /// This can be called from outside the scroll area,
+    /// and takes effect the next time it is shown.
+    /// Get the `id` from [`ScrollAreaOutput::id`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// let jump_to_bottom = ui.button("Jump to bottom").clicked();
+    /// let output = egui::ScrollArea::vertical().show(ui, |ui| {
+    ///     for i in 0..1000 {
+    ///         ui.label(format!("Item {}", i));
+    ///     }
+    /// });
+    /// if jump_to_bottom {
+    ///     egui::ScrollArea::scroll_to_offset(ui.ctx(), output.id, output.max_offset(), true);
+    /// }
+    /// # });
+    /// ```
+    pub fn scroll_to_offset(ctx: &Context, id: Id, offset: Vec2, animated: bool) {
+        let time = ctx.input().time;
+        let mut state = State::load(ctx, id).unwrap_or_default();
+        for d in 0..2 {
+            state.scroll_to(d, offset[d], animated, time);
+        }
+        state.store(ctx, id);
+        ctx.request_repaint();
+    }
+
+    /// Scroll the [`ScrollArea`] with the given [`Id`] so that `rect` is visible, with the given alignment.
+    ///
+    /// `rect` is relative to the top left of the contents, like the viewport in [`Self::show_viewport`].
+    /// See also [`Self::scroll_to_offset`] and [`Ui::scroll_to_rect`].
+    pub fn scroll_to_content_rect(ctx: &Context, id: Id, rect: Rect, align: Align, animated: bool) {
+        let time = ctx.input().time;
+        let mut state = State::load(ctx, id).unwrap_or_default();
+        let center_factor = align.to_factor();
+        for d in 0..2 {
+            let target = lerp(rect.min[d]..=rect.max[d], center_factor);
+            let offset = target - state.viewport_size[d] * center_factor;
+            state.scroll_to(d, offset.at_least(0.0), animated, time);
+        }
+        state.store(ctx, id);
+        ctx.request_repaint();
+    }
 }
 
 struct Prepared {
@@ -265,6 +422,8 @@ struct Prepared {
     viewport: Rect,
     scrolling_enabled: bool,
     stick_to_end: [bool; 2],
+    smooth_scrolling: bool,
+    scroll_momentum: bool,
 }
 
 impl ScrollArea {
@@ -279,6 +438,8 @@ impl ScrollArea {
             offset_y,
             scrolling_enabled,
             stick_to_end,
+            smooth_scrolling,
+            scroll_momentum,
         } = self;
 
         let ctx = ui.ctx().clone();
@@ -359,13 +520,19 @@ impl ScrollArea {
             viewport,
             scrolling_enabled,
             stick_to_end,
+            smooth_scrolling,
+            scroll_momentum,
         }
     }
 
     /// Show the `ScrollArea`, and add the contents to the viewport.
     ///
     /// If the inner area can be very long, consider using [`Self::show_rows`] instead.
-    pub fn show<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+    pub fn show<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> ScrollAreaOutput<R> {
         self.show_viewport_dyn(ui, Box::new(|ui, _viewport| add_contents(ui)))
     }
 
@@ -391,7 +558,7 @@ impl ScrollArea {
         row_height_sans_spacing: f32,
         total_rows: usize,
         add_contents: impl FnOnce(&mut Ui, std::ops::Range<usize>) -> R,
-    ) -> R {
+    ) -> ScrollAreaOutput<R> {
         let spacing = ui.spacing().item_spacing;
         let row_height_with_spacing = row_height_sans_spacing + spacing.y;
         self.show_viewport(ui, |ui, viewport| {
@@ -420,7 +587,11 @@ impl ScrollArea {
     ///
     /// `add_contents` is past the viewport, which is the relative view of the content.
     /// So if the passed rect has min = zero, then show the top left content (the user has not scrolled).
-    pub fn show_viewport<R>(self, ui: &mut Ui, add_contents: impl FnOnce(&mut Ui, Rect) -> R) -> R {
+    pub fn show_viewport<R>(
+        self,
+        ui: &mut Ui,
+        add_contents: impl FnOnce(&mut Ui, Rect) -> R,
+    ) -> ScrollAreaOutput<R> {
         self.show_viewport_dyn(ui, Box::new(add_contents))
     }
 
@@ -428,16 +599,24 @@ impl ScrollArea {
         self,
         ui: &mut Ui,
         add_contents: Box<dyn FnOnce(&mut Ui, Rect) -> R + 'c>,
-    ) -> R {
+    ) -> ScrollAreaOutput<R> {
         let mut prepared = self.begin(ui);
-        let ret = add_contents(&mut prepared.content_ui, prepared.viewport);
-        prepared.end(ui);
-        ret
+        let id = prepared.id;
+        let inner = add_contents(&mut prepared.content_ui, prepared.viewport);
+        let (content_size, state, inner_rect) = prepared.end(ui);
+        ScrollAreaOutput {
+            inner,
+            id,
+            content_size,
+            inner_rect,
+            viewport: Rect::from_min_size(Pos2::ZERO + state.offset, inner_rect.size()),
+        }
     }
 }
 
 impl Prepared {
-    fn end(self, ui: &mut Ui) {
+    /// Returns the size of the contents, the new state and the inner rect.
+    fn end(self, ui: &mut Ui) -> (Vec2, State, Rect) {
         let Prepared {
             id,
             mut state,
@@ -450,15 +629,18 @@ impl Prepared {
             viewport: _,
             scrolling_enabled,
             stick_to_end,
+            smooth_scrolling,
+            scroll_momentum,
         } = self;
 
+        let time = ui.input().time;
         let content_size = content_ui.min_size();
 
         for d in 0..2 {
             if has_bar[d] {
                 // We take the scroll target so only this ScrollArea will use it:
                 let scroll_target = content_ui.ctx().frame_state().scroll_target[d].take();
-                if let Some((scroll, align)) = scroll_target {
+                if let Some((scroll, align, animated)) = scroll_target {
                     let center_factor = align.to_factor();
 
                     let min = content_ui.min_rect().min[d];
@@ -470,7 +652,7 @@ impl Prepared {
                     // Depending on the alignment we need to add or subtract the spacing
                     spacing *= remap(center_factor, 0.0..=1.0, -1.0..=1.0);
 
-                    state.offset[d] = offset + spacing;
+                    state.scroll_to(d, offset + spacing, animated, time);
                 }
             }
         }
@@ -530,11 +712,23 @@ impl Prepared {
                         state.offset[d] -= input.pointer.delta()[d];
                         state.vel[d] = input.pointer.velocity()[d];
                         state.scroll_stuck_to_end[d] = false;
+                        state.scroll_animation[d] = None;
                     } else {
                         state.vel[d] = 0.0;
                     }
                 }
             } else {
+                if content_response.drag_released() {
+                    if let Some(fling_velocity) = input.fling_velocity() {
+                        // Keep scrolling after a fling (kinetic scrolling):
+                        for d in 0..2 {
+                            if has_bar[d] {
+                                state.vel[d] = fling_velocity[d];
+                            }
+                        }
+                    }
+                }
+
                 let stop_speed = 20.0; // Pixels per second.
                 let friction_coeff = 1000.0; // Pixels per second squared.
                 let dt = input.unstable_dt;
@@ -563,7 +757,23 @@ impl Prepared {
                     let scrolling_down = state.offset[d] < max_offset[d] && scroll_delta[d] < 0.0;
 
                     if scrolling_up || scrolling_down {
-                        state.offset[d] -= scroll_delta[d];
+                        if smooth_scrolling {
+                            // Keep going from where the previous wheel step was heading:
+                            let from = state.offset[d];
+                            let to = state.scroll_animation[d].map_or(from, |anim| anim.to)
+                                - scroll_delta[d];
+                            state.scroll_animation[d] = Some(ScrollAnimation {
+                                from,
+                                to: to.clamp(0.0, max_offset[d].at_least(0.0)),
+                                start_time: time,
+                            });
+                        } else if scroll_momentum {
+                            state.vel[d] += WHEEL_MOMENTUM_FACTOR * scroll_delta[d];
+                            ui.ctx().request_repaint();
+                        } else {
+                            state.offset[d] -= scroll_delta[d];
+                            state.scroll_animation[d] = None;
+                        }
                         // Clear scroll delta so no parent scroll will use it.
                         frame_state.scroll_delta[d] = 0.0;
                         state.scroll_stuck_to_end[d] = false;
@@ -572,6 +782,18 @@ impl Prepared {
             }
         }
 
+        for d in 0..2 {
+            if let Some(animation) = state.scroll_animation[d] {
+                let (offset, done) = animation.offset_at(time);
+                state.offset[d] = offset;
+                if done {
+                    state.scroll_animation[d] = None;
+                } else {
+                    ui.ctx().request_repaint();
+                }
+            }
+        }
+
         let show_scroll_this_frame = [
             content_is_too_small[0] || always_show_scroll,
             content_is_too_small[1] || always_show_scroll,
@@ -614,7 +836,10 @@ impl Prepared {
             };
 
             // maybe force increase in offset to keep scroll stuck to end position
-            if stick_to_end[d] && state.scroll_stuck_to_end[d] {
+            if stick_to_end[d]
+                && state.scroll_stuck_to_end[d]
+                && state.scroll_animation[d].is_none()
+            {
                 state.offset[d] = content_size[d] - inner_rect.size()[d];
             }
 
@@ -663,6 +888,7 @@ impl Prepared {
 
                 // some manual action taken, scroll not stuck
                 state.scroll_stuck_to_end[d] = false;
+                state.scroll_animation[d] = None;
             } else {
                 state.scroll_start_offset_from_top_left[d] = None;
             }
@@ -673,6 +899,7 @@ impl Prepared {
 
             if state.offset[d] != unbounded_offset {
                 state.vel[d] = 0.0;
+                state.scroll_animation[d] = None;
             }
 
             if ui.is_rect_visible(outer_scroll_rect) {
@@ -746,8 +973,11 @@ impl Prepared {
         ];
 
         state.show_scroll = show_scroll_this_frame;
+        state.viewport_size = inner_rect.size();
 
         state.store(ui.ctx(), id);
+
+        (content_size, state, inner_rect)
     }
 }
 
@@ -755,3 +985,141 @@ impl Prepared {
 fn max_scroll_bar_width_with_margin(ui: &Ui) -> f32 {
     ui.spacing().item_spacing.x + ui.spacing().scroll_bar_width
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Show a scroll area, 100 points high, with 1000 points of contents.
+    fn run(
+        ctx: &mut CtxRef,
+        time: f64,
+        events: Vec<Event>,
+        scroll_area: ScrollArea,
+    ) -> ScrollAreaOutput<()> {
+        let raw_input = RawInput {
+            time: Some(time),
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(400.0, 400.0))),
+            events,
+            ..Default::default()
+        };
+        let mut output = None;
+        let _ = ctx.run(raw_input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                output = Some(scroll_area.max_height(100.0).show(ui, |ui| {
+                    ui.allocate_space(vec2(100.0, 1000.0));
+                }));
+            });
+        });
+        output.unwrap()
+    }
+
+    /// Scroll the mouse wheel down by `delta` points, over the scroll area.
+    fn wheel(delta: f32) -> Vec<Event> {
+        vec![
+            Event::PointerMoved(pos2(50.0, 50.0)),
+            Event::Scroll(vec2(0.0, -delta)),
+        ]
+    }
+
+    #[test]
+    fn output() {
+        let mut ctx = CtxRef::default();
+        let output = run(&mut ctx, 0.0, vec![], ScrollArea::vertical());
+        assert_eq!(output.content_size.y, 1000.0);
+        assert_eq!(output.inner_rect.height(), 100.0);
+        assert_eq!(output.viewport.size(), output.inner_rect.size());
+        assert_eq!(output.offset(), Vec2::ZERO);
+        assert_eq!(output.max_offset().y, 900.0);
+    }
+
+    #[test]
+    fn scroll_to_offset_is_clamped() {
+        let mut ctx = CtxRef::default();
+        let id = run(&mut ctx, 0.0, vec![], ScrollArea::vertical()).id;
+
+        ScrollArea::scroll_to_offset(&ctx, id, vec2(0.0, 300.0), false);
+        let output = run(&mut ctx, 0.1, vec![], ScrollArea::vertical());
+        assert_eq!(output.offset().y, 300.0);
+
+        ScrollArea::scroll_to_offset(&ctx, id, vec2(0.0, 10_000.0), false);
+        let output = run(&mut ctx, 0.2, vec![], ScrollArea::vertical());
+        assert_eq!(output.offset().y, output.max_offset().y);
+
+        ScrollArea::scroll_to_offset(&ctx, id, vec2(0.0, -100.0), false);
+        let output = run(&mut ctx, 0.3, vec![], ScrollArea::vertical());
+        assert_eq!(output.offset().y, 0.0);
+    }
+
+    #[test]
+    fn animated_scroll_to_content_rect() {
+        let mut ctx = CtxRef::default();
+        let id = run(&mut ctx, 0.0, vec![], ScrollArea::vertical()).id;
+
+        let rect = Rect::from_min_size(pos2(0.0, 500.0), vec2(100.0, 20.0));
+        ScrollArea::scroll_to_content_rect(&ctx, id, rect, Align::Min, true);
+
+        let halfway = run(&mut ctx, 0.1, vec![], ScrollArea::vertical())
+            .offset()
+            .y;
+        assert!(0.0 < halfway && halfway < 500.0, "{}", halfway);
+
+        let done = run(
+            &mut ctx,
+            0.1 + SCROLL_ANIMATION_TIME as f64,
+            vec![],
+            ScrollArea::vertical(),
+        );
+        assert_eq!(done.offset().y, 500.0);
+
+        // Centering a rect near the end is limited by the end of the contents:
+        let rect = Rect::from_min_size(pos2(0.0, 990.0), vec2(100.0, 10.0));
+        ScrollArea::scroll_to_content_rect(&ctx, id, rect, Align::Center, true);
+        let done = run(&mut ctx, 1.0, vec![], ScrollArea::vertical());
+        assert_eq!(done.offset().y, 900.0);
+    }
+
+    #[test]
+    fn smooth_wheel_scrolling() {
+        let scroll_area = || ScrollArea::vertical().smooth_scrolling(true);
+        let mut ctx = CtxRef::default();
+        run(&mut ctx, 0.0, vec![], scroll_area());
+
+        let start = run(&mut ctx, 0.1, wheel(50.0), scroll_area()).offset().y;
+        assert!(start < 50.0, "should not jump: {}", start);
+        let halfway = run(&mut ctx, 0.2, vec![], scroll_area()).offset().y;
+        assert!(start < halfway && halfway < 50.0, "{}", halfway);
+
+        // A second wheel step continues from where the first one was heading:
+        run(&mut ctx, 0.2, wheel(50.0), scroll_area());
+        let done = run(&mut ctx, 1.0, vec![], scroll_area());
+        assert_eq!(done.offset().y, 100.0);
+
+        // The animation never goes past the end:
+        run(&mut ctx, 1.1, wheel(10_000.0), scroll_area());
+        let done = run(&mut ctx, 2.0, vec![], scroll_area());
+        assert_eq!(done.offset().y, done.max_offset().y);
+    }
+
+    #[test]
+    fn wheel_scrolling_with_momentum() {
+        let scroll_area = || ScrollArea::vertical().scroll_momentum(true);
+        let mut ctx = CtxRef::default();
+        run(&mut ctx, 0.0, vec![], scroll_area());
+        run(&mut ctx, 0.1, wheel(20.0), scroll_area());
+
+        let mut offsets = vec![];
+        for frame in 3..100 {
+            offsets.push(
+                run(&mut ctx, frame as f64 * 0.05, vec![], scroll_area())
+                    .offset()
+                    .y,
+            );
+        }
+        assert!(offsets[0] > 0.0, "should keep moving after the wheel step");
+        assert!(offsets.windows(2).all(|w| w[0] <= w[1]));
+        let last = *offsets.last().unwrap();
+        assert!(last < 900.0, "friction should stop it: {}", last);
+        assert_eq!(offsets[offsets.len() - 2], last, "should have stopped");
+    }
+}
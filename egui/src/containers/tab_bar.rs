@@ -0,0 +1,701 @@
+//! A row of tabs for switching between documents or pages, see [`TabBar`].
+
+use std::hash::Hash;
+
+use crate::{widget_text::WidgetTextGalley, *};
+
+/// What a [`TabBar`] does when the tabs don't fit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TabOverflow {
+    /// Put the tabs in a horizontal [`ScrollArea`].
+    Scroll,
+
+    /// Show as many tabs as fit (always including the active one),
+    /// and a button with a dropdown menu listing all tabs.
+    Dropdown,
+}
+
+/// How one tab in a [`TabBar`] looks.
+///
+/// ```
+/// # use egui::Tab;
+/// let tab = Tab::new("main.rs").id("src/main.rs").dirty(true);
+/// ```
+pub struct Tab {
+    title: WidgetText,
+    id: Option<Id>,
+    dirty: bool,
+    closable: bool,
+}
+
+impl Tab {
+    /// A closable tab with the given title.
+    pub fn new(title: impl Into<WidgetText>) -> Self {
+        Self {
+            title: title.into(),
+            id: None,
+            dirty: false,
+            closable: true,
+        }
+    }
+
+    /// Identifies the tab, so that it stays active when other tabs are added or removed.
+    ///
+    /// Must be unique within the [`TabBar`]. Without it, tabs are identified by their position.
+    pub fn id(mut self, id_source: impl Hash) -> Self {
+        self.id = Some(Id::new(id_source));
+        self
+    }
+
+    /// Show a dot marking that the tab has unsaved changes.
+    ///
+    /// If the tab is closable, the dot turns into the close button when hovered.
+    pub fn dirty(mut self, dirty: bool) -> Self {
+        self.dirty = dirty;
+        self
+    }
+
+    /// Show a close button. Default: `true`.
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+struct State {
+    /// The key of the active tab, see [`tab_keys`].
+    active: Option<Id>,
+
+    /// Where the active tab was, so that a neighbor becomes active if it is removed.
+    active_index: usize,
+}
+
+impl State {
+    fn load(ctx: &Context, id: Id) -> Option<Self> {
+        ctx.memory().data.get_persisted(id)
+    }
+
+    fn store(self, ctx: &Context, id: Id) {
+        ctx.memory().data.insert_persisted(id, self);
+    }
+
+    /// Index of the active tab among `keys`.
+    fn active_index(&self, keys: &[Id]) -> Option<usize> {
+        if keys.is_empty() {
+            None
+        } else if let Some(index) = keys.iter().position(|key| Some(*key) == self.active) {
+            Some(index)
+        } else {
+            Some(self.active_index.min(keys.len() - 1))
+        }
+    }
+}
+
+/// What happened in a [`TabBar`].
+pub struct TabBarResponse {
+    /// The whole tab bar.
+    pub response: Response,
+
+    /// Index of the active tab, or `None` if there are no tabs.
+    pub active: Option<usize>,
+
+    /// The active tab changed this frame,
+    /// by clicking, with the keyboard, or because the active tab was removed.
+    pub changed: bool,
+
+    /// The user clicked the close button of this tab, or middle-clicked it.
+    ///
+    /// The tab is not removed automatically, so that you can e.g. ask to save changes first.
+    pub close_requested: Option<usize>,
+
+    /// The user dragged a tab to a new position this frame.
+    /// The slice passed to [`TabBar::show`] has been reordered to match.
+    pub reordered: bool,
+}
+
+/// A row of tabs, e.g. for open documents.
+///
+/// The active tab is remembered in [`Memory`], keyed by [`Tab::id`],
+/// so it stays active when tabs are added, removed or reordered.
+/// Show the contents of the active tab yourself, below the tab bar.
+///
+/// Ctrl+Tab and Ctrl+Shift+Tab switch to the next and previous tab.
+/// From the frame after the tab bar is first shown, they no longer move the keyboard focus.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut files = vec!["main.rs".to_owned(), "lib.rs".to_owned()];
+/// let tabs = egui::TabBar::new("files").show(ui, &mut files, |file| {
+///     egui::Tab::new(file.as_str()).id(file)
+/// });
+/// if let Some(closed) = tabs.close_requested {
+///     files.remove(closed);
+/// } else if let Some(active) = tabs.active {
+///     ui.label(format!("Editing {}", files[active]));
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct TabBar {
+    id_source: Id,
+    overflow: TabOverflow,
+    reorderable: bool,
+    keyboard_switching: bool,
+}
+
+impl TabBar {
+    /// The id source must be unique within the parent [`Ui`].
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id_source: Id::new(id_source),
+            overflow: TabOverflow::Scroll,
+            reorderable: true,
+            keyboard_switching: true,
+        }
+    }
+
+    /// What to do when the tabs don't fit. Default: [`TabOverflow::Scroll`].
+    pub fn overflow(mut self, overflow: TabOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Can the tabs be reordered by dragging them? Default: `true`.
+    pub fn reorderable(mut self, reorderable: bool) -> Self {
+        self.reorderable = reorderable;
+        self
+    }
+
+    /// Switch tabs with Ctrl+Tab and Ctrl+Shift+Tab? Default: `true`.
+    ///
+    /// Turn this off for all but one tab bar if you show several of them.
+    pub fn keyboard_switching(mut self, keyboard_switching: bool) -> Self {
+        self.keyboard_switching = keyboard_switching;
+        self
+    }
+
+    /// Show one tab for each element of `tabs`.
+    ///
+    /// `tab` describes how each element looks.
+    pub fn show<T>(
+        self,
+        ui: &mut Ui,
+        tabs: &mut [T],
+        mut tab: impl FnMut(&T) -> Tab,
+    ) -> TabBarResponse {
+        let Self {
+            id_source,
+            overflow,
+            reorderable,
+            keyboard_switching,
+        } = self;
+
+        let id = ui.make_persistent_id(id_source);
+        let mut state = State::load(ui.ctx(), id).unwrap_or_default();
+
+        let layouts: Vec<TabLayout> = tabs.iter().map(|t| TabLayout::new(ui, tab(t))).collect();
+        let mut tab_ids: Vec<Option<Id>> = layouts.iter().map(|layout| layout.id).collect();
+        let mut keys = tab_keys(id, &tab_ids);
+        let mut active = state.active_index(&keys);
+        // The active tab may have been removed since last frame:
+        let mut changed = state.active.is_some() && active.map(|i| keys[i]) != state.active;
+
+        if keyboard_switching {
+            ui.memory().claim_ctrl_tab();
+            if let Some(index) = active {
+                let steps: isize = ui
+                    .input()
+                    .events
+                    .iter()
+                    .map(|event| match event {
+                        Event::Key {
+                            key: Key::Tab,
+                            pressed: true,
+                            modifiers,
+                        } if modifiers.ctrl => {
+                            if modifiers.shift {
+                                -1
+                            } else {
+                                1
+                            }
+                        }
+                        _ => 0,
+                    })
+                    .sum();
+                if steps != 0 {
+                    let new_index = (index as isize + steps).rem_euclid(keys.len() as isize);
+                    active = Some(new_index as usize);
+                    changed = true;
+                }
+            }
+        }
+
+        let visible = match overflow {
+            TabOverflow::Scroll => 0..layouts.len(),
+            TabOverflow::Dropdown => {
+                let spacing = ui.spacing().item_spacing.x;
+                let dropdown_width = ui.spacing().interact_size.y + spacing;
+                let widths: Vec<f32> = layouts.iter().map(|layout| layout.width).collect();
+                visible_tabs(
+                    &widths,
+                    active,
+                    ui.available_width() - dropdown_width,
+                    spacing,
+                )
+            }
+        };
+        let needs_dropdown = visible.len() < layouts.len();
+        let titles: Vec<String> = if needs_dropdown {
+            layouts
+                .iter()
+                .map(|layout| layout.galley.text().to_owned())
+                .collect()
+        } else {
+            vec![]
+        };
+
+        // Scroll the active tab into view if it changed without being clicked:
+        let scroll_to_active = changed && overflow == TabOverflow::Scroll;
+
+        let show_row = |ui: &mut Ui| {
+            ui.horizontal(|ui| {
+                let mut row = row_ui(
+                    ui,
+                    &keys,
+                    layouts,
+                    visible,
+                    active,
+                    reorderable,
+                    scroll_to_active,
+                );
+                if needs_dropdown {
+                    let picked = ui.menu_button("⏷", |ui| {
+                        for (index, title) in titles.iter().enumerate() {
+                            if ui
+                                .selectable_label(Some(index) == active, title.as_str())
+                                .clicked()
+                            {
+                                ui.close_menu();
+                                return Some(index);
+                            }
+                        }
+                        None
+                    });
+                    if let Some(index) = picked.inner.flatten() {
+                        row.clicked = Some(index);
+                    }
+                }
+                row
+            })
+            .inner
+        };
+
+        let InnerResponse {
+            inner: row,
+            response,
+        } = ui.scope(|ui| match overflow {
+            TabOverflow::Scroll => {
+                ScrollArea::horizontal()
+                    .id_source(id.with("scroll"))
+                    .auto_shrink([false, true])
+                    .show(ui, show_row)
+                    .inner
+            }
+            TabOverflow::Dropdown => show_row(ui),
+        });
+
+        if let Some(clicked) = row.clicked {
+            changed |= Some(clicked) != active;
+            active = Some(clicked);
+        }
+
+        let mut reordered = false;
+        if let Some((a, b)) = row.swap {
+            tabs.swap(a, b);
+            tab_ids.swap(a, b);
+            keys = tab_keys(id, &tab_ids);
+            // Keep dragging the tab, which may now have a different key:
+            ui.memory().interaction.drag_id = Some(keys[b]);
+            if active == Some(a) {
+                active = Some(b);
+            } else if active == Some(b) {
+                active = Some(a);
+            }
+            reordered = true;
+        }
+
+        ui.painter().line_segment(
+            [response.rect.left_bottom(), response.rect.right_bottom()],
+            ui.visuals().widgets.noninteractive.bg_stroke,
+        );
+
+        state.active = active.map(|index| keys[index]);
+        state.active_index = active.unwrap_or_default();
+        state.store(ui.ctx(), id);
+
+        if changed {
+            ui.ctx().request_repaint(); // The caller shows the new contents next frame.
+        }
+
+        TabBarResponse {
+            response,
+            active,
+            changed,
+            close_requested: row.close_requested,
+            reordered,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+struct TabLayout {
+    galley: WidgetTextGalley,
+    id: Option<Id>,
+    dirty: bool,
+    closable: bool,
+    width: f32,
+}
+
+impl TabLayout {
+    fn new(ui: &Ui, tab: Tab) -> Self {
+        let Tab {
+            title,
+            id,
+            dirty,
+            closable,
+        } = tab;
+        let galley = title.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Button);
+        let mut width = 2.0 * ui.spacing().button_padding.x + galley.size().x;
+        if dirty || closable {
+            width += ui.spacing().item_spacing.x + close_button_size(ui);
+        }
+        Self {
+            galley,
+            id,
+            dirty,
+            closable,
+            width,
+        }
+    }
+}
+
+/// Tabs are keyed by their [`Tab::id`] if they have one, and otherwise by their index.
+fn tab_keys(id: Id, tab_ids: &[Option<Id>]) -> Vec<Id> {
+    tab_ids
+        .iter()
+        .enumerate()
+        .map(|(index, tab_id)| match tab_id {
+            Some(tab_id) => id.with(tab_id),
+            None => id.with("index").with(index),
+        })
+        .collect()
+}
+
+fn close_button_size(ui: &Ui) -> f32 {
+    ui.fonts()[TextStyle::Button].row_height()
+}
+
+/// The tabs that fit in `max_width`, starting from the first one, but always including the active one.
+fn visible_tabs(
+    widths: &[f32],
+    active: Option<usize>,
+    max_width: f32,
+    spacing: f32,
+) -> std::ops::Range<usize> {
+    let total_width: f32 = widths.iter().map(|width| width + spacing).sum();
+    if total_width <= max_width + spacing {
+        return 0..widths.len();
+    }
+
+    // Go left from the active tab, or right from the first one, taking as many as fit:
+    let mut width = 0.0;
+    let mut end = 0;
+    while end < widths.len() && width + widths[end] <= max_width {
+        width += widths[end] + spacing;
+        end += 1;
+    }
+    match active {
+        Some(active) if active >= end => {
+            let mut start = active + 1;
+            let mut width = 0.0;
+            while start > 0 && width + widths[start - 1] <= max_width {
+                width += widths[start - 1] + spacing;
+                start -= 1;
+            }
+            start.min(active)..active + 1
+        }
+        _ => 0..end.max(1).min(widths.len()),
+    }
+}
+
+#[derive(Default)]
+struct RowOutput {
+    clicked: Option<usize>,
+    close_requested: Option<usize>,
+    /// Swap these two tabs, because one is being dragged past the other.
+    swap: Option<(usize, usize)>,
+}
+
+fn row_ui(
+    ui: &mut Ui,
+    keys: &[Id],
+    layouts: Vec<TabLayout>,
+    visible: std::ops::Range<usize>,
+    active: Option<usize>,
+    reorderable: bool,
+    scroll_to_active: bool,
+) -> RowOutput {
+    let mut output = RowOutput::default();
+    let mut rects = Vec::with_capacity(visible.len());
+    let mut dragged = None;
+
+    let height = ui.spacing().interact_size.y;
+    let button_padding = ui.spacing().button_padding;
+    let close_size = close_button_size(ui);
+
+    for (index, layout) in layouts.into_iter().enumerate() {
+        if !visible.contains(&index) {
+            continue;
+        }
+        let TabLayout {
+            galley,
+            id: _,
+            dirty,
+            closable,
+            width,
+        } = layout;
+        let selected = Some(index) == active;
+
+        let (rect, _) = ui.allocate_exact_size(vec2(width, height), Sense::hover());
+        rects.push(rect);
+        if selected && scroll_to_active {
+            ui.scroll_to_rect(rect, Align::Center, true);
+        }
+
+        // The close button first, so that it gets the click rather than the tab:
+        let close_rect = Rect::from_center_size(
+            pos2(
+                rect.right() - button_padding.x - close_size / 2.0,
+                rect.center().y,
+            ),
+            Vec2::splat(close_size),
+        );
+        let close_response = closable.then(|| {
+            ui.interact(close_rect, keys[index].with("close"), Sense::click())
+                .on_hover_text("Close")
+        });
+        let close_clicked = close_response.as_ref().map_or(false, Response::clicked);
+
+        let sense = if reorderable {
+            Sense::click_and_drag()
+        } else {
+            Sense::click()
+        };
+        let response = ui.interact(rect, keys[index], sense);
+        response.widget_info(|| {
+            WidgetInfo::selected(WidgetType::SelectableLabel, selected, galley.text())
+        });
+
+        if close_clicked || (closable && response.middle_clicked()) {
+            output.close_requested = Some(index);
+        } else if response.clicked() {
+            output.clicked = Some(index);
+        }
+        if response.dragged() {
+            ui.output().cursor_icon = CursorIcon::Grabbing;
+            dragged = Some(index);
+        }
+
+        if ui.is_rect_visible(rect) {
+            let visuals = ui.style().interact_selectable(&response, selected);
+            if selected || response.hovered() {
+                ui.painter().rect(
+                    rect.expand(visuals.expansion),
+                    visuals.corner_radius,
+                    visuals.bg_fill,
+                    visuals.bg_stroke,
+                );
+            }
+
+            let text_pos = pos2(
+                rect.left() + button_padding.x,
+                rect.center().y - galley.size().y / 2.0,
+            );
+            galley.paint_with_visuals(ui.painter(), text_pos, &visuals);
+
+            // The dirty marker turns into the close button when hovered:
+            let hovered =
+                response.hovered() || close_response.as_ref().map_or(false, Response::hovered);
+            if closable && (hovered || (selected && !dirty)) {
+                let close_visuals = close_response.as_ref().map_or(&visuals, |close_response| {
+                    ui.style().interact(close_response)
+                });
+                ui.painter().text(
+                    close_rect.center(),
+                    Align2::CENTER_CENTER,
+                    "🗙",
+                    TextStyle::Button,
+                    close_visuals.text_color(),
+                );
+            } else if dirty {
+                ui.painter().circle_filled(
+                    close_rect.center(),
+                    close_size / 4.0,
+                    visuals.text_color(),
+                );
+            }
+        }
+    }
+
+    // Swap with a neighbor once the pointer has moved past its center:
+    if let (Some(index), Some(pointer)) = (dragged, ui.input().pointer.interact_pos()) {
+        let i = index - visible.start;
+        if i > 0 && pointer.x < rects[i - 1].center().x {
+            output.swap = Some((index, index - 1));
+        } else if i + 1 < rects.len() && pointer.x > rects[i + 1].center().x {
+            output.swap = Some((index, index + 1));
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn active_tab_follows_its_key() {
+        let keys = [Id::new(1), Id::new(2), Id::new(3)];
+        let state = State {
+            active: Some(keys[1]),
+            active_index: 1,
+        };
+        assert_eq!(state.active_index(&keys), Some(1));
+        assert_eq!(state.active_index(&[keys[1], keys[0]]), Some(0));
+
+        // The active tab was removed, so its neighbor takes over:
+        assert_eq!(state.active_index(&[keys[0], keys[2]]), Some(1));
+        assert_eq!(state.active_index(&[keys[0]]), Some(0));
+        assert_eq!(state.active_index(&[]), None);
+    }
+
+    #[test]
+    fn visible_tabs_overflow() {
+        let widths = [50.0; 5];
+        let spacing = 4.0;
+        assert_eq!(visible_tabs(&widths, Some(0), 300.0, spacing), 0..5);
+        assert_eq!(visible_tabs(&widths, Some(0), 160.0, spacing), 0..3);
+        assert_eq!(visible_tabs(&widths, None, 160.0, spacing), 0..3);
+        assert_eq!(visible_tabs(&widths, Some(2), 160.0, spacing), 0..3);
+
+        // The active tab is always visible, with as many of the tabs before it as fit:
+        assert_eq!(visible_tabs(&widths, Some(4), 160.0, spacing), 2..5);
+        assert_eq!(visible_tabs(&widths, Some(3), 110.0, spacing), 2..4);
+
+        // At least one tab, even if nothing fits:
+        assert_eq!(visible_tabs(&widths, None, 10.0, spacing), 0..1);
+        assert_eq!(visible_tabs(&widths, Some(4), 10.0, spacing), 4..5);
+        assert_eq!(visible_tabs(&[], None, 10.0, spacing), 0..0);
+    }
+
+    fn show(ctx: &mut CtxRef, events: Vec<Event>, tabs: &mut [&str]) -> TabBarResponse {
+        let raw_input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(400.0, 400.0))),
+            events,
+            ..Default::default()
+        };
+        let mut response = None;
+        let _ = ctx.run(raw_input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                response = Some(TabBar::new("tabs").show(ui, tabs, |tab| Tab::new(*tab)));
+            });
+        });
+        response.unwrap()
+    }
+
+    fn button(pos: Pos2, pressed: bool) -> Event {
+        Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        }
+    }
+
+    #[test]
+    fn tabs_with_equal_titles() {
+        let tab_ids = [None, None];
+        let keys = tab_keys(Id::new("tabs"), &tab_ids);
+        assert_ne!(keys[0], keys[1]);
+
+        let mut ctx = CtxRef::default();
+        let mut tabs = ["same", "same"];
+        show(&mut ctx, vec![], &mut tabs);
+        let ctrl_tab = Event::Key {
+            key: Key::Tab,
+            pressed: true,
+            modifiers: Modifiers {
+                ctrl: true,
+                ..Default::default()
+            },
+        };
+        let response = show(&mut ctx, vec![ctrl_tab], &mut tabs);
+        assert_eq!(response.active, Some(1));
+        assert!(response.changed);
+
+        let response = show(&mut ctx, vec![], &mut tabs);
+        assert_eq!(
+            response.active,
+            Some(1),
+            "the second tab should stay active"
+        );
+        assert!(!response.changed);
+    }
+
+    #[test]
+    fn drag_to_reorder() {
+        let mut ctx = CtxRef::default();
+        let mut tabs = ["a", "b", "c"];
+        let response = show(&mut ctx, vec![], &mut tabs);
+        assert_eq!(response.active, Some(0));
+
+        let bar = response.response.rect;
+        let y = bar.center().y;
+        let start = pos2(bar.left() + 5.0, y);
+        show(
+            &mut ctx,
+            vec![Event::PointerMoved(start), button(start, true)],
+            &mut tabs,
+        );
+
+        let mut reordered = false;
+        for x in (10..=200).step_by(10) {
+            let response = show(
+                &mut ctx,
+                vec![Event::PointerMoved(pos2(bar.left() + x as f32, y))],
+                &mut tabs,
+            );
+            reordered |= response.reordered;
+        }
+        show(
+            &mut ctx,
+            vec![button(pos2(bar.left() + 200.0, y), false)],
+            &mut tabs,
+        );
+
+        assert!(reordered);
+        assert_eq!(
+            tabs,
+            ["b", "c", "a"],
+            "the dragged tab should move past both others"
+        );
+        let response = show(&mut ctx, vec![], &mut tabs);
+        assert_eq!(response.active, Some(2), "the active tab follows the drag");
+    }
+}
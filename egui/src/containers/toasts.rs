@@ -0,0 +1,548 @@
+//! Short-lived messages stacked in a corner of the screen, see [`Toasts`].
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::animation::{Easing, Keyframes};
+use crate::*;
+
+/// How long a toast takes to slide in and out, in seconds.
+const SLIDE_TIME: f32 = 0.2;
+
+/// The severity of a [`Toast`], which decides its icon and color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum ToastKind {
+    /// Something the user may want to know, e.g. that a task has started.
+    Info,
+
+    /// Something went well, e.g. a file was saved.
+    Success,
+
+    /// Something may need the attention of the user, but nothing failed (yet).
+    Warning,
+
+    /// Something failed.
+    Error,
+}
+
+impl ToastKind {
+    fn icon(self) -> &'static str {
+        match self {
+            Self::Info => "ℹ",
+            Self::Success => "✔",
+            Self::Warning => "⚠",
+            Self::Error => "❗",
+        }
+    }
+
+    fn color(self, visuals: &Visuals) -> Color32 {
+        match self {
+            Self::Info => visuals.text_color(),
+            Self::Success => Color32::from_rgb(0, 190, 90),
+            Self::Warning => Color32::from_rgb(230, 170, 0),
+            Self::Error => Color32::from_rgb(230, 60, 60),
+        }
+    }
+}
+
+/// Identifies a [`Toast`] that has been added to [`Toasts`], so that it can be updated or dismissed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ToastId(u64);
+
+/// A message shown by [`Toasts`].
+///
+/// ```
+/// # use egui::Toast;
+/// let toast = Toast::success("Saved").duration(Some(2.0));
+/// let task = Toast::info("Uploading…").progress(0.0).duration(None);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast {
+    /// The message.
+    pub text: String,
+
+    pub kind: ToastKind,
+
+    /// Seconds before the toast goes away, not counting the time it is hovered.
+    /// `None` means it stays until dismissed.
+    pub duration: Option<f32>,
+
+    /// Show a progress bar, from `0.0` to `1.0`.
+    pub progress: Option<f32>,
+
+    /// Show a button for dismissing the toast.
+    pub closable: bool,
+}
+
+impl Toast {
+    /// A toast that goes away after four seconds.
+    pub fn new(kind: ToastKind, text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            kind,
+            duration: Some(4.0),
+            progress: None,
+            closable: true,
+        }
+    }
+
+    /// A [`ToastKind::Info`] toast that goes away after four seconds.
+    pub fn info(text: impl Into<String>) -> Self {
+        Self::new(ToastKind::Info, text)
+    }
+
+    /// A [`ToastKind::Success`] toast that goes away after four seconds.
+    pub fn success(text: impl Into<String>) -> Self {
+        Self::new(ToastKind::Success, text)
+    }
+
+    /// A [`ToastKind::Warning`] toast that goes away after four seconds.
+    pub fn warning(text: impl Into<String>) -> Self {
+        Self::new(ToastKind::Warning, text)
+    }
+
+    /// A [`ToastKind::Error`] toast that goes away after four seconds.
+    pub fn error(text: impl Into<String>) -> Self {
+        Self::new(ToastKind::Error, text)
+    }
+
+    /// Seconds before the toast goes away, not counting the time it is hovered.
+    /// `None` means it stays until dismissed.
+    pub fn duration(mut self, duration: Option<f32>) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Show a progress bar, from `0.0` to `1.0`.
+    pub fn progress(mut self, progress: f32) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Show a button for dismissing the toast. Default: `true`.
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+struct ToastState {
+    id: ToastId,
+    toast: Toast,
+    /// Seconds shown so far, not counting the time it was hovered.
+    shown_time: f32,
+    /// Dismissed or timed out, and now sliding out.
+    closing: bool,
+}
+
+#[derive(Default)]
+struct State {
+    next_id: u64,
+    toasts: Vec<ToastState>,
+}
+
+/// Shared with other threads (see [`ToastSender`]), so that they can add toasts while we show them.
+///
+/// This is a `std` mutex rather than [`crate::mutex::Mutex`],
+/// since the latter panics on contention unless the `multi_threaded` feature is enabled.
+type SharedState = Arc<Mutex<State>>;
+
+impl State {
+    /// Must be called from the thread running egui, since it locks [`Context::memory`].
+    fn get(ctx: &Context, id: Id) -> SharedState {
+        ctx.memory()
+            .data
+            .get_temp_mut_or_insert_with(id, SharedState::default)
+            .clone()
+    }
+
+    fn lock(state: &SharedState) -> MutexGuard<'_, State> {
+        // A panic while holding the lock can't leave the toasts in an invalid state:
+        state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn add(&mut self, toast: Toast) -> ToastId {
+        let id = ToastId(self.next_id);
+        self.next_id += 1;
+        self.toasts.push(ToastState {
+            id,
+            toast,
+            shown_time: 0.0,
+            closing: false,
+        });
+        id
+    }
+
+    fn find(&mut self, id: ToastId) -> Option<&mut ToastState> {
+        self.toasts.iter_mut().find(|toast| toast.id == id)
+    }
+}
+
+/// Adds, updates and dismisses [`Toasts`] from any thread. Get one with [`Toasts::sender`].
+///
+/// Unlike the methods of [`Toasts`], this never locks anything of the [`Context`],
+/// so it works without the `multi_threaded` feature.
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// use egui::{Toast, Toasts};
+///
+/// let sender = Toasts::new().sender(ctx);
+/// std::thread::spawn(move || {
+///     let id = sender.add(Toast::info("Uploading…").progress(0.0).duration(None));
+///     sender.update(id, |toast| toast.progress = Some(1.0));
+///     sender.dismiss(id);
+/// })
+/// .join()
+/// .unwrap();
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct ToastSender {
+    state: SharedState,
+    ctx: CtxRef,
+}
+
+impl ToastSender {
+    /// Add a toast, and request a repaint.
+    pub fn add(&self, toast: Toast) -> ToastId {
+        let id = State::lock(&self.state).add(toast);
+        self.ctx.request_repaint();
+        id
+    }
+
+    /// Change a toast that has been added, e.g. to update its progress.
+    ///
+    /// Does nothing if the toast is already gone.
+    pub fn update(&self, id: ToastId, update: impl FnOnce(&mut Toast)) {
+        if let Some(toast) = State::lock(&self.state).find(id) {
+            update(&mut toast.toast);
+            self.ctx.request_repaint();
+        }
+    }
+
+    /// Remove a toast.
+    pub fn dismiss(&self, id: ToastId) {
+        if let Some(toast) = State::lock(&self.state).find(id) {
+            toast.closing = true;
+            self.ctx.request_repaint();
+        }
+    }
+
+    /// Is the toast still shown?
+    pub fn contains(&self, id: ToastId) -> bool {
+        State::lock(&self.state).find(id).is_some()
+    }
+}
+
+/// Short-lived messages, like "Saved" or "Connection lost", stacked in a corner of the screen.
+///
+/// Toasts can be added from anywhere you have a [`Context`],
+/// and from other threads with a [`ToastSender`] (see [`Self::sender`]).
+/// Call [`Self::show`] once each frame to show them.
+/// Hovering a toast pauses its countdown.
+///
+/// ```
+/// # egui::__run_test_ctx(|ctx| {
+/// use egui::{Toast, Toasts};
+///
+/// // Anywhere:
+/// let id = Toasts::new().add(ctx, Toast::info("Uploading…").progress(0.0).duration(None));
+/// Toasts::new().update(ctx, id, |toast| toast.progress = Some(0.5));
+///
+/// // Once each frame:
+/// Toasts::new().show(ctx);
+/// # });
+/// ```
+#[must_use = "You should call .show() or .add()"]
+pub struct Toasts {
+    id: Id,
+    anchor: Align2,
+    offset: Vec2,
+    width: f32,
+}
+
+impl Default for Toasts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Toasts {
+    /// Toasts in the bottom right corner of the screen.
+    pub fn new() -> Self {
+        Self {
+            id: Id::new("__toasts"),
+            anchor: Align2::RIGHT_BOTTOM,
+            offset: vec2(-8.0, -8.0),
+            width: 280.0,
+        }
+    }
+
+    /// Use different ids to have several independent stacks of toasts.
+    pub fn id(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id = Id::new(id_source);
+        self
+    }
+
+    /// Which corner (or edge) of the screen to stack the toasts in, and how far from it.
+    ///
+    /// The offset is added to the position, so e.g. an offset of `[-8.0, 8.0]`
+    /// would move the toasts left and down from [`Align2::RIGHT_TOP`].
+    pub fn anchor(mut self, anchor: Align2, offset: impl Into<Vec2>) -> Self {
+        self.anchor = anchor;
+        self.offset = offset.into();
+        self
+    }
+
+    /// The width of each toast.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// A handle for adding toasts to this stack from other threads.
+    ///
+    /// The methods of [`Toasts`] itself lock the memory of the [`Context`],
+    /// which panics if another thread is using it at the same time
+    /// (unless the `multi_threaded` feature is enabled).
+    ///
+    /// When adding toasts from another thread, you may also need to wake up the integration,
+    /// e.g. with `epi::Frame::request_repaint`.
+    pub fn sender(&self, ctx: &CtxRef) -> ToastSender {
+        ToastSender {
+            state: State::get(ctx, self.id),
+            ctx: ctx.clone(),
+        }
+    }
+
+    /// Add a toast. Use [`Self::sender`] to add toasts from other threads.
+    pub fn add(&self, ctx: &Context, toast: Toast) -> ToastId {
+        let id = State::lock(&State::get(ctx, self.id)).add(toast);
+        ctx.request_repaint();
+        id
+    }
+
+    /// Change a toast that has been added, e.g. to update its progress.
+    ///
+    /// Does nothing if the toast is already gone. Use [`Self::sender`] to update toasts from other threads.
+    pub fn update(&self, ctx: &Context, id: ToastId, update: impl FnOnce(&mut Toast)) {
+        if let Some(toast) = State::lock(&State::get(ctx, self.id)).find(id) {
+            update(&mut toast.toast);
+            ctx.request_repaint();
+        }
+    }
+
+    /// Remove a toast. Use [`Self::sender`] to dismiss toasts from other threads.
+    pub fn dismiss(&self, ctx: &Context, id: ToastId) {
+        if let Some(toast) = State::lock(&State::get(ctx, self.id)).find(id) {
+            toast.closing = true;
+            ctx.request_repaint();
+        }
+    }
+
+    /// Is the toast still shown?
+    pub fn contains(&self, ctx: &Context, id: ToastId) -> bool {
+        State::lock(&State::get(ctx, self.id)).find(id).is_some()
+    }
+
+    /// Show all toasts, and remove the ones that have timed out.
+    pub fn show(self, ctx: &CtxRef) {
+        let Self {
+            id,
+            anchor,
+            offset,
+            width,
+        } = self;
+
+        let state = State::get(ctx, id);
+        let mut state = State::lock(&state);
+        if state.toasts.is_empty() {
+            return;
+        }
+
+        let dt = ctx.input().unstable_dt;
+        let spacing = ctx.style().spacing.item_spacing.y;
+        let screen = ctx.available_rect();
+
+        // Toasts slide in and out towards the edge they are anchored to:
+        let slide_direction = match anchor.x() {
+            Align::Min => -Vec2::X,
+            Align::Max => Vec2::X,
+            Align::Center if anchor.y() == Align::Min => -Vec2::Y,
+            Align::Center => Vec2::Y,
+        };
+        let slide_distance = width + offset.length() + spacing;
+
+        // The newest toast is the one closest to the anchor:
+        let mut stack_height = 0.0;
+        let mut gone = vec![];
+        for toast in state.toasts.iter_mut().rev() {
+            let area_id = id.with(toast.id.0);
+            let area = toast_area(area_id);
+
+            let slide_in = ctx.animate_keyframes(
+                area_id.with("slide_in"),
+                &Keyframes::new(1.0).then(0.0, SLIDE_TIME, Easing::EaseOut),
+            );
+            let slide_out = if toast.closing {
+                ctx.animate_keyframes(
+                    area_id.with("slide_out"),
+                    &Keyframes::new(0.0).then(1.0, SLIDE_TIME, Easing::EaseIn),
+                )
+            } else {
+                0.0
+            };
+            let slide = slide_direction * slide_distance * (slide_in + slide_out);
+
+            // Move smoothly into place when the toasts below us go away:
+            let height = ctx
+                .memory()
+                .areas
+                .get(area.layer().id)
+                .map_or(0.0, |area| area.size.y);
+            let stack_offset = ctx.animate_value(
+                area_id.with("stack_offset"),
+                stack_height,
+                SLIDE_TIME,
+                Easing::EaseOut,
+            );
+            stack_height += height + spacing;
+
+            let size = vec2(width, height);
+            let mut pos = anchor.align_size_within_rect(size, screen).min + offset + slide;
+            pos.y += match anchor.y() {
+                Align::Min | Align::Center => stack_offset,
+                Align::Max => -stack_offset,
+            };
+
+            let response = area
+                .fixed_pos(pos)
+                .show(ctx, |ui| toast_ui(ui, toast, width))
+                .response;
+
+            if !response.hovered() {
+                toast.shown_time += dt;
+            }
+            if let Some(duration) = toast.toast.duration {
+                if toast.shown_time >= duration {
+                    toast.closing = true;
+                }
+            }
+            if toast.closing && slide_out >= 1.0 {
+                gone.push(toast.id);
+            }
+        }
+
+        state.toasts.retain(|toast| !gone.contains(&toast.id));
+        for toast_id in gone {
+            // Forget everything about the toast, so that it doesn't pile up in memory:
+            let area_id = id.with(toast_id.0);
+            for animation in ["slide_in", "slide_out", "stack_offset"] {
+                ctx.restart_animation(area_id.with(animation));
+            }
+            ctx.memory().areas.remove(toast_area(area_id).layer());
+        }
+
+        if !state.toasts.is_empty() {
+            ctx.request_repaint(); // Count down, and slide
+        }
+    }
+}
+
+fn toast_area(area_id: Id) -> Area {
+    Area::new(area_id).order(Order::Foreground)
+}
+
+fn toast_ui(ui: &mut Ui, toast: &mut ToastState, width: f32) {
+    Frame::popup(ui.style()).show(ui, |ui| {
+        ui.set_width(width - 2.0 * ui.spacing().window_padding.x);
+        ui.horizontal(|ui| {
+            let kind = toast.toast.kind;
+            ui.colored_label(kind.color(ui.visuals()), kind.icon());
+            ui.add(Label::new(&toast.toast.text).wrap(true));
+            if toast.toast.closable {
+                ui.with_layout(Layout::right_to_left(), |ui| {
+                    if ui.small_button("🗙").on_hover_text("Dismiss").clicked() {
+                        toast.closing = true;
+                    }
+                });
+            }
+        });
+        if let Some(progress) = toast.toast.progress {
+            ui.add(ProgressBar::new(progress).show_percentage());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toasts_time_out() {
+        let mut ctx = CtxRef::default();
+        let toasts = || Toasts::new();
+        let id = toasts().add(&ctx, Toast::info("Hello").duration(Some(1.0)));
+        let sticky = toasts().add(&ctx, Toast::info("Working…").duration(None));
+
+        for frame in 0..=30 {
+            let raw_input = RawInput {
+                time: Some(frame as f64 * 0.1),
+                ..Default::default()
+            };
+            let _ = ctx.run(raw_input, |ctx| toasts().show(ctx));
+        }
+        assert!(!toasts().contains(&ctx, id));
+        assert!(toasts().contains(&ctx, sticky));
+        let sticky_layer = toast_area(Id::new("__toasts").with(sticky.0)).layer();
+        assert!(ctx.memory().areas.get(sticky_layer.id).is_some());
+
+        toasts().dismiss(&ctx, sticky);
+        for frame in 31..=40 {
+            let raw_input = RawInput {
+                time: Some(frame as f64 * 0.1),
+                ..Default::default()
+            };
+            let _ = ctx.run(raw_input, |ctx| toasts().show(ctx));
+        }
+        assert!(!toasts().contains(&ctx, sticky));
+        assert!(ctx.memory().areas.get(sticky_layer.id).is_none());
+    }
+
+    #[test]
+    fn toasts_from_another_thread() {
+        let mut ctx = CtxRef::default();
+        let sender = Toasts::new().sender(&ctx);
+        let (ids_sender, ids) = std::sync::mpsc::channel();
+        let worker = std::thread::spawn(move || {
+            for i in 0..100 {
+                let id = sender.add(Toast::info(format!("Toast {}", i)).duration(None));
+                sender.update(id, |toast| toast.progress = Some(0.5));
+                ids_sender.send(id).unwrap();
+            }
+        });
+
+        // Show the toasts while they are being added:
+        let mut received = Vec::new();
+        let mut frame = 0;
+        while received.len() < 100 {
+            frame += 1;
+            let raw_input = RawInput {
+                time: Some(frame as f64 * 0.01),
+                ..Default::default()
+            };
+            let _ = ctx.run(raw_input, |ctx| Toasts::new().show(ctx));
+            received.extend(ids.try_iter());
+        }
+        worker.join().unwrap();
+
+        for id in received {
+            assert!(Toasts::new().contains(&ctx, id));
+        }
+    }
+}
@@ -6,6 +6,7 @@ use std::sync::{
 };
 
 use crate::{
+    animation::{Animatable, Easing, Keyframes},
     animation_manager::AnimationManager,
     data::output::Output,
     frame_state::FrameState,
@@ -13,6 +14,7 @@ use crate::{
     layers::GraphicLayers,
     menu::ContextMenuSystem,
     mutex::{Mutex, MutexGuard},
+    tessellation_cache::TessellationCache,
     *,
 };
 use epaint::{stats::*, text::Fonts, *};
@@ -225,7 +227,8 @@ impl CtxRef {
         enabled: bool,
         hovered: bool,
     ) -> Response {
-        let hovered = hovered && enabled; // can't even hover disabled widgets
+        let blocked = self.memory().is_blocked_by_modal(layer_id);
+        let hovered = hovered && enabled && !blocked; // can't even hover disabled widgets
 
         let mut response = Response {
             ctx: self.clone(),
@@ -246,7 +249,7 @@ impl CtxRef {
 
         let mut memory = self.memory();
 
-        if !enabled || !sense.focusable || !layer_id.allow_interaction() {
+        if !enabled || blocked || !sense.focusable || !layer_id.allow_interaction() {
             // Not interested or allowed input:
             memory.surrender_focus(id);
             return response;
@@ -392,6 +395,7 @@ pub struct Context {
     output: Arc<Mutex<Output>>,
 
     paint_stats: Arc<Mutex<PaintStats>>,
+    tessellation_cache: Arc<Mutex<TessellationCache>>,
 
     /// While positive, keep requesting repaints. Decrement at the end of each frame.
     repaint_requests: AtomicU32,
@@ -408,6 +412,7 @@ impl Clone for Context {
             graphics: self.graphics.clone(),
             output: self.output.clone(),
             paint_stats: self.paint_stats.clone(),
+            tessellation_cache: self.tessellation_cache.clone(),
             repaint_requests: self.repaint_requests.load(SeqCst).into(),
             context_menu_system: self.context_menu_system.clone(),
         }
@@ -462,8 +467,11 @@ impl Context {
     /// Not valid until first call to [`CtxRef::run()`].
     /// That's because since we don't know the proper `pixels_per_point` until then.
     pub fn fonts(&self) -> &Fonts {
-        &*self
-            .fonts
+        self.fonts_arc()
+    }
+
+    fn fonts_arc(&self) -> &Arc<Fonts> {
+        self.fonts
             .as_ref()
             .expect("No fonts available until first call to CtxRef::run()")
     }
@@ -493,6 +501,28 @@ impl Context {
         self.memory().new_font_definitions = Some(font_definitions);
     }
 
+    /// The fonts given to [`Self::set_fonts`], even if they are not in use until the next frame.
+    pub fn font_definitions(&self) -> FontDefinitions {
+        if let Some(new_font_definitions) = &self.memory().new_font_definitions {
+            return new_font_definitions.clone();
+        }
+        self.fonts
+            .as_ref()
+            .map_or_else(FontDefinitions::default, |fonts| {
+                fonts.definitions().clone()
+            })
+    }
+
+    /// Use the visuals, spacing and fonts of a [`crate::Theme`].
+    ///
+    /// # Errors
+    /// When a font file of the theme hasn't been loaded (see [`crate::ThemeFonts::load_files`]),
+    /// or it names a font that doesn't exist.
+    /// Nothing is changed then.
+    pub fn set_theme(&self, theme: &crate::Theme) -> std::io::Result<()> {
+        theme.apply(self)
+    }
+
     /// The [`Style`] used by all subsequent windows, panels etc.
     pub fn style(&self) -> Arc<Style> {
         self.memory().options.style.clone()
@@ -526,6 +556,48 @@ impl Context {
         std::sync::Arc::make_mut(&mut self.memory().options.style).visuals = visuals;
     }
 
+    /// Register a [`crate::StyleClass`] under a name, e.g. `"danger"`, replacing any class with the same name.
+    ///
+    /// Use it with [`crate::Button::class`], [`crate::Ui::add_style_class`] etc.
+    pub fn set_style_class(&self, name: &str, class: crate::StyleClass) {
+        let id = Id::new(name);
+        let mut memory = self.memory();
+        memory.options.style_classes.insert(id, Arc::new(class));
+        memory.classed_styles.remove(&id);
+    }
+
+    /// The [`crate::StyleClass`] registered under this name with [`Self::set_style_class`], if any.
+    pub fn style_class(&self, name: &str) -> Option<Arc<crate::StyleClass>> {
+        self.style_class_by_id(Id::new(name))
+    }
+
+    pub(crate) fn style_class_by_id(&self, id: Id) -> Option<Arc<crate::StyleClass>> {
+        self.memory().options.style_classes.get(&id).cloned()
+    }
+
+    /// `style` with the [`crate::StyleClass`] registered under `class` applied,
+    /// or `None` if there is no such class.
+    ///
+    /// The result is cached, so that a [`Style`] is only cloned the first time a class is applied to it.
+    pub(crate) fn style_with_class(&self, style: &Arc<Style>, class: Id) -> Option<Arc<Style>> {
+        let mut memory = self.memory();
+        let memory = &mut *memory;
+        let style_class = memory.options.style_classes.get(&class)?;
+        let classed_styles = memory.classed_styles.entry(class).or_default();
+        if let Some((_, classed)) = classed_styles
+            .iter()
+            .find(|(base, _)| Arc::ptr_eq(base, style))
+        {
+            return Some(classed.clone());
+        }
+
+        let mut classed = (**style).clone();
+        style_class.apply_to(&mut classed);
+        let classed = Arc::new(classed);
+        classed_styles.push((style.clone(), classed.clone()));
+        Some(classed)
+    }
+
     /// The number of physical pixels for each logical point.
     #[inline(always)]
     pub fn pixels_per_point(&self) -> f32 {
@@ -682,28 +754,62 @@ impl Context {
 
     fn drain_paint_lists(&self) -> Vec<ClippedShape> {
         let memory = self.memory();
-        self.graphics().drain(memory.areas.order()).collect()
+        let (shapes, painted_layers) = self.graphics().drain(memory.areas.order());
+        self.tessellation_cache
+            .lock()
+            .set_painted_layers(painted_layers);
+        shapes
     }
 
     /// Tessellate the given shapes into triangle meshes.
+    ///
+    /// The shapes of each layer (e.g. each [`crate::Window`]) are hashed,
+    /// and if they are the same as last frame, last frame's meshes are reused.
+    /// With the `multi_threaded` feature the layers that did change are tessellated in parallel.
+    /// This only works for the shapes returned by [`Self::end_frame`] (unmodified);
+    /// any other shapes are tessellated from scratch.
     pub fn tessellate(&self, shapes: Vec<ClippedShape>) -> Vec<ClippedMesh> {
-        // A tempting optimization is to reuse the tessellation from last frame if the
-        // shapes are the same, but just comparing the shapes takes about 50% of the time
-        // it takes to tessellate them, so it is not a worth optimization.
-
-        let mut tessellation_options = self.memory().options.tessellation_options;
-        tessellation_options.pixels_per_point = self.pixels_per_point();
-        tessellation_options.aa_size = 1.0 / self.pixels_per_point();
         let paint_stats = PaintStats::from_shapes(&shapes);
-        let clipped_meshes = tessellator::tessellate_shapes(
+        let (clipped_meshes, cache_stats) = self.tessellation_cache.lock().tessellate_meshes(
+            self.fonts_arc(),
+            self.tessellation_options(),
             shapes,
-            tessellation_options,
-            self.fonts().font_image().size(),
         );
-        *self.paint_stats.lock() = paint_stats.with_clipped_meshes(&clipped_meshes);
+        let mut paint_stats = paint_stats.with_clipped_meshes(&clipped_meshes);
+        paint_stats.cached_layers = cache_stats.hits;
+        paint_stats.tessellated_layers = cache_stats.misses;
+        *self.paint_stats.lock() = paint_stats;
         clipped_meshes
     }
 
+    /// Statistics about the shapes and meshes of the last call to [`Self::tessellate`]
+    /// or [`Self::tessellate_primitives`], including how many layers were reused from the frame before.
+    pub fn paint_stats(&self) -> PaintStats {
+        *self.paint_stats.lock()
+    }
+
+    /// Like [`Self::tessellate`], but keeps [`epaint::Shape::Group`]s as [`epaint::Primitive::Group`]s,
+    /// for backends that can paint them with offscreen render targets.
+    pub fn tessellate_primitives(&self, shapes: Vec<ClippedShape>) -> Vec<ClippedPrimitive> {
+        let paint_stats = PaintStats::from_shapes(&shapes);
+        let (clipped_primitives, cache_stats) = self
+            .tessellation_cache
+            .lock()
+            .tessellate_primitives(self.fonts_arc(), self.tessellation_options(), shapes);
+        let mut paint_stats = paint_stats.with_clipped_primitives(&clipped_primitives);
+        paint_stats.cached_layers = cache_stats.hits;
+        paint_stats.tessellated_layers = cache_stats.misses;
+        *self.paint_stats.lock() = paint_stats;
+        clipped_primitives
+    }
+
+    fn tessellation_options(&self) -> epaint::TessellationOptions {
+        let mut tessellation_options = self.memory().options.tessellation_options;
+        tessellation_options.pixels_per_point = self.pixels_per_point();
+        tessellation_options.aa_size = 1.0 / self.pixels_per_point();
+        tessellation_options
+    }
+
     // ---------------------------------------------------------------------
 
     /// How much space is used by panels and windows.
@@ -828,6 +934,65 @@ impl Context {
         animated_value
     }
 
+    /// Smoothly animate towards `target`, e.g. a position or a color.
+    ///
+    /// The first time called it will return `target`.
+    /// When called with a new `target` it starts moving from the current value to it,
+    /// taking `duration` seconds.
+    ///
+    /// The function will call [`Self::request_repaint()`] while the value is moving.
+    ///
+    /// ```
+    /// # let ctx = egui::CtxRef::default();
+    /// # let hovered = false;
+    /// use egui::animation::Easing;
+    /// let color = if hovered { egui::Color32::WHITE } else { egui::Color32::GRAY };
+    /// let color = ctx.animate_value(egui::Id::new("button color"), color, 0.2, Easing::EaseOut);
+    /// ```
+    pub fn animate_value<T: Animatable>(
+        &self,
+        id: Id,
+        target: T,
+        duration: f32,
+        easing: Easing,
+    ) -> T {
+        let frame =
+            self.animation_manager
+                .lock()
+                .animate_value(&self.input, id, target, duration, easing);
+        self.handle_animation_frame(frame)
+    }
+
+    /// Play the keyframes, starting the first time this is called for the given `id`.
+    ///
+    /// Once started, the animation will keep playing the same keyframes
+    /// (even if you pass in different ones) until [`Self::restart_animation`] is called.
+    /// After the last keyframe it stays at the last value.
+    ///
+    /// The function will call [`Self::request_repaint()`] while the value is moving.
+    pub fn animate_keyframes<T: Animatable>(&self, id: Id, keyframes: &Keyframes<T>) -> T {
+        let frame = self
+            .animation_manager
+            .lock()
+            .animate_keyframes(&self.input, id, keyframes);
+        self.handle_animation_frame(frame)
+    }
+
+    /// Forget the animation with the given id, so that it starts over the next time it is used.
+    pub fn restart_animation(&self, id: Id) {
+        self.animation_manager.lock().restart(id);
+    }
+
+    fn handle_animation_frame<T>(&self, frame: crate::animation::AnimationFrame<T>) -> T {
+        if frame.in_progress {
+            self.request_repaint();
+        }
+        if let Some(on_complete) = frame.on_complete {
+            on_complete();
+        }
+        frame.value
+    }
+
     /// Clear memory of any animations.
     pub fn clear_animations(&self) {
         *self.animation_manager.lock() = Default::default();
@@ -1035,5 +1200,10 @@ impl Context {
         let mut style: Style = (*self.style()).clone();
         style.ui(ui);
         self.set_style(style);
+
+        #[cfg(feature = "theme_files")]
+        ui.collapsing("💾 Save as theme", |ui| {
+            crate::theme::save_theme_ui(self, ui);
+        });
     }
 }
@@ -0,0 +1,174 @@
+//! Attaching data to a drag, so that drop targets can accept it.
+//!
+//! See [`crate::Response::dnd_set_payload`], [`crate::Ui::dnd_drag_source`] and [`crate::Ui::dnd_drop_zone`].
+
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::{DroppedFile, Event, HoveredFile, InputState, Key, RawInput};
+
+/// The payload of the ongoing drag (if any), stored in [`crate::Memory`].
+///
+/// Files dragged in from the OS become a payload too:
+/// `Vec<HoveredFile>` while they are hovered and `Vec<DroppedFile>` once they are dropped.
+#[derive(Clone, Default)]
+pub(crate) struct DragAndDrop {
+    payload: Option<Arc<dyn Any + Send + Sync>>,
+
+    /// Files were dropped this frame, so the payload is released without any pointer release.
+    files_dropped: bool,
+}
+
+impl std::fmt::Debug for DragAndDrop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DragAndDrop")
+            .field("has_payload", &self.payload.is_some())
+            .field("files_dropped", &self.files_dropped)
+            .finish()
+    }
+}
+
+impl DragAndDrop {
+    pub(crate) fn begin_frame(&mut self, new_input: &RawInput) {
+        self.files_dropped = false;
+
+        if !new_input.dropped_files.is_empty() {
+            let files: Vec<DroppedFile> = new_input.dropped_files.clone();
+            self.payload = Some(Arc::new(files));
+            self.files_dropped = true;
+        } else if !new_input.hovered_files.is_empty() {
+            let files: Vec<HoveredFile> = new_input.hovered_files.clone();
+            self.payload = Some(Arc::new(files));
+        }
+
+        let escape_pressed = new_input.events.iter().any(|event| {
+            matches!(
+                event,
+                Event::Key {
+                    key: Key::Escape,
+                    pressed: true,
+                    ..
+                }
+            )
+        });
+        if escape_pressed {
+            self.cancel();
+        }
+    }
+
+    pub(crate) fn end_frame(&mut self, input: &InputState) {
+        if !input.pointer.any_down() {
+            // The drag ended this frame, and every drop target had its chance to accept the payload.
+            self.cancel();
+        }
+    }
+
+    pub(crate) fn set_payload<T: Any + Send + Sync>(&mut self, payload: T) {
+        self.payload = Some(Arc::new(payload));
+        self.files_dropped = false;
+    }
+
+    pub(crate) fn has_payload(&self) -> bool {
+        self.payload.is_some()
+    }
+
+    pub(crate) fn payload<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        self.payload.clone()?.downcast().ok()
+    }
+
+    /// Is the payload dropped this frame?
+    pub(crate) fn is_released(&self, input: &InputState) -> bool {
+        self.files_dropped || input.pointer.any_released()
+    }
+
+    /// Take the payload, if it is of the given type.
+    pub(crate) fn take_payload<T: Any + Send + Sync>(&mut self) -> Option<Arc<T>> {
+        let payload = self.payload::<T>()?;
+        self.cancel();
+        Some(payload)
+    }
+
+    pub(crate) fn cancel(&mut self) {
+        self.payload = None;
+        self.files_dropped = false;
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn run(ctx: &mut CtxRef, events: Vec<Event>, add_contents: impl FnOnce(&mut Ui)) {
+        let raw_input = RawInput {
+            events,
+            ..Default::default()
+        };
+        let _ = ctx.run(raw_input, |ctx| {
+            CentralPanel::default().show(ctx, add_contents);
+        });
+    }
+
+    fn pointer_button(pos: Pos2, pressed: bool) -> Event {
+        Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        }
+    }
+
+    /// Returns what the target received.
+    fn drag_to_target(ctx: &mut CtxRef, frames: Vec<Vec<Event>>) -> Option<std::sync::Arc<u32>> {
+        let mut received = None;
+        for events in frames {
+            run(ctx, events, |ui| {
+                let source = ui.add(Label::new("source").sense(Sense::drag()));
+                source.dnd_set_payload(42_u32);
+                ui.add_space(100.0);
+                let target = ui.label("target");
+                if let Some(payload) = target.dnd_release_payload::<u32>() {
+                    received = Some(payload);
+                }
+                assert!(target.dnd_release_payload::<String>().is_none());
+            });
+        }
+        received
+    }
+
+    #[test]
+    fn drag_payload_to_target() {
+        let mut ctx = CtxRef::default();
+        run(&mut ctx, vec![], |_| {}); // Lay out once, so we know where the widgets are.
+        let source = pos2(12.0, 12.0);
+        let target = pos2(12.0, 126.0);
+
+        let received = drag_to_target(
+            &mut ctx,
+            vec![
+                vec![Event::PointerMoved(source), pointer_button(source, true)],
+                vec![Event::PointerMoved(target)],
+                vec![pointer_button(target, false)],
+            ],
+        );
+        assert_eq!(received.as_deref(), Some(&42));
+        assert!(!ctx.memory().dnd_has_payload());
+
+        // Escape cancels the drag:
+        let escape = Event::Key {
+            key: Key::Escape,
+            pressed: true,
+            modifiers: Default::default(),
+        };
+        let received = drag_to_target(
+            &mut ctx,
+            vec![
+                vec![Event::PointerMoved(source), pointer_button(source, true)],
+                vec![Event::PointerMoved(target), escape],
+                vec![pointer_button(target, false)],
+            ],
+        );
+        assert_eq!(received, None);
+    }
+}
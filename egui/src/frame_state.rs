@@ -27,8 +27,8 @@ pub(crate) struct FrameState {
 
     /// Cleared by the first `ScrollArea` that makes use of it.
     pub(crate) scroll_delta: Vec2, // TODO: move to a Mutex inside of `InputState` ?
-    /// horizontal, vertical
-    pub(crate) scroll_target: [Option<(f32, Align)>; 2],
+    /// horizontal, vertical. The target position, how to align it, and whether to animate there.
+    pub(crate) scroll_target: [Option<(f32, Align, bool)>; 2],
 }
 
 impl Default for FrameState {
@@ -66,6 +66,14 @@ impl FrameState {
         *scroll_target = [None; 2];
     }
 
+    /// Ask the closest enclosing [`ScrollArea`] to scroll so that `rect` is visible.
+    pub(crate) fn scroll_to_rect(&mut self, rect: Rect, align: Align, animated: bool) {
+        for d in 0..2 {
+            let target = lerp(rect.min[d]..=rect.max[d], align.to_factor());
+            self.scroll_target[d] = Some((target, align, animated));
+        }
+    }
+
     /// How much space is still available after panels has been added.
     /// This is the "background" area, what egui doesn't cover with panels (but may cover with windows).
     /// This is also the area to which windows are constrained.
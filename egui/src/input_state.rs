@@ -1,3 +1,4 @@
+mod gesture_state;
 mod touch_state;
 
 use crate::data::input::*;
@@ -5,6 +6,8 @@ use crate::{emath::*, util::History};
 use std::collections::{BTreeMap, HashSet};
 
 pub use crate::data::input::Key;
+use gesture_state::GestureState;
+pub use gesture_state::{Swipe, SwipeDirection};
 pub use touch_state::MultiTouchInfo;
 use touch_state::TouchState;
 
@@ -33,6 +36,9 @@ pub struct InputState {
     /// (We keep a separate `TouchState` for each encountered touch device.)
     touch_states: BTreeMap<TouchDeviceId, TouchState>,
 
+    /// Long-presses, swipes and flings.
+    gestures: GestureState,
+
     /// How many pixels the user scrolled.
     pub scroll_delta: Vec2,
 
@@ -78,6 +84,7 @@ impl Default for InputState {
             raw: Default::default(),
             pointer: Default::default(),
             touch_states: Default::default(),
+            gestures: Default::default(),
             scroll_delta: Vec2::ZERO,
             zoom_factor_delta: 1.0,
             screen_rect: Rect::from_min_size(Default::default(), vec2(10_000.0, 10_000.0)),
@@ -104,7 +111,10 @@ impl InputState {
         for touch_state in self.touch_states.values_mut() {
             touch_state.begin_frame(time, &new, self.pointer.interact_pos);
         }
+        let press_origin = self.pointer.press_origin(); // Forgotten by the pointer on release
         let pointer = self.pointer.begin_frame(time, &new);
+        let mut gestures = self.gestures;
+        gestures.begin_frame(press_origin, &pointer);
 
         let mut keys_down = self.keys_down;
         let mut scroll_delta = Vec2::ZERO;
@@ -130,6 +140,7 @@ impl InputState {
         InputState {
             pointer,
             touch_states: self.touch_states,
+            gestures,
             scroll_delta,
             zoom_factor_delta,
             screen_rect,
@@ -189,7 +200,10 @@ impl InputState {
     }
 
     pub fn wants_repaint(&self) -> bool {
-        self.pointer.wants_repaint() || self.scroll_delta != Vec2::ZERO || !self.events.is_empty()
+        self.pointer.wants_repaint()
+            || (self.any_touches() && self.gestures.wants_repaint(&self.pointer))
+            || self.scroll_delta != Vec2::ZERO
+            || !self.events.is_empty()
     }
 
     /// Was the given key pressed this frame?
@@ -288,6 +302,39 @@ impl InputState {
         }
     }
 
+    /// Rotation in radians this frame, from a multi-touch gesture. `0.0` if there is none.
+    ///
+    /// See [`MultiTouchInfo::rotation_delta`].
+    pub fn rotation_delta(&self) -> f32 {
+        self.multi_touch().map_or(0.0, |touch| touch.rotation_delta)
+    }
+
+    /// Was the pointer held still for a while this frame (usually with a finger)?
+    ///
+    /// Only `true` on the frame the long-press is recognized, while the pointer is still down.
+    /// The position is [`PointerState::press_origin`].
+    /// A long-press is never also a click.
+    ///
+    /// See also [`crate::Response::long_pressed`].
+    pub fn long_pressed(&self) -> bool {
+        self.gestures.long_pressed()
+    }
+
+    /// A swipe that ended this frame, if any.
+    ///
+    /// See also [`crate::Response::swiped`].
+    pub fn swipe(&self) -> Option<Swipe> {
+        self.gestures.swipe()
+    }
+
+    /// If a drag was released while moving fast this frame (a "fling"),
+    /// this is how fast it was moving, in points per second.
+    ///
+    /// [`crate::ScrollArea`] uses this for kinetic scrolling.
+    pub fn fling_velocity(&self) -> Option<Vec2> {
+        self.gestures.fling_velocity()
+    }
+
     /// True if there currently are any fingers touching egui.
     pub fn any_touches(&self) -> bool {
         !self.touch_states.is_empty()
@@ -687,6 +734,7 @@ impl InputState {
             raw,
             pointer,
             touch_states,
+            gestures,
             scroll_delta,
             zoom_factor_delta,
             screen_rect,
@@ -714,6 +762,7 @@ impl InputState {
             });
         }
 
+        ui.label(format!("gestures: {:?}", gestures));
         ui.label(format!("scroll_delta: {:?} points", scroll_delta));
         ui.label(format!("zoom_factor_delta: {:4.2}x", zoom_factor_delta));
         ui.label(format!("screen_rect: {:?} points", screen_rect));
@@ -0,0 +1,235 @@
+use crate::emath::{Pos2, Vec2};
+
+use super::PointerState;
+
+/// Holding the pointer still for this long is a long-press.
+///
+/// This is the same as the longest click, so a long-press never also becomes a click.
+const LONG_PRESS_DURATION: f64 = super::MAX_CLICK_DURATION;
+
+/// A swipe must move the pointer at least this far, in points.
+const MIN_SWIPE_DISTANCE: f32 = 30.0;
+
+/// A swipe must be released at least this fast, in points per second.
+const MIN_SWIPE_SPEED: f32 = 300.0;
+
+/// A drag released at least this fast is a fling, in points per second.
+const MIN_FLING_SPEED: f32 = 100.0;
+
+/// In which direction a [`Swipe`] went.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// A quick, mostly straight movement of the pointer (usually a finger) that was then released.
+///
+/// See [`crate::InputState::swipe`] and [`crate::Response::swiped`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Swipe {
+    pub direction: SwipeDirection,
+
+    /// Where the pointer was pressed.
+    pub start_pos: Pos2,
+
+    /// Where the pointer was released.
+    pub end_pos: Pos2,
+
+    /// How fast the pointer moved when released, in points per second.
+    pub velocity: Vec2,
+}
+
+/// Recognizes single-pointer gestures (long-press, swipe, fling) from the [`PointerState`].
+#[derive(Clone, Debug, Default)]
+pub(crate) struct GestureState {
+    /// Has the current press already been reported as a long-press?
+    long_press_reported: bool,
+
+    /// Only set on the frame the gesture is recognized:
+    long_pressed: bool,
+    swipe: Option<Swipe>,
+    fling_velocity: Option<Vec2>,
+}
+
+impl GestureState {
+    /// `press_origin` is where the pointer was pressed, as of last frame.
+    pub fn begin_frame(&mut self, press_origin: Option<Pos2>, current: &PointerState) {
+        self.long_pressed = false;
+        self.swipe = None;
+        self.fling_velocity = None;
+
+        match current.press_start_time() {
+            Some(press_start_time) if current.any_down() => {
+                if !self.long_press_reported
+                    && !current.has_moved_too_much_for_a_click
+                    && current.time - press_start_time >= LONG_PRESS_DURATION
+                {
+                    self.long_pressed = true;
+                    self.long_press_reported = true;
+                }
+            }
+            _ => {
+                self.long_press_reported = false;
+            }
+        }
+
+        if current.any_released() && current.has_moved_too_much_for_a_click {
+            let velocity = current.velocity();
+            if velocity.length() >= MIN_FLING_SPEED {
+                self.fling_velocity = Some(velocity);
+            }
+
+            if let (Some(start_pos), Some(end_pos)) = (press_origin, current.interact_pos()) {
+                self.swipe = recognize_swipe(start_pos, end_pos, velocity);
+            }
+        }
+    }
+
+    /// A long-press that hasn't been recognized yet needs another frame to be recognized.
+    ///
+    /// Only used for touches, so that holding a mouse button down doesn't repaint every frame.
+    pub fn wants_repaint(&self, pointer: &PointerState) -> bool {
+        pointer.any_down() && !self.long_press_reported && !pointer.has_moved_too_much_for_a_click
+    }
+
+    pub fn long_pressed(&self) -> bool {
+        self.long_pressed
+    }
+
+    pub fn swipe(&self) -> Option<Swipe> {
+        self.swipe
+    }
+
+    pub fn fling_velocity(&self) -> Option<Vec2> {
+        self.fling_velocity
+    }
+}
+
+/// A swipe is fast, long enough, and mostly along one axis.
+fn recognize_swipe(start_pos: Pos2, end_pos: Pos2, velocity: Vec2) -> Option<Swipe> {
+    let delta = end_pos - start_pos;
+    if delta.length() < MIN_SWIPE_DISTANCE || velocity.length() < MIN_SWIPE_SPEED {
+        return None;
+    }
+
+    let direction = if delta.x.abs() > 2.0 * delta.y.abs() {
+        if delta.x < 0.0 {
+            SwipeDirection::Left
+        } else {
+            SwipeDirection::Right
+        }
+    } else if delta.y.abs() > 2.0 * delta.x.abs() {
+        if delta.y < 0.0 {
+            SwipeDirection::Up
+        } else {
+            SwipeDirection::Down
+        }
+    } else {
+        return None; // diagonal
+    };
+
+    Some(Swipe {
+        direction,
+        start_pos,
+        end_pos,
+        velocity,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        pos2, Event, InputState, PointerButton, RawInput, SwipeDirection, TouchDeviceId, TouchId,
+        TouchPhase,
+    };
+
+    fn frame(input: InputState, time: f64, events: Vec<Event>) -> InputState {
+        input.begin_frame(RawInput {
+            time: Some(time),
+            events,
+            ..Default::default()
+        })
+    }
+
+    fn button(pos: crate::Pos2, pressed: bool) -> Event {
+        Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        }
+    }
+
+    fn touch(pos: crate::Pos2, phase: TouchPhase) -> Event {
+        Event::Touch {
+            device_id: TouchDeviceId(0),
+            id: TouchId(0),
+            phase,
+            pos,
+            force: 0.0,
+        }
+    }
+
+    #[test]
+    fn long_press_is_reported_once() {
+        let pos = pos2(10.0, 10.0);
+        let mut input = frame(
+            InputState::default(),
+            0.0,
+            vec![touch(pos, TouchPhase::Start), button(pos, true)],
+        );
+        assert!(!input.long_pressed());
+        input = frame(input, 0.1, vec![]);
+        assert!(input.wants_repaint(), "waiting for the long-press");
+
+        input = frame(input, 1.0, vec![]);
+        assert!(input.long_pressed());
+        input = frame(input, 2.0, vec![]);
+        assert!(!input.long_pressed());
+
+        input = frame(input, 2.1, vec![button(pos, false)]);
+        assert!(input
+            .pointer
+            .pointer_events
+            .iter()
+            .all(|event| !event.is_click()));
+    }
+
+    #[test]
+    fn holding_a_mouse_button_does_not_repaint() {
+        let pos = pos2(10.0, 10.0);
+        let mut input = frame(InputState::default(), 0.0, vec![button(pos, true)]);
+        input = frame(input, 0.1, vec![]);
+        assert!(!input.wants_repaint());
+    }
+
+    #[test]
+    fn swipe_and_fling() {
+        let mut input = frame(
+            InputState::default(),
+            0.0,
+            vec![button(pos2(100.0, 10.0), true)],
+        );
+        for i in 1..=5 {
+            let x = 100.0 - 20.0 * i as f32;
+            input = frame(
+                input,
+                0.01 * i as f64,
+                vec![Event::PointerMoved(pos2(x, 12.0))],
+            );
+        }
+        input = frame(input, 0.06, vec![button(pos2(0.0, 12.0), false)]);
+
+        let swipe = input.swipe().unwrap();
+        assert_eq!(swipe.direction, SwipeDirection::Left);
+        assert_eq!(swipe.start_pos, pos2(100.0, 10.0));
+        assert!(input.fling_velocity().unwrap().x < 0.0);
+
+        input = frame(input, 0.07, vec![]);
+        assert!(input.swipe().is_none());
+        assert!(input.fling_velocity().is_none());
+    }
+}
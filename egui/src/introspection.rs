@@ -94,6 +94,8 @@ impl Widget for &epaint::stats::PaintStats {
                 clipped_meshes,
                 vertices,
                 indices,
+                cached_layers,
+                tessellated_layers,
             } = self;
 
             ui.label("Intermediate:");
@@ -117,6 +119,19 @@ impl Widget for &epaint::stats::PaintStats {
             label(ui, indices, "indices").on_hover_text("Three 32-bit indices per triangles");
             ui.add_space(10.0);
 
+            ui.label("Tessellation cache:");
+            let hit_rate = self
+                .cache_hit_rate()
+                .map_or_else(|| "-".to_owned(), |rate| format!("{:.0}%", 100.0 * rate));
+            ui.label(format!(
+                "{:6} layers reused, {} tessellated ({} hit rate)",
+                cached_layers, tessellated_layers, hit_rate
+            ))
+            .on_hover_text(
+                "Layers whose shapes didn't change since last frame are not re-tessellated",
+            );
+            ui.add_space(10.0);
+
             // ui.label("Total:");
             // ui.label(self.total().format(""));
         })
@@ -135,6 +150,7 @@ impl Widget for &mut epaint::TessellationOptions {
                 pixels_per_point: _,
                 aa_size: _,
                 anti_alias,
+                bezier_tolerance,
                 coarse_tessellation_culling,
                 round_text_to_pixels,
                 debug_paint_clip_rects,
@@ -143,6 +159,14 @@ impl Widget for &mut epaint::TessellationOptions {
             } = self;
             ui.checkbox(anti_alias, "Antialias")
                 .on_hover_text("Turn off for small performance gain.");
+            ui.add(
+                crate::widgets::Slider::new(bezier_tolerance, 0.01..=10.0)
+                    .logarithmic(true)
+                    .text("Bezier tolerance"),
+            )
+            .on_hover_text(
+                "Maximum distance in pixels between a curve and the lines it is drawn with.",
+            );
             ui.collapsing("debug", |ui| {
                 ui.checkbox(
                     coarse_tessellation_culling,
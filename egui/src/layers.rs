@@ -162,8 +162,19 @@ impl GraphicLayers {
             .or_default()
     }
 
-    pub fn drain(&mut self, area_order: &[LayerId]) -> impl ExactSizeIterator<Item = ClippedShape> {
+    /// Returns all shapes, back to front,
+    /// together with which layer they came from and how many shapes each layer has.
+    pub fn drain(&mut self, area_order: &[LayerId]) -> (Vec<ClippedShape>, Vec<(LayerId, usize)>) {
         let mut all_shapes: Vec<_> = Default::default();
+        let mut layers = vec![];
+
+        let mut append = |layer_id: LayerId, list: &Mutex<PaintList>| {
+            let mut list = list.lock();
+            if !list.is_empty() {
+                layers.push((layer_id, list.0.len()));
+                all_shapes.append(&mut list.0);
+            }
+        };
 
         for &order in &Order::ALL {
             let order_map = &mut self.0[order as usize];
@@ -176,18 +187,18 @@ impl GraphicLayers {
             // First do the layers part of area_order:
             for layer_id in area_order {
                 if layer_id.order == order {
-                    if let Some(list) = order_map.get_mut(&layer_id.id) {
-                        all_shapes.append(&mut list.lock().0);
+                    if let Some(list) = order_map.get(&layer_id.id) {
+                        append(*layer_id, list);
                     }
                 }
             }
 
             // Also draw areas that are missing in `area_order`:
-            for shapes in order_map.values_mut() {
-                all_shapes.append(&mut shapes.lock().0);
+            for (&id, list) in order_map.iter() {
+                append(LayerId::new(order, id), list);
             }
         }
 
-        all_shapes.into_iter()
+        (all_shapes, layers)
     }
 }
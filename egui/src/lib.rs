@@ -356,10 +356,12 @@
 #![allow(clippy::float_cmp)]
 #![allow(clippy::manual_range_contains)]
 
+pub mod animation;
 mod animation_manager;
 pub mod containers;
 mod context;
 mod data;
+mod drag_and_drop;
 mod frame_state;
 pub(crate) mod grid;
 mod id;
@@ -371,9 +373,13 @@ mod memory;
 pub mod menu;
 mod painter;
 pub(crate) mod placer;
+#[cfg(feature = "remote")]
+pub mod remote;
 mod response;
 mod sense;
+mod tessellation_cache;
 pub mod style;
+mod theme;
 mod ui;
 pub mod util;
 mod widget_text;
@@ -386,7 +392,7 @@ pub use emath::{lerp, pos2, remap, remap_clamp, vec2, Align, Align2, NumExt, Pos
 pub use epaint::{
     color, mutex,
     text::{FontData, FontDefinitions, FontFamily, TextStyle},
-    ClippedMesh, Color32, FontImage, Rgba, Shape, Stroke, TextureId,
+    Brush, ClippedMesh, ClippedPrimitive, Color32, FontImage, Rgba, Shape, Stroke, TextureId,
 };
 
 pub mod text {
@@ -405,15 +411,16 @@ pub use {
     },
     grid::Grid,
     id::{Id, IdMap},
-    input_state::{InputState, MultiTouchInfo, PointerState},
+    input_state::{InputState, MultiTouchInfo, PointerState, Swipe, SwipeDirection},
     layers::{LayerId, Order},
     layout::*,
     memory::Memory,
     painter::Painter,
     response::{InnerResponse, Response},
     sense::Sense,
-    style::{Style, Visuals},
+    style::{Style, StyleClass, Visuals},
     text::{Galley, TextFormat},
+    theme::{Theme, ThemeFonts},
     ui::Ui,
     widget_text::{RichText, WidgetText},
     widgets::*,
@@ -548,7 +555,7 @@ pub mod special_emojis {
 }
 
 /// The different types of built-in widgets in egui
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum WidgetType {
     Label, // TODO: emit Label events
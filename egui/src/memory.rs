@@ -1,6 +1,6 @@
 use epaint::ahash::AHashSet;
 
-use crate::{area, window, Id, IdMap, InputState, LayerId, Pos2, Rect, Style};
+use crate::{area, window, Id, IdMap, InputState, LayerId, Pos2, Rect, Style, StyleClass};
 
 // ----------------------------------------------------------------------------
 
@@ -53,6 +53,11 @@ pub struct Memory {
     #[cfg_attr(feature = "persistence", serde(skip))]
     pub caches: crate::util::cache::CacheStorage,
 
+    /// [`Style`]:s with a [`StyleClass`] applied, by the [`Id`] of the class,
+    /// as `(style, style with the class)`. See [`crate::Context::style_with_class`].
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub(crate) classed_styles: IdMap<Vec<(std::sync::Arc<Style>, std::sync::Arc<Style>)>>,
+
     // ------------------------------------------
     /// new scale that will be applied at the start of the next frame
     #[cfg_attr(feature = "persistence", serde(skip))]
@@ -78,6 +83,13 @@ pub struct Memory {
     #[cfg_attr(feature = "persistence", serde(skip))]
     popup: Option<Id>,
 
+    /// The backdrops of the open [`crate::Modal`]s.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub(crate) modals: Modals,
+
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub(crate) drag_and_drop: crate::drag_and_drop::DragAndDrop,
+
     #[cfg_attr(feature = "persistence", serde(skip))]
     everything_is_visible: bool,
 }
@@ -93,6 +105,10 @@ pub struct Options {
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) style: std::sync::Arc<Style>,
 
+    /// The classes registered with [`crate::Context::set_style_class`], by the [`Id`] of their name.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) style_classes: IdMap<std::sync::Arc<StyleClass>>,
+
     /// Controls the tessellator.
     pub tessellation_options: epaint::TessellationOptions,
 
@@ -159,11 +175,21 @@ pub(crate) struct Focus {
     /// If `true`, pressing tab will NOT move focus away from the current widget.
     is_focus_locked: bool,
 
+    /// If `true`, pressing escape will NOT remove focus from the current widget.
+    is_escape_locked: bool,
+
     /// Set at the beginning of the frame, set to `false` when "used".
     pressed_tab: bool,
 
     /// Set at the beginning of the frame, set to `false` when "used".
     pressed_shift_tab: bool,
+
+    /// A [`crate::TabBar`] switches tabs with Ctrl+Tab, so it shouldn't also move the focus.
+    ctrl_tab_claimed: bool,
+
+    /// Was Ctrl+Tab claimed last frame? Tab presses are handled at the start of the frame,
+    /// before any `TabBar` is shown, so this is what decides what Ctrl+Tab does.
+    ctrl_tab_claimed_last_frame: bool,
 }
 
 impl Interaction {
@@ -208,6 +234,7 @@ impl Focus {
 
         self.pressed_tab = false;
         self.pressed_shift_tab = false;
+        self.ctrl_tab_claimed_last_frame = std::mem::take(&mut self.ctrl_tab_claimed);
         for event in &new_input.events {
             if matches!(
                 event,
@@ -217,8 +244,10 @@ impl Focus {
                     modifiers: _,
                 }
             ) {
-                self.id = None;
-                self.is_focus_locked = false;
+                if !self.is_escape_locked {
+                    self.id = None;
+                    self.is_focus_locked = false;
+                }
                 break;
             }
 
@@ -228,7 +257,8 @@ impl Focus {
                 modifiers,
             } = event
             {
-                if !self.is_focus_locked {
+                let switches_tabs = modifiers.ctrl && self.ctrl_tab_claimed_last_frame;
+                if !self.is_focus_locked && !switches_tabs {
                     if modifiers.shift {
                         self.pressed_shift_tab = true;
                     } else {
@@ -284,6 +314,14 @@ impl Memory {
         new_input: &crate::data::input::RawInput,
     ) {
         self.interaction.begin_frame(prev_input, new_input);
+        self.modals.begin_frame();
+        self.drag_and_drop.begin_frame(new_input);
+
+        // Forget the classed styles of styles that no `Ui` uses anymore:
+        self.classed_styles.retain(|_, classed_styles| {
+            classed_styles.retain(|(style, _)| std::sync::Arc::strong_count(style) > 1);
+            !classed_styles.is_empty()
+        });
 
         if !prev_input.pointer.any_down() {
             self.window_interaction = None;
@@ -295,6 +333,7 @@ impl Memory {
         self.areas.end_frame();
         self.interaction.focus.end_frame(used_ids);
         self.drag_value.end_frame(input);
+        self.drag_and_drop.end_frame(input);
     }
 
     /// Top-most layer at the given position.
@@ -306,6 +345,13 @@ impl Memory {
         self.interaction.focus.id_previous_frame == Some(id)
     }
 
+    /// Ctrl+Tab switches tabs in a [`crate::TabBar`] instead of moving the keyboard focus.
+    ///
+    /// Call this each frame, and Ctrl+Tab moves the focus like Tab again once you stop.
+    pub(crate) fn claim_ctrl_tab(&mut self) {
+        self.interaction.focus.ctrl_tab_claimed = true;
+    }
+
     /// True if the given widget had keyboard focus last frame, but not this one.
     pub(crate) fn lost_focus(&self, id: Id) -> bool {
         self.had_focus_last_frame(id) && !self.has_focus(id)
@@ -335,6 +381,15 @@ impl Memory {
         }
     }
 
+    /// Prevent keyboard focus from being removed from this widget when the user presses the escape key.
+    /// This is useful when the widget wants to handle escape itself, e.g. to close a popup.
+    /// You must first give focus to the widget before calling this.
+    pub fn lock_focus_on_escape(&mut self, id: Id, lock_focus: bool) {
+        if self.had_focus_last_frame(id) && self.has_focus(id) {
+            self.interaction.focus.is_escape_locked = lock_focus;
+        }
+    }
+
     /// Is the keyboard focus locked on this widget? If so the focus won't move even if the user presses the tab key.
     pub fn has_lock_focus(&mut self, id: Id) -> bool {
         if self.had_focus_last_frame(id) && self.has_focus(id) {
@@ -350,6 +405,7 @@ impl Memory {
     pub fn request_focus(&mut self, id: Id) {
         self.interaction.focus.id = Some(id);
         self.interaction.focus.is_focus_locked = false;
+        self.interaction.focus.is_escape_locked = false;
     }
 
     /// Surrender keyboard focus for a specific widget.
@@ -359,6 +415,7 @@ impl Memory {
         if self.interaction.focus.id == Some(id) {
             self.interaction.focus.id = None;
             self.interaction.focus.is_focus_locked = false;
+            self.interaction.focus.is_escape_locked = false;
         }
     }
 
@@ -436,6 +493,78 @@ impl Memory {
     }
 }
 
+/// ## Drag and drop
+impl Memory {
+    /// Is anything being dragged with a payload (see [`crate::Response::dnd_set_payload`])?
+    ///
+    /// This includes files being dragged in from the OS.
+    pub fn dnd_has_payload(&self) -> bool {
+        self.drag_and_drop.has_payload()
+    }
+
+    /// The payload of the ongoing drag, if it is of type `T`.
+    pub fn dnd_payload<T: std::any::Any + Send + Sync>(&self) -> Option<std::sync::Arc<T>> {
+        self.drag_and_drop.payload()
+    }
+
+    /// Drop the payload without giving it to anyone. Pressing Escape does this too.
+    pub fn dnd_cancel(&mut self) {
+        self.drag_and_drop.cancel();
+    }
+}
+
+/// ## Modals
+impl Memory {
+    /// Is there a [`crate::Modal`] on top of this layer?
+    ///
+    /// Layers below a modal can't be hovered, clicked or focused.
+    pub fn is_blocked_by_modal(&self, layer_id: LayerId) -> bool {
+        match self.top_modal() {
+            Some(top) => self.areas.is_below(layer_id, top),
+            None => false,
+        }
+    }
+
+    /// The backdrop of the top-most open [`crate::Modal`].
+    pub(crate) fn top_modal(&self) -> Option<LayerId> {
+        self.modals
+            .previous_frame
+            .iter()
+            .chain(&self.modals.current_frame)
+            .copied()
+            .reduce(|top, layer_id| {
+                if self.areas.is_below(top, layer_id) {
+                    layer_id
+                } else {
+                    top
+                }
+            })
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// The backdrop layers of the [`crate::Modal`]s shown this frame and the previous frame.
+///
+/// We look at the previous frame too, so that a modal blocks the widgets that were added before it.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Modals {
+    previous_frame: Vec<LayerId>,
+    current_frame: Vec<LayerId>,
+}
+
+impl Modals {
+    fn begin_frame(&mut self) {
+        self.previous_frame = std::mem::take(&mut self.current_frame);
+    }
+
+    pub(crate) fn register(&mut self, backdrop: LayerId) {
+        if !self.current_frame.contains(&backdrop) {
+            self.current_frame.push(backdrop);
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 /// Keeps track of `Area`s, which are free-floating `Ui`s.
@@ -523,6 +652,28 @@ impl Areas {
             .collect()
     }
 
+    /// Is `a` painted below `b`?
+    ///
+    /// Layers that aren't in any known order (e.g. panels) are below all areas of the same [`crate::Order`].
+    pub(crate) fn is_below(&self, a: LayerId, b: LayerId) -> bool {
+        let index = |layer_id| {
+            self.order
+                .iter()
+                .position(|x| *x == layer_id)
+                .map_or(-1, |index| index as i64)
+        };
+        (a.order, index(a)) < (b.order, index(b))
+    }
+
+    /// Forget the area, e.g. when it will never be shown again.
+    pub(crate) fn remove(&mut self, layer_id: LayerId) {
+        self.areas.remove(&layer_id.id);
+        self.order.retain(|x| *x != layer_id);
+        self.visible_last_frame.remove(&layer_id);
+        self.visible_current_frame.remove(&layer_id);
+        self.wants_to_be_on_top.remove(&layer_id);
+    }
+
     pub fn move_to_top(&mut self, layer_id: LayerId) {
         self.visible_current_frame.insert(layer_id);
         self.wants_to_be_on_top.insert(layer_id);
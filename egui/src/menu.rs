@@ -351,6 +351,12 @@ impl MenuRoot {
                 }
             }
         }
+        if response.long_pressed() && response.ctx.input().any_touches() {
+            // Touch screens have no secondary button:
+            if let Some(pos) = pointer.press_origin() {
+                return MenuResponse::Create(pos, id);
+            }
+        }
         MenuResponse::Stay
     }
 
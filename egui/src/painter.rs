@@ -6,7 +6,7 @@ use crate::{
 use epaint::{
     mutex::Mutex,
     text::{Fonts, Galley, TextStyle},
-    CircleShape, RectShape, Shape, Stroke, TextShape,
+    Brush, CircleShape, RectShape, Shape, Stroke, TextShape,
 };
 
 /// Helper to paint shapes and text to a specific region on a specific layer.
@@ -253,8 +253,9 @@ impl Painter {
         self.add(CircleShape {
             center,
             radius,
-            fill: fill_color.into(),
+            fill: Brush::Solid(fill_color.into()),
             stroke: stroke.into(),
+            dash: None,
         });
     }
 
@@ -262,8 +263,9 @@ impl Painter {
         self.add(CircleShape {
             center,
             radius,
-            fill: fill_color.into(),
+            fill: Brush::Solid(fill_color.into()),
             stroke: Default::default(),
+            dash: None,
         });
     }
 
@@ -273,6 +275,7 @@ impl Painter {
             radius,
             fill: Default::default(),
             stroke: stroke.into(),
+            dash: None,
         });
     }
 
@@ -286,8 +289,9 @@ impl Painter {
         self.add(RectShape {
             rect,
             corner_radius,
-            fill: fill_color.into(),
+            fill: Brush::Solid(fill_color.into()),
             stroke: stroke.into(),
+            dash: None,
         });
     }
 
@@ -295,8 +299,9 @@ impl Painter {
         self.add(RectShape {
             rect,
             corner_radius,
-            fill: fill_color.into(),
+            fill: Brush::Solid(fill_color.into()),
             stroke: Default::default(),
+            dash: None,
         });
     }
 
@@ -306,6 +311,7 @@ impl Painter {
             corner_radius,
             fill: Default::default(),
             stroke: stroke.into(),
+            dash: None,
         });
     }
 
@@ -0,0 +1,631 @@
+//! Run egui in one process (e.g. a headless server) and paint it in another (a thin client).
+//!
+//! The server runs egui as usual and encodes each tessellated frame with a [`FrameEncoder`].
+//! The client decodes it with a [`FrameDecoder`] and hands the result to any painter.
+//! In the other direction the client sends its [`RawInput`] with [`encode_input`],
+//! which the server decodes with [`decode_input`] and passes to [`crate::CtxRef::run`].
+//!
+//! Frames are sent as [`ClippedMesh`]es rather than [`crate::epaint::ClippedShape`]s,
+//! so the client needs no fonts of its own.
+//! Meshes that are the same as last frame, and the parts of the font texture that haven't changed,
+//! are not sent again. This means every frame must be decoded, in order, by the same [`FrameDecoder`].
+//! If a client (re)connects, call [`FrameEncoder::reset`] so the next frame is sent in full.
+//!
+//! The messages are a small header ([`MAGIC`] and [`FORMAT_VERSION`]) followed by
+//! [bincode](https://crates.io/crates/bincode).
+//! Use [`write_message`] and [`read_message`] to send them over a stream, e.g. a `TcpStream`.
+//!
+//! This module is only available with the `remote` feature.
+
+use std::hash::{Hash, Hasher};
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use epaint::ahash::AHashMap;
+
+use crate::{ClippedMesh, Color32, FontImage, RawInput, TextureId};
+
+/// Every message starts with these four bytes.
+pub const MAGIC: [u8; 4] = *b"egui";
+
+/// Increased whenever the encoding changes.
+/// A decoder only accepts messages of the same version.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// [`read_message`] refuses messages larger than this many bytes,
+/// so that a corrupt or malicious length prefix can't make it allocate huge amounts of memory.
+pub const MAX_MESSAGE_SIZE: usize = 256 * 1024 * 1024;
+
+const KIND_FRAME: u8 = 0;
+const KIND_INPUT: u8 = 1;
+
+/// Why a message could not be decoded.
+#[derive(Debug)]
+pub enum DecodeError {
+    /// The message doesn't start with [`MAGIC`], or is of the wrong kind (e.g. input instead of a frame).
+    NotEgui,
+
+    /// The message was encoded with another [`FORMAT_VERSION`].
+    UnsupportedVersion(u16),
+
+    /// The message refers to a previous frame that this [`FrameDecoder`] hasn't decoded.
+    MissingPreviousFrame,
+
+    /// The message is corrupt.
+    Corrupt(String),
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotEgui => write!(f, "Not an egui message of the expected kind"),
+            Self::UnsupportedVersion(version) => write!(
+                f,
+                "Unsupported egui message version {} (expected {})",
+                version, FORMAT_VERSION
+            ),
+            Self::MissingPreviousFrame => {
+                write!(f, "The egui frame refers to a frame that was never decoded")
+            }
+            Self::Corrupt(err) => write!(f, "Corrupt egui message: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+// ----------------------------------------------------------------------------
+
+/// A change to a user texture ([`TextureId::User`]).
+///
+/// egui itself doesn't know about user textures,
+/// so the server needs to report them to [`FrameEncoder::encode`]
+/// (e.g. from `epi::backend::TexAllocationData`).
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum TextureUpdate {
+    /// Allocate (or replace) the texture with the given id.
+    Set {
+        id: u64,
+        /// width, height
+        size: [usize; 2],
+        /// Row by row, top to bottom.
+        pixels: Vec<Color32>,
+    },
+
+    /// The texture with the given id is no longer used.
+    Free { id: u64 },
+}
+
+/// Rows of the font texture.
+#[derive(serde::Deserialize, serde::Serialize)]
+struct FontImageUpdate {
+    version: u64,
+    width: usize,
+    height: usize,
+    /// The rows from here to the bottom are sent; the ones above are the same as last time.
+    first_row: usize,
+    pixels: Vec<u8>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+enum MeshUpdate {
+    /// The same as the mesh with this index in the previous frame.
+    Same(u32),
+    New(ClippedMesh),
+}
+
+#[derive(serde::Deserialize, serde::Serialize)]
+struct FrameMessage {
+    frame_nr: u64,
+    /// The frame that [`MeshUpdate::Same`] and partial font image updates refer to.
+    previous_frame_nr: Option<u64>,
+    font_image: Option<FontImageUpdate>,
+    textures: Vec<TextureUpdate>,
+    meshes: Vec<MeshUpdate>,
+}
+
+// ----------------------------------------------------------------------------
+
+/// Encodes frames on the server side. See the [module level docs](self).
+#[derive(Default)]
+pub struct FrameEncoder {
+    frame_nr: u64,
+    previous: Option<PreviousFrame>,
+}
+
+struct PreviousFrame {
+    frame_nr: u64,
+    font_image: FontImage,
+    clipped_meshes: Vec<ClippedMesh>,
+    mesh_indices: AHashMap<u64, u32>,
+}
+
+impl FrameEncoder {
+    /// Forget what has been sent, so that the next frame is sent in full.
+    ///
+    /// Call this when a new client connects.
+    pub fn reset(&mut self) {
+        self.previous = None;
+    }
+
+    /// Encode the output of one frame.
+    ///
+    /// `font_image` is [`crate::Context::font_image`], and `clipped_meshes` the output of [`crate::Context::tessellate`].
+    pub fn encode(
+        &mut self,
+        font_image: &FontImage,
+        clipped_meshes: &[ClippedMesh],
+        texture_updates: &[TextureUpdate],
+    ) -> Vec<u8> {
+        let frame_nr = self.frame_nr;
+        self.frame_nr += 1;
+
+        let previous = self.previous.take();
+
+        let font_image_update = match &previous {
+            Some(previous) if previous.font_image.version == font_image.version => None,
+            Some(previous)
+                if previous.font_image.width == font_image.width
+                    && previous.font_image.height <= font_image.height =>
+            {
+                let width = font_image.width;
+                let first_row = (0..previous.font_image.height)
+                    .find(|&y| {
+                        let row = y * width..(y + 1) * width;
+                        previous.font_image.pixels[row.clone()] != font_image.pixels[row]
+                    })
+                    .unwrap_or(previous.font_image.height);
+                Some(font_image_rows(font_image, first_row))
+            }
+            _ => Some(font_image_rows(font_image, 0)),
+        };
+
+        let mut mesh_indices = AHashMap::default();
+        let meshes = clipped_meshes
+            .iter()
+            .enumerate()
+            .map(|(index, clipped_mesh)| {
+                let mesh_hash = hash_clipped_mesh(clipped_mesh);
+                mesh_indices.entry(mesh_hash).or_insert(index as u32);
+
+                let same_as_before = previous.as_ref().and_then(|previous| {
+                    let previous_index = *previous.mesh_indices.get(&mesh_hash)?;
+                    let ClippedMesh(clip_rect, mesh) =
+                        &previous.clipped_meshes[previous_index as usize];
+                    if *clip_rect == clipped_mesh.0 && *mesh == clipped_mesh.1 {
+                        Some(previous_index)
+                    } else {
+                        None
+                    }
+                });
+
+                match same_as_before {
+                    Some(previous_index) => MeshUpdate::Same(previous_index),
+                    None => MeshUpdate::New(clipped_mesh.clone()),
+                }
+            })
+            .collect();
+
+        let message = FrameMessage {
+            frame_nr,
+            previous_frame_nr: previous.map(|previous| previous.frame_nr),
+            font_image: font_image_update,
+            textures: texture_updates.to_vec(),
+            meshes,
+        };
+
+        self.previous = Some(PreviousFrame {
+            frame_nr,
+            font_image: font_image.clone(),
+            clipped_meshes: clipped_meshes.to_vec(),
+            mesh_indices,
+        });
+
+        encode(KIND_FRAME, &message)
+    }
+}
+
+fn font_image_rows(font_image: &FontImage, first_row: usize) -> FontImageUpdate {
+    FontImageUpdate {
+        version: font_image.version,
+        width: font_image.width,
+        height: font_image.height,
+        first_row,
+        pixels: font_image.pixels[first_row * font_image.width..].to_vec(),
+    }
+}
+
+fn hash_clipped_mesh(ClippedMesh(clip_rect, mesh): &ClippedMesh) -> u64 {
+    let mut hasher = epaint::ahash::AHasher::new_with_keys(123, 456);
+    for coordinate in [
+        clip_rect.min.x,
+        clip_rect.min.y,
+        clip_rect.max.x,
+        clip_rect.max.y,
+    ] {
+        coordinate.to_bits().hash(&mut hasher);
+    }
+    mesh.hash(&mut hasher);
+    hasher.finish()
+}
+
+// ----------------------------------------------------------------------------
+
+/// One frame, as decoded by [`FrameDecoder::decode`].
+pub struct DecodedFrame {
+    /// What to paint.
+    pub clipped_meshes: Vec<ClippedMesh>,
+
+    /// Set if the font texture has changed since the previous frame.
+    pub font_image: Option<Arc<FontImage>>,
+
+    /// User textures that were allocated or freed since the previous frame.
+    pub texture_updates: Vec<TextureUpdate>,
+}
+
+impl DecodedFrame {
+    /// Change the [`TextureId::User`] of each mesh,
+    /// e.g. to the ids that the painter of the client gave the textures in [`Self::texture_updates`].
+    pub fn map_user_textures(&mut self, mut map: impl FnMut(u64) -> TextureId) {
+        for ClippedMesh(_, mesh) in &mut self.clipped_meshes {
+            if let TextureId::User(id) = mesh.texture_id {
+                mesh.texture_id = map(id);
+            }
+        }
+    }
+}
+
+/// Decodes frames on the client side. See the [module level docs](self).
+#[derive(Default)]
+pub struct FrameDecoder {
+    frame_nr: Option<u64>,
+    font_image: Option<Arc<FontImage>>,
+    clipped_meshes: Vec<ClippedMesh>,
+}
+
+impl FrameDecoder {
+    /// The font texture as of the last decoded frame.
+    pub fn font_image(&self) -> Option<&Arc<FontImage>> {
+        self.font_image.as_ref()
+    }
+
+    /// Decode a message from [`FrameEncoder::encode`].
+    ///
+    /// # Errors
+    /// If the message is corrupt (including a mesh index past the end of its vertices),
+    /// has another [`FORMAT_VERSION`],
+    /// or if the previous frame it is relative to was not decoded by this decoder.
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<DecodedFrame, DecodeError> {
+        let message: FrameMessage = decode(KIND_FRAME, bytes)?;
+
+        if message.previous_frame_nr.is_some() && message.previous_frame_nr != self.frame_nr {
+            return Err(DecodeError::MissingPreviousFrame);
+        }
+
+        let corrupt = |what: &str| DecodeError::Corrupt(what.to_owned());
+
+        let font_image = match message.font_image {
+            Some(update) => {
+                let size = update
+                    .width
+                    .checked_mul(update.height)
+                    .ok_or_else(|| corrupt("font image size overflows"))?;
+                let mut pixels = match &self.font_image {
+                    Some(font_image) if update.first_row > 0 => {
+                        if font_image.width != update.width || font_image.height < update.first_row
+                        {
+                            return Err(DecodeError::MissingPreviousFrame);
+                        }
+                        let unchanged = update
+                            .first_row
+                            .checked_mul(update.width)
+                            .filter(|&unchanged| unchanged <= font_image.pixels.len())
+                            .ok_or_else(|| corrupt("font image rows out of bounds"))?;
+                        font_image.pixels[..unchanged].to_vec()
+                    }
+                    None if update.first_row > 0 => return Err(DecodeError::MissingPreviousFrame),
+                    _ => vec![],
+                };
+                pixels.extend_from_slice(&update.pixels);
+                if pixels.len() != size {
+                    return Err(corrupt("wrong font image size"));
+                }
+                Some(Arc::new(FontImage {
+                    version: update.version,
+                    width: update.width,
+                    height: update.height,
+                    pixels,
+                }))
+            }
+            None => None,
+        };
+
+        let mut clipped_meshes = Vec::with_capacity(message.meshes.len());
+        for mesh_update in message.meshes {
+            clipped_meshes.push(match mesh_update {
+                MeshUpdate::Same(index) => self
+                    .clipped_meshes
+                    .get(index as usize)
+                    .cloned()
+                    .ok_or(DecodeError::MissingPreviousFrame)?,
+                MeshUpdate::New(clipped_mesh) => {
+                    if !clipped_mesh.1.is_valid() {
+                        return Err(corrupt("mesh index out of bounds"));
+                    }
+                    clipped_mesh
+                }
+            });
+        }
+
+        self.frame_nr = Some(message.frame_nr);
+        if font_image.is_some() {
+            self.font_image = font_image.clone();
+        }
+        self.clipped_meshes = clipped_meshes.clone();
+
+        Ok(DecodedFrame {
+            clipped_meshes,
+            font_image,
+            texture_updates: message.textures,
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Encode the input of the client, to be decoded on the server with [`decode_input`].
+pub fn encode_input(raw_input: &RawInput) -> Vec<u8> {
+    encode(KIND_INPUT, raw_input)
+}
+
+/// Decode a message from [`encode_input`].
+///
+/// # Errors
+/// If the message is corrupt or has another [`FORMAT_VERSION`].
+pub fn decode_input(bytes: &[u8]) -> Result<RawInput, DecodeError> {
+    decode(KIND_INPUT, bytes)
+}
+
+fn encode(kind: u8, value: &impl serde::Serialize) -> Vec<u8> {
+    let mut bytes = MAGIC.to_vec();
+    bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+    bytes.push(kind);
+    bincode::serialize_into(&mut bytes, value).expect("Failed to encode egui message");
+    bytes
+}
+
+fn decode<T: serde::de::DeserializeOwned>(kind: u8, bytes: &[u8]) -> Result<T, DecodeError> {
+    if bytes.len() < 7 || bytes[..4] != MAGIC {
+        return Err(DecodeError::NotEgui);
+    }
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    if version != FORMAT_VERSION {
+        return Err(DecodeError::UnsupportedVersion(version));
+    }
+    if bytes[6] != kind {
+        return Err(DecodeError::NotEgui);
+    }
+    bincode::deserialize(&bytes[7..]).map_err(|err| DecodeError::Corrupt(err.to_string()))
+}
+
+// ----------------------------------------------------------------------------
+
+/// Write a message prefixed by its length, so that it can be read back with [`read_message`].
+///
+/// # Errors
+/// If writing fails, or if the message is larger than [`MAX_MESSAGE_SIZE`].
+pub fn write_message(writer: &mut impl Write, message: &[u8]) -> std::io::Result<()> {
+    if message.len() > MAX_MESSAGE_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("message of {} bytes is too large", message.len()),
+        ));
+    }
+    writer.write_all(&(message.len() as u64).to_le_bytes())?;
+    writer.write_all(message)?;
+    writer.flush()
+}
+
+/// Read a message written by [`write_message`].
+///
+/// # Errors
+/// If reading fails, or with [`std::io::ErrorKind::InvalidData`]
+/// if the message is larger than [`MAX_MESSAGE_SIZE`].
+pub fn read_message(reader: &mut impl Read) -> std::io::Result<Vec<u8>> {
+    let mut len = [0; 8];
+    reader.read_exact(&mut len)?;
+    let len = u64::from_le_bytes(len);
+    if len > MAX_MESSAGE_SIZE as u64 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("message of {} bytes is too large", len),
+        ));
+    }
+    let mut message = vec![0; len as usize];
+    reader.read_exact(&mut message)?;
+    Ok(message)
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CtxRef;
+    use std::net::{TcpListener, TcpStream};
+
+    fn ui(ctx: &CtxRef, clicks: &mut usize) {
+        crate::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Remote egui");
+            if ui.button("Click me").clicked() {
+                *clicks += 1;
+            }
+            ui.label(format!("Clicked {} times", clicks));
+        });
+    }
+
+    #[test]
+    fn remote_ui_over_socket() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        const NUM_FRAMES: usize = 4;
+
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut ctx = CtxRef::default();
+            let mut encoder = FrameEncoder::default();
+            let mut clicks = 0;
+            let mut frames = vec![];
+
+            let mut raw_input = RawInput::default();
+            for _ in 0..NUM_FRAMES {
+                let (_, shapes) = ctx.run(raw_input, |ctx| ui(ctx, &mut clicks));
+                let clipped_meshes = ctx.tessellate(shapes);
+                let message = encoder.encode(&ctx.font_image(), &clipped_meshes, &[]);
+                write_message(&mut stream, &message).unwrap();
+                frames.push((message.len(), clipped_meshes));
+
+                raw_input = decode_input(&read_message(&mut stream).unwrap()).unwrap();
+            }
+            frames
+        });
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        let mut decoder = FrameDecoder::default();
+        let mut decoded_frames = vec![];
+        for _ in 0..NUM_FRAMES {
+            let frame = decoder.decode(&read_message(&mut stream).unwrap()).unwrap();
+            decoded_frames.push(frame.clipped_meshes);
+            write_message(&mut stream, &encode_input(&RawInput::default())).unwrap();
+        }
+
+        let frames = server.join().unwrap();
+        for ((_, clipped_meshes), decoded) in frames.iter().zip(&decoded_frames) {
+            assert_eq!(clipped_meshes.len(), decoded.len());
+            for (a, b) in clipped_meshes.iter().zip(decoded) {
+                assert_eq!(a.0, b.0);
+                assert_eq!(a.1, b.1);
+            }
+        }
+
+        let (first_size, _) = frames[0];
+        let (last_size, _) = frames[NUM_FRAMES - 1];
+        assert!(
+            last_size < first_size / 10,
+            "An unchanged frame should be much smaller than the first ({} vs {} bytes)",
+            last_size,
+            first_size
+        );
+        assert!(decoder.font_image().is_some());
+    }
+
+    #[test]
+    fn decoding_requires_the_previous_frame() {
+        let font_image = FontImage {
+            version: 1,
+            width: 2,
+            height: 2,
+            pixels: vec![0, 1, 2, 3],
+        };
+        let mut encoder = FrameEncoder::default();
+        let _first = encoder.encode(&font_image, &[], &[]);
+        let second = encoder.encode(&font_image, &[], &[]);
+        assert!(matches!(
+            FrameDecoder::default().decode(&second),
+            Err(DecodeError::MissingPreviousFrame)
+        ));
+        assert!(matches!(decode_input(&second), Err(DecodeError::NotEgui)));
+    }
+
+    #[test]
+    fn too_large_messages_are_refused() {
+        let mut bytes = vec![];
+        write_message(&mut bytes, b"hello").unwrap();
+        assert_eq!(read_message(&mut bytes.as_slice()).unwrap(), b"hello");
+
+        let mut bytes = (MAX_MESSAGE_SIZE as u64 + 1).to_le_bytes().to_vec();
+        bytes.extend_from_slice(b"hello");
+        let err = read_message(&mut bytes.as_slice()).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    fn frame_message(font_image: Option<FontImageUpdate>, meshes: Vec<MeshUpdate>) -> Vec<u8> {
+        encode(
+            KIND_FRAME,
+            &FrameMessage {
+                frame_nr: 0,
+                previous_frame_nr: None,
+                font_image,
+                textures: vec![],
+                meshes,
+            },
+        )
+    }
+
+    #[test]
+    fn corrupt_frames_are_refused() {
+        let is_corrupt = |bytes: &[u8]| {
+            matches!(
+                FrameDecoder::default().decode(bytes),
+                Err(DecodeError::Corrupt(_))
+            )
+        };
+
+        let huge_font_image = FontImageUpdate {
+            version: 1,
+            width: usize::MAX,
+            height: 2,
+            first_row: 0,
+            pixels: vec![0; 4],
+        };
+        assert!(is_corrupt(&frame_message(Some(huge_font_image), vec![])));
+
+        let mut mesh = crate::epaint::Mesh::default();
+        mesh.colored_vertex(crate::pos2(0.0, 0.0), Color32::WHITE);
+        mesh.add_triangle(0, 1, 2);
+        let mesh = MeshUpdate::New(ClippedMesh(crate::Rect::EVERYTHING, mesh));
+        assert!(is_corrupt(&frame_message(None, vec![mesh])));
+    }
+
+    #[test]
+    fn font_image_rows_must_fit_in_the_previous_font_image() {
+        let font_image = FontImage {
+            version: 1,
+            width: 2,
+            height: 2,
+            pixels: vec![0, 1, 2, 3],
+        };
+        let mut decoder = FrameDecoder::default();
+        decoder
+            .decode(&FrameEncoder::default().encode(&font_image, &[], &[]))
+            .unwrap();
+
+        let update = FontImageUpdate {
+            version: 2,
+            width: usize::MAX / 2 + 1,
+            height: 1,
+            first_row: 1,
+            pixels: vec![],
+        };
+        // A different width means the update is not relative to our font image:
+        assert!(matches!(
+            decoder.decode(&frame_message(Some(update), vec![])),
+            Err(DecodeError::MissingPreviousFrame)
+        ));
+
+        let update = FontImageUpdate {
+            version: 2,
+            width: 2,
+            height: 4,
+            first_row: 1,
+            pixels: vec![4, 5, 6, 7, 8, 9],
+        };
+        let frame = decoder
+            .decode(&frame_message(Some(update), vec![]))
+            .unwrap();
+        assert_eq!(
+            frame.font_image.unwrap().pixels,
+            vec![0, 1, 4, 5, 6, 7, 8, 9]
+        );
+    }
+}
@@ -1,5 +1,7 @@
+use std::{any::Any, sync::Arc};
+
 use crate::{
-    emath::{lerp, Align, Pos2, Rect, Vec2},
+    emath::{Align, Pos2, Rect, Vec2},
     CtxRef, CursorIcon, Id, LayerId, PointerButton, Sense, Ui, WidgetText, NUM_POINTER_BUTTONS,
 };
 
@@ -246,6 +248,34 @@ impl Response {
         self.dragged && self.ctx.input().pointer.any_pressed()
     }
 
+    /// The pointer was pressed on this widget and held still for a while (usually with a finger).
+    ///
+    /// Only `true` on the frame the long-press is recognized.
+    /// Works with any [`Sense`]. [`Self::context_menu`] opens on a long-press too.
+    pub fn long_pressed(&self) -> bool {
+        let input = self.ctx.input();
+        input.long_pressed()
+            && input
+                .pointer
+                .press_origin()
+                .map_or(false, |pos| self.is_on_top_at(pos))
+    }
+
+    /// A swipe that started on this widget and ended this frame.
+    ///
+    /// Works with any [`Sense`].
+    pub fn swiped(&self) -> Option<crate::Swipe> {
+        self.ctx
+            .input()
+            .swipe()
+            .filter(|swipe| self.is_on_top_at(swipe.start_pos))
+    }
+
+    /// Is this widget at the given position, and not covered by another layer?
+    fn is_on_top_at(&self, pos: Pos2) -> bool {
+        self.enabled && self.rect.contains(pos) && self.ctx.layer_id_at(pos) == Some(self.layer_id)
+    }
+
     /// The widget was being dragged, but now it has been released.
     pub fn drag_released(&self) -> bool {
         self.drag_released
@@ -448,12 +478,19 @@ impl Response {
     /// });
     /// # });
     /// ```
+    ///
+    /// This jumps right away. See [`Self::scroll_to_me_animated`] for smooth scrolling.
     pub fn scroll_to_me(&self, align: Align) {
-        let scroll_target = lerp(self.rect.x_range(), align.to_factor());
-        self.ctx.frame_state().scroll_target[0] = Some((scroll_target, align));
+        self.ctx
+            .frame_state()
+            .scroll_to_rect(self.rect, align, false);
+    }
 
-        let scroll_target = lerp(self.rect.y_range(), align.to_factor());
-        self.ctx.frame_state().scroll_target[1] = Some((scroll_target, align));
+    /// Like [`Self::scroll_to_me`], but smoothly scrolls there over a short time.
+    pub fn scroll_to_me_animated(&self, align: Align) {
+        self.ctx
+            .frame_state()
+            .scroll_to_rect(self.rect, align, true);
     }
 
     /// For accessibility.
@@ -499,6 +536,60 @@ impl Response {
     }
 }
 
+/// ## Drag and drop
+impl Response {
+    /// Attach a payload to the drag, if this widget started being dragged this frame.
+    ///
+    /// The widget must sense drags (see [`Sense::drag`]).
+    /// Drop targets can check for the payload with [`Self::dnd_hover_payload`]
+    /// and accept it with [`Self::dnd_release_payload`].
+    /// The payload is dropped when the drag ends, or when the user presses Escape.
+    ///
+    /// See also [`Ui::dnd_drag_source`].
+    pub fn dnd_set_payload<T: Any + Send + Sync>(&self, payload: T) {
+        if self.drag_started() {
+            self.ctx.memory().drag_and_drop.set_payload(payload);
+        }
+    }
+
+    /// The payload of the ongoing drag, if it is of type `T` and the pointer is over this widget.
+    ///
+    /// Files dragged in from the OS are a `Vec<HoveredFile>`.
+    pub fn dnd_hover_payload<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        if self.is_dnd_target() {
+            self.ctx.memory().dnd_payload()
+        } else {
+            None
+        }
+    }
+
+    /// The payload, if it is of type `T` and was dropped on this widget this frame.
+    ///
+    /// Files dropped from the OS are a `Vec<DroppedFile>`.
+    pub fn dnd_release_payload<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        if self.is_dnd_target()
+            && self
+                .ctx
+                .memory()
+                .drag_and_drop
+                .is_released(self.ctx.input())
+        {
+            self.ctx.memory().drag_and_drop.take_payload()
+        } else {
+            None
+        }
+    }
+
+    /// Is the pointer over this widget while something is dragged?
+    ///
+    /// We can't use [`Self::hovered`], since nothing else is hovered while dragging.
+    fn is_dnd_target(&self) -> bool {
+        self.enabled
+            && self.ctx.memory().dnd_has_payload()
+            && self.ctx.rect_contains_pointer(self.layer_id, self.rect)
+    }
+}
+
 impl Response {
     /// A logical "or" operation.
     /// For instance `a.union(b).hovered` means "was either a or b hovered?".
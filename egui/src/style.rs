@@ -2,8 +2,9 @@
 
 #![allow(clippy::if_same_then_else)]
 
-use crate::{color::*, emath::*, Response, RichText, WidgetText};
+use crate::{color::*, emath::*, Response, RichText, WidgetText, WidgetType};
 use epaint::{Shadow, Stroke, TextStyle};
+use std::collections::BTreeMap;
 
 /// Specifies the look and feel of egui.
 ///
@@ -63,8 +64,26 @@ impl Style {
         self.visuals.widgets.style(response)
     }
 
+    /// Like [`Self::interact`], but uses the [`Visuals::widget_overrides`] of `widget_type`, if any.
+    pub fn interact_as(&self, widget_type: WidgetType, response: &Response) -> &WidgetVisuals {
+        self.visuals.widgets_for(widget_type).style(response)
+    }
+
     pub fn interact_selectable(&self, response: &Response, selected: bool) -> WidgetVisuals {
-        let mut visuals = *self.visuals.widgets.style(response);
+        self.selectable(*self.interact(response), selected)
+    }
+
+    /// Like [`Self::interact_selectable`], but uses the [`Visuals::widget_overrides`] of `widget_type`, if any.
+    pub fn interact_selectable_as(
+        &self,
+        widget_type: WidgetType,
+        response: &Response,
+        selected: bool,
+    ) -> WidgetVisuals {
+        self.selectable(*self.interact_as(widget_type, response), selected)
+    }
+
+    fn selectable(&self, mut visuals: WidgetVisuals, selected: bool) -> WidgetVisuals {
         if selected {
             visuals.bg_fill = self.visuals.selection.bg_fill;
             // visuals.bg_stroke = self.visuals.selection.stroke;
@@ -73,12 +92,122 @@ impl Style {
         visuals
     }
 
+    /// Like [`Self::interact_as`], but the [`StyleClass::widgets`] of the class of the widget (if any) take precedence.
+    pub fn interact_with_class<'a>(
+        &'a self,
+        class: Option<&'a StyleClass>,
+        widget_type: WidgetType,
+        response: &Response,
+    ) -> &'a WidgetVisuals {
+        match class.and_then(|class| class.widgets.as_ref()) {
+            Some(widgets) => widgets.style(response),
+            None => self.interact_as(widget_type, response),
+        }
+    }
+
     /// Style to use for non-interactive widgets.
     pub fn noninteractive(&self) -> &WidgetVisuals {
         &self.visuals.widgets.noninteractive
     }
 }
 
+/// A named set of style overrides, e.g. `"danger"`, `"primary"` or `"toolbar"`.
+///
+/// Register a class with [`crate::Context::set_style_class`], then give it to
+/// [`crate::Button::class`], [`crate::Label::class`], [`crate::Slider::class`] or [`crate::Frame::show_with_class`],
+/// or apply it to everything in a [`crate::Ui`] with [`crate::Ui::add_style_class`].
+///
+/// Classes cascade like CSS: a field that is `None` is inherited from the surrounding [`crate::Ui`],
+/// the class of a widget wins over the classes of the `Ui`:s it is in,
+/// and settings on the widget itself (e.g. [`crate::RichText::color`] or [`crate::Button::fill`]) win over any class.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// let mut danger = egui::StyleClass::default();
+/// danger.widgets = Some(egui::style::Widgets::light());
+/// danger.text_color = Some(egui::Color32::RED);
+/// ui.ctx().set_style_class("danger", danger);
+///
+/// ui.add(egui::Button::new("Delete everything").class("danger"));
+/// ui.scope_with_class("danger", |ui| {
+///     ui.label("This is red too");
+/// });
+/// # });
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct StyleClass {
+    /// The visuals of interactive widgets.
+    /// Replaces both [`Visuals::widgets`] and [`Visuals::widget_overrides`].
+    pub widgets: Option<Widgets>,
+
+    /// The color of text that doesn't have a color of its own.
+    pub text_color: Option<Color32>,
+
+    /// The [`TextStyle`] of text that doesn't have a text style of its own.
+    pub text_style: Option<TextStyle>,
+
+    /// See [`Spacing::item_spacing`].
+    pub item_spacing: Option<Vec2>,
+
+    /// See [`Spacing::button_padding`].
+    pub button_padding: Option<Vec2>,
+
+    /// The background of a [`crate::Frame`] with this class. Not inherited by its contents.
+    pub fill: Option<Color32>,
+
+    /// The outline of a [`crate::Frame`] with this class. Not inherited by its contents.
+    pub stroke: Option<Stroke>,
+
+    /// The corner radius of a [`crate::Frame`] with this class. Not inherited by its contents.
+    pub corner_radius: Option<f32>,
+}
+
+impl StyleClass {
+    /// Apply the inherited fields (all but the frame fields) to a style.
+    pub fn apply_to(&self, style: &mut Style) {
+        let Self {
+            widgets,
+            text_color,
+            text_style,
+            item_spacing,
+            button_padding,
+            fill: _,
+            stroke: _,
+            corner_radius: _,
+        } = self;
+
+        if let Some(widgets) = widgets {
+            style.visuals.widgets = widgets.clone();
+            style.visuals.widget_overrides.clear();
+        }
+        if let Some(text_color) = text_color {
+            style.visuals.override_text_color = Some(*text_color);
+        }
+        if let Some(text_style) = text_style {
+            style.override_text_style = Some(*text_style);
+        }
+        if let Some(item_spacing) = item_spacing {
+            style.spacing.item_spacing = *item_spacing;
+        }
+        if let Some(button_padding) = button_padding {
+            style.spacing.button_padding = *button_padding;
+        }
+    }
+
+    /// Give text the color and text style of this class, unless it has its own.
+    pub fn text(&self, mut text: WidgetText) -> WidgetText {
+        if let Some(text_style) = self.text_style {
+            text = text.fallback_text_style(text_style);
+        }
+        if let Some(text_color) = self.text_color {
+            text = text.fallback_text_color(text_color);
+        }
+        text
+    }
+}
+
 /// Controls the sizes and distances between widgets.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -199,6 +328,11 @@ pub struct Visuals {
     /// Visual styles of widgets
     pub widgets: Widgets,
 
+    /// Replaces [`Self::widgets`] for one type of widget, e.g. to give all buttons another color.
+    ///
+    /// Not all widgets look here, but buttons, checkboxes, sliders, text edits and the like do.
+    pub widget_overrides: BTreeMap<WidgetType, Widgets>,
+
     pub selection: Selection,
 
     /// The color used for `Hyperlink`,
@@ -238,6 +372,13 @@ pub struct Visuals {
 }
 
 impl Visuals {
+    /// The visuals of a type of widget: its [`Self::widget_overrides`], or else [`Self::widgets`].
+    pub fn widgets_for(&self, widget_type: WidgetType) -> &Widgets {
+        self.widget_overrides
+            .get(&widget_type)
+            .unwrap_or(&self.widgets)
+    }
+
     #[inline(always)]
     pub fn noninteractive(&self) -> &WidgetVisuals {
         &self.widgets.noninteractive
@@ -408,6 +549,7 @@ impl Visuals {
             dark_mode: true,
             override_text_color: None,
             widgets: Widgets::default(),
+            widget_overrides: Default::default(),
             selection: Selection::default(),
             hyperlink_color: Color32::from_rgb(90, 170, 255),
             faint_bg_color: Color32::from_gray(24),
@@ -820,6 +962,7 @@ impl Visuals {
             dark_mode: _,
             override_text_color: _,
             widgets,
+            widget_overrides,
             selection,
             hyperlink_color,
             faint_bg_color,
@@ -856,6 +999,9 @@ impl Visuals {
         });
 
         ui.collapsing("Widgets", |ui| widgets.ui(ui));
+        ui.collapsing("Widget overrides", |ui| {
+            widget_overrides_ui(ui, widget_overrides, widgets);
+        });
         ui.collapsing("Selection", |ui| selection.ui(ui));
 
         ui_color(
@@ -909,6 +1055,47 @@ impl DebugOptions {
     }
 }
 
+fn widget_overrides_ui(
+    ui: &mut Ui,
+    widget_overrides: &mut BTreeMap<WidgetType, Widgets>,
+    widgets: &Widgets,
+) {
+    ui.label("Give one type of widget other visuals than the rest.");
+
+    widget_overrides.retain(|widget_type, widgets| {
+        let mut keep = true;
+        ui.collapsing(format!("{:?}", widget_type), |ui| {
+            widgets.ui(ui);
+            keep = !ui.button("Remove override").clicked();
+        });
+        keep
+    });
+
+    ui.menu_button("Add override…", |ui| {
+        for widget_type in [
+            WidgetType::Button,
+            WidgetType::Checkbox,
+            WidgetType::RadioButton,
+            WidgetType::SelectableLabel,
+            WidgetType::ComboBox,
+            WidgetType::Slider,
+            WidgetType::TextEdit,
+            WidgetType::Label,
+            WidgetType::Hyperlink,
+            WidgetType::ColorButton,
+            WidgetType::ImageButton,
+            WidgetType::CollapsingHeader,
+        ] {
+            if !widget_overrides.contains_key(&widget_type)
+                && ui.button(format!("{:?}", widget_type)).clicked()
+            {
+                widget_overrides.insert(widget_type, widgets.clone());
+                ui.close_menu();
+            }
+        }
+    });
+}
+
 // TODO: improve and standardize `slider_vec2`
 fn slider_vec2<'a>(
     value: &'a mut Vec2,
@@ -940,3 +1127,108 @@ fn ui_color(ui: &mut Ui, srgba: &mut Color32, label: impl Into<WidgetText>) -> R
     })
     .response
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Button, CentralPanel, CtxRef, Frame, Label, RichText, Shape};
+
+    fn class(color: Color32) -> StyleClass {
+        let mut widgets = Widgets::default();
+        widgets.inactive.bg_fill = color;
+        StyleClass {
+            widgets: Some(widgets),
+            text_color: Some(color),
+            fill: Some(color),
+            ..Default::default()
+        }
+    }
+
+    fn text_color(ui: &mut Ui, label: Label) -> Color32 {
+        let (_, text_galley, _) = label.layout_in_ui(ui);
+        text_galley.galley.job.sections[0].format.color
+    }
+
+    fn rect_fills(shape: &Shape, fills: &mut Vec<epaint::Brush>) {
+        match shape {
+            Shape::Rect(rect) => fills.push(rect.fill),
+            Shape::Vec(shapes) => {
+                for shape in shapes {
+                    rect_fills(shape, fills);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn classes_cascade() {
+        let mut ctx = CtxRef::default();
+        ctx.set_style_class("ui", class(Color32::RED));
+        ctx.set_style_class("frame", class(Color32::GREEN));
+        ctx.set_style_class("widget", class(Color32::BLUE));
+
+        let (_, shapes) = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.add_style_class("ui");
+                assert_eq!(text_color(ui, Label::new("ui")), Color32::RED);
+
+                Frame::default().show_with_class(ui, "frame", |ui| {
+                    // The class of the frame wins over the class of the `Ui`:
+                    assert_eq!(text_color(ui, Label::new("frame")), Color32::GREEN);
+                    ui.add(Button::new("frame"));
+
+                    // The class of a widget wins over the class of the frame:
+                    let label = Label::new("widget").class("widget");
+                    assert_eq!(text_color(ui, label), Color32::BLUE);
+                    ui.add(Button::new("widget").class("widget"));
+
+                    // Settings on the widget itself win over any class:
+                    let label = Label::new(RichText::new("own").color(Color32::YELLOW));
+                    assert_eq!(text_color(ui, label.class("widget")), Color32::YELLOW);
+                    ui.add(Button::new("own").class("widget").fill(Color32::YELLOW));
+                });
+
+                assert_eq!(text_color(ui, Label::new("ui")), Color32::RED);
+            });
+        });
+
+        let mut fills = vec![];
+        for clipped_shape in &shapes {
+            rect_fills(&clipped_shape.1, &mut fills);
+        }
+        let colors = [
+            Color32::GREEN, // the frame
+            Color32::GREEN,
+            Color32::BLUE,
+            Color32::YELLOW,
+        ];
+        let colors: Vec<epaint::Brush> = colors.iter().map(|&color| color.into()).collect();
+        assert!(fills.ends_with(&colors), "{:?}", fills);
+    }
+
+    #[test]
+    fn classed_styles_are_cached() {
+        let mut ctx = CtxRef::default();
+        ctx.set_style_class("danger", class(Color32::RED));
+
+        fn show(ctx: &mut CtxRef) -> std::sync::Arc<Style> {
+            let mut style = None;
+            let _ = ctx.run(Default::default(), |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    ui.add_style_class("danger");
+                    style = Some(ui.style().clone());
+                });
+            });
+            style.unwrap()
+        }
+
+        let first = show(&mut ctx);
+        assert_eq!(first.visuals.override_text_color, Some(Color32::RED));
+        assert!(std::sync::Arc::ptr_eq(&first, &show(&mut ctx)));
+
+        ctx.set_style_class("danger", class(Color32::BLUE));
+        let changed = show(&mut ctx);
+        assert_eq!(changed.visuals.override_text_color, Some(Color32::BLUE));
+    }
+}
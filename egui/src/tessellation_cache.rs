@@ -0,0 +1,250 @@
+//! Reuse the tessellation of layers that haven't changed since last frame.
+//!
+//! Comparing the shapes of a layer takes about half the time it takes to tessellate them,
+//! so we first compare hashes, and only compare the shapes of layers with the same hash as last frame.
+//! For mostly static windows this is still a win: see the `demo_only_tessellate` benchmarks
+//! of `egui_demo_lib`.
+
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use epaint::{ahash::AHashMap, text::Fonts, ClippedShape, TessellationOptions};
+
+use crate::LayerId;
+
+/// Everything except the shapes that the tessellation depends on.
+#[derive(Clone)]
+struct Settings {
+    fonts: Arc<Fonts>,
+    options: TessellationOptions,
+    font_image_size: [usize; 2],
+}
+
+impl PartialEq for Settings {
+    fn eq(&self, other: &Self) -> bool {
+        // Holding on to the `Fonts` means the pointer can't be reused by new fonts.
+        Arc::ptr_eq(&self.fonts, &other.fonts)
+            && self.options == other.options
+            && self.font_image_size == other.font_image_size
+    }
+}
+
+struct CachedLayer<T> {
+    shapes_hash: u64,
+    /// Compared on a hash match, so that a hash collision can't reuse the wrong tessellation.
+    shapes: Vec<ClippedShape>,
+    tessellated: Vec<T>,
+}
+
+/// The tessellation of each layer from last frame, for one kind of output
+/// ([`epaint::ClippedMesh`] or [`epaint::ClippedPrimitive`]).
+struct LayerCache<T> {
+    layers: AHashMap<LayerId, CachedLayer<T>>,
+}
+
+impl<T> Default for LayerCache<T> {
+    fn default() -> Self {
+        Self {
+            layers: Default::default(),
+        }
+    }
+}
+
+/// How many layers were reused by the last call to [`TessellationCache::tessellate_meshes`]
+/// or [`TessellationCache::tessellate_primitives`].
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+#[derive(Default)]
+pub(crate) struct TessellationCache {
+    /// Which layers the shapes returned by the last `end_frame` came from,
+    /// and how many shapes each of them has (in painting order).
+    painted_layers: Vec<(LayerId, usize)>,
+
+    settings: Option<Settings>,
+    meshes: LayerCache<epaint::ClippedMesh>,
+    primitives: LayerCache<epaint::ClippedPrimitive>,
+}
+
+impl TessellationCache {
+    /// Called at the end of each frame with the layers of the shapes that are about to be returned.
+    pub fn set_painted_layers(&mut self, painted_layers: Vec<(LayerId, usize)>) {
+        self.painted_layers = painted_layers;
+    }
+
+    pub fn tessellate_meshes(
+        &mut self,
+        fonts: &Arc<Fonts>,
+        options: TessellationOptions,
+        shapes: Vec<ClippedShape>,
+    ) -> (Vec<epaint::ClippedMesh>, CacheStats) {
+        self.update_settings(fonts, options);
+        let font_image_size = fonts.font_image().size();
+        self.meshes.tessellate(
+            &self.painted_layers,
+            shapes,
+            |shapes| epaint::tessellator::tessellate_shapes(shapes, options, font_image_size),
+            |epaint::ClippedMesh(clip_rect, mesh), epaint::ClippedMesh(next_clip_rect, next)| {
+                append_mesh(*clip_rect, mesh, *next_clip_rect, next)
+            },
+        )
+    }
+
+    pub fn tessellate_primitives(
+        &mut self,
+        fonts: &Arc<Fonts>,
+        options: TessellationOptions,
+        shapes: Vec<ClippedShape>,
+    ) -> (Vec<epaint::ClippedPrimitive>, CacheStats) {
+        self.update_settings(fonts, options);
+        let font_image_size = fonts.font_image().size();
+        self.primitives.tessellate(
+            &self.painted_layers,
+            shapes,
+            |shapes| epaint::tessellator::tessellate_primitives(shapes, options, font_image_size),
+            |last, next| match (last, next) {
+                (
+                    epaint::ClippedPrimitive(clip_rect, epaint::Primitive::Mesh(mesh)),
+                    epaint::ClippedPrimitive(next_clip_rect, epaint::Primitive::Mesh(next)),
+                ) => append_mesh(*clip_rect, mesh, *next_clip_rect, next),
+                _ => false,
+            },
+        )
+    }
+
+    /// Throw away everything if anything but the shapes changed.
+    fn update_settings(&mut self, fonts: &Arc<Fonts>, options: TessellationOptions) {
+        let settings = Settings {
+            fonts: fonts.clone(),
+            options,
+            font_image_size: fonts.font_image().size(),
+        };
+        if self.settings.as_ref() != Some(&settings) {
+            self.settings = Some(settings);
+            self.meshes = Default::default();
+            self.primitives = Default::default();
+        }
+    }
+}
+
+/// Append `next` to `mesh` if they can be painted together. Returns `false` if they can't.
+fn append_mesh(
+    clip_rect: epaint::Rect,
+    mesh: &mut epaint::Mesh,
+    next_clip_rect: epaint::Rect,
+    next: &epaint::Mesh,
+) -> bool {
+    if clip_rect == next_clip_rect && mesh.texture_id == next.texture_id {
+        mesh.append(next.clone());
+        true
+    } else {
+        false
+    }
+}
+
+impl<T: Clone + Send> LayerCache<T> {
+    /// `append` should try to append the second argument to the first (see [`append_mesh`]),
+    /// so that the meshes of neighboring layers are merged just like when all shapes are tessellated together.
+    fn tessellate(
+        &mut self,
+        painted_layers: &[(LayerId, usize)],
+        mut shapes: Vec<ClippedShape>,
+        tessellate: impl Fn(Vec<ClippedShape>) -> Vec<T> + Sync,
+        append: impl Fn(&mut T, &T) -> bool,
+    ) -> (Vec<T>, CacheStats) {
+        let num_painted_shapes: usize = painted_layers.iter().map(|(_, len)| len).sum();
+        if num_painted_shapes != shapes.len() {
+            // These are not (just) the shapes from the last `end_frame`,
+            // so we don't know which layers they belong to.
+            return (tessellate(shapes), CacheStats::default());
+        }
+
+        // Split the shapes up by layer, back to front:
+        let mut layers = Vec::with_capacity(painted_layers.len());
+        for &(layer_id, len) in painted_layers.iter().rev() {
+            let layer_shapes = shapes.split_off(shapes.len() - len);
+            layers.push((layer_id, layer_shapes));
+        }
+        layers.reverse();
+
+        let mut previous = std::mem::take(&mut self.layers);
+        let mut stats = CacheStats::default();
+        let mut changed = vec![];
+        let mut entries = Vec::with_capacity(layers.len());
+        for (index, (layer_id, layer_shapes)) in layers.into_iter().enumerate() {
+            let shapes_hash = hash_shapes(&layer_shapes);
+            match previous.remove(&layer_id) {
+                Some(cached)
+                    if cached.shapes_hash == shapes_hash && cached.shapes == layer_shapes =>
+                {
+                    stats.hits += 1;
+                    entries.push((layer_id, shapes_hash, layer_shapes, cached.tessellated));
+                }
+                _ => {
+                    stats.misses += 1;
+                    changed.push((index, layer_shapes.clone()));
+                    entries.push((layer_id, shapes_hash, layer_shapes, vec![]));
+                }
+            }
+        }
+
+        for (index, tessellated) in tessellate_all(changed, &tessellate) {
+            entries[index].3 = tessellated;
+        }
+
+        // Layers that weren't painted this frame are forgotten.
+        let mut tessellated: Vec<T> = vec![];
+        for (layer_id, shapes_hash, shapes, layer_tessellated) in entries {
+            let mut rest = &layer_tessellated[..];
+            if let (Some(last), Some(first)) = (tessellated.last_mut(), rest.first()) {
+                if append(last, first) {
+                    rest = &rest[1..];
+                }
+            }
+            tessellated.extend_from_slice(rest);
+            self.layers.insert(
+                layer_id,
+                CachedLayer {
+                    shapes_hash,
+                    shapes,
+                    tessellated: layer_tessellated,
+                },
+            );
+        }
+        (tessellated, stats)
+    }
+}
+
+fn hash_shapes(shapes: &[ClippedShape]) -> u64 {
+    let mut hasher = epaint::ahash::AHasher::new_with_keys(123, 456);
+    shapes.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Tessellate the shapes of each changed layer, in parallel if the `multi_threaded` feature is enabled.
+#[cfg(feature = "multi_threaded")]
+fn tessellate_all<T: Send>(
+    changed: Vec<(usize, Vec<ClippedShape>)>,
+    tessellate: &(impl Fn(Vec<ClippedShape>) -> Vec<T> + Sync),
+) -> Vec<(usize, Vec<T>)> {
+    use rayon::prelude::*;
+    changed
+        .into_par_iter()
+        .map(|(index, shapes)| (index, tessellate(shapes)))
+        .collect()
+}
+
+/// Tessellate the shapes of each changed layer, one after the other.
+#[cfg(not(feature = "multi_threaded"))]
+fn tessellate_all<T>(
+    changed: Vec<(usize, Vec<ClippedShape>)>,
+    tessellate: &impl Fn(Vec<ClippedShape>) -> Vec<T>,
+) -> Vec<(usize, Vec<T>)> {
+    changed
+        .into_iter()
+        .map(|(index, shapes)| (index, tessellate(shapes)))
+        .collect()
+}
@@ -0,0 +1,317 @@
+//! Themes: the [`Visuals`], [`Spacing`] and fonts of egui as one value,
+//! that can be saved to and loaded from a file.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::{style::Spacing, *};
+
+/// The fonts of a [`Theme`].
+///
+/// These are applied on top of the fonts the [`Context`] already has,
+/// so an empty [`ThemeFonts`] leaves the fonts as they are.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ThemeFonts {
+    /// The [`FontFamily`] and size of each [`TextStyle`].
+    /// Text styles that are not listed keep their font.
+    pub family_and_size: BTreeMap<TextStyle, (FontFamily, f32)>,
+
+    /// Font files (`.ttf` or `.otf`) to load, by font name.
+    ///
+    /// [`Theme::load`] makes relative paths relative to the theme file, and reads them into [`Self::data`].
+    pub files: BTreeMap<String, PathBuf>,
+
+    /// The contents of [`Self::files`], by font name.
+    ///
+    /// Filled in by [`Theme::load`] (or [`Self::load_files`]), and never saved,
+    /// so that applying a theme doesn't touch the file system.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub data: BTreeMap<String, FontData>,
+
+    /// The fonts (names from [`Self::files`], or built-in fonts) to use first for each [`FontFamily`].
+    /// The fonts that were there before are kept as fallbacks.
+    pub families: BTreeMap<FontFamily, Vec<String>>,
+}
+
+impl ThemeFonts {
+    fn apply_to(&self, definitions: &mut FontDefinitions) -> std::io::Result<()> {
+        let Self {
+            family_and_size,
+            files,
+            data,
+            families,
+        } = self;
+
+        for (name, path) in files {
+            if !data.contains_key(name) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!(
+                        "The font file {} has not been loaded, see ThemeFonts::load_files",
+                        path.display()
+                    ),
+                ));
+            }
+        }
+        definitions
+            .font_data
+            .extend(data.iter().map(|(name, data)| (name.clone(), data.clone())));
+
+        for (family, names) in families {
+            let fonts = definitions.fonts_for_family.entry(*family).or_default();
+            // In reverse, so that the first name ends up first:
+            for name in names.iter().rev() {
+                if !definitions.font_data.contains_key(name) {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("No font named {:?}", name),
+                    ));
+                }
+                // Applying a theme twice shouldn't list a font twice:
+                fonts.retain(|existing| existing != name);
+                fonts.insert(0, name.clone());
+            }
+        }
+
+        definitions
+            .family_and_size
+            .extend(family_and_size.iter().map(|(style, font)| (*style, *font)));
+
+        Ok(())
+    }
+}
+
+/// The look of egui: [`Visuals`] (including per-widget overrides), [`Spacing`] and fonts.
+///
+/// Use [`Context::set_theme`] to switch to a theme, and [`Theme::from_context`] to get the current one.
+///
+/// With the `theme_files` feature a theme can be saved to and loaded from a [RON](https://github.com/ron-rs/ron) file.
+/// Fields that are missing from a theme file get their default value.
+/// The native integrations can watch a theme file and re-apply it whenever it changes
+/// (see `epi::NativeOptions::theme_file`), and [`Context::style_ui`] has a button for saving the current style as a theme.
+///
+/// ```
+/// # let ctx = egui::CtxRef::default();
+/// let mut theme = egui::Theme::from_context(&ctx);
+/// theme.visuals.widget_overrides.insert(
+///     egui::WidgetType::Button,
+///     egui::style::Widgets::light(),
+/// );
+/// ctx.set_theme(&theme).unwrap();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Theme {
+    pub visuals: Visuals,
+    pub spacing: Spacing,
+    pub fonts: ThemeFonts,
+}
+
+impl Theme {
+    /// The visuals, spacing and font sizes the [`Context`] uses right now.
+    pub fn from_context(ctx: &Context) -> Self {
+        let style = ctx.style();
+        Self {
+            visuals: style.visuals.clone(),
+            spacing: style.spacing.clone(),
+            fonts: ThemeFonts {
+                family_and_size: ctx.font_definitions().family_and_size,
+                ..Default::default()
+            },
+        }
+    }
+
+    pub(crate) fn apply(&self, ctx: &Context) -> std::io::Result<()> {
+        // Load the fonts first, so that nothing changes if that fails:
+        let mut font_definitions = ctx.font_definitions();
+        self.fonts.apply_to(&mut font_definitions)?;
+
+        let mut style = (*ctx.style()).clone();
+        style.visuals = self.visuals.clone();
+        style.spacing = self.spacing.clone();
+        ctx.set_style(style);
+        ctx.set_fonts(font_definitions);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "theme_files")]
+impl Theme {
+    /// Parse a theme in the format written by [`Self::to_ron`].
+    ///
+    /// # Errors
+    /// When the theme is not valid RON, or has fields of the wrong type.
+    pub fn from_ron(ron: &str) -> Result<Self, ron::Error> {
+        ron::from_str(ron)
+    }
+
+    /// The theme as (pretty-printed) RON.
+    ///
+    /// # Errors
+    /// Only if serialization fails, which shouldn't happen.
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, Default::default())
+    }
+
+    /// Read a theme file written by [`Self::save`] (or by hand), and the font files it lists.
+    ///
+    /// Relative paths to font files are made relative to the directory of the theme file.
+    ///
+    /// # Errors
+    /// When failing to read or parse the file, or to read a font file.
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let mut theme = Self::from_ron(&std::fs::read_to_string(path)?)?;
+        if let Some(dir) = path.parent() {
+            for file in theme.fonts.files.values_mut() {
+                if file.is_relative() {
+                    *file = dir.join(&file);
+                }
+            }
+        }
+        theme.fonts.load_files()?;
+        Ok(theme)
+    }
+
+    /// Write the theme to a file, as RON.
+    ///
+    /// # Errors
+    /// When failing to serialize or write the file.
+    pub fn save(
+        &self,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        std::fs::write(path, self.to_ron()?)?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "theme_files")]
+impl ThemeFonts {
+    /// Read [`Self::files`] into [`Self::data`].
+    ///
+    /// # Errors
+    /// When failing to read a font file.
+    pub fn load_files(&mut self) -> std::io::Result<()> {
+        for (name, path) in &self.files {
+            let font = std::fs::read(path).map_err(|err| {
+                std::io::Error::new(err.kind(), format!("{}: {}", path.display(), err))
+            })?;
+            self.data.insert(name.clone(), FontData::from_owned(font));
+        }
+        Ok(())
+    }
+}
+
+/// The "Save as theme" section of [`Context::style_ui`].
+#[cfg(feature = "theme_files")]
+pub(crate) fn save_theme_ui(ctx: &Context, ui: &mut Ui) {
+    #[derive(Clone)]
+    struct SaveState {
+        path: String,
+        message: Option<String>,
+    }
+
+    let id = Id::new("__save_theme");
+    let mut state = ui.memory().data.get_temp(id).unwrap_or_else(|| SaveState {
+        path: "theme.ron".to_owned(),
+        message: None,
+    });
+
+    ui.horizontal(|ui| {
+        ui.label("File:");
+        ui.text_edit_singleline(&mut state.path);
+    });
+    ui.horizontal(|ui| {
+        if ui
+            .button("💾 Save")
+            .on_hover_text("Save the current visuals, spacing and font sizes as a theme file")
+            .clicked()
+        {
+            state.message = Some(match Theme::from_context(ctx).save(&state.path) {
+                Ok(()) => format!("Saved {}", state.path),
+                Err(err) => format!("Failed to save {}: {}", state.path, err),
+            });
+        }
+        if ui
+            .button("📋 Copy")
+            .on_hover_text("Copy the theme file to the clipboard")
+            .clicked()
+        {
+            match Theme::from_context(ctx).to_ron() {
+                Ok(ron) => ui.output().copied_text = ron,
+                Err(err) => state.message = Some(format!("Failed to copy: {}", err)),
+            }
+        }
+    });
+    if let Some(message) = &state.message {
+        ui.label(message);
+    }
+
+    ui.memory().data.insert_temp(id, state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "theme_files")]
+    #[test]
+    fn ron_round_trip() {
+        let mut theme = Theme {
+            visuals: Visuals::light(),
+            ..Default::default()
+        };
+        theme
+            .visuals
+            .widget_overrides
+            .insert(WidgetType::Button, style::Widgets::dark());
+        theme
+            .fonts
+            .family_and_size
+            .insert(TextStyle::Heading, (FontFamily::Monospace, 24.0));
+
+        let ron = theme.to_ron().unwrap();
+        assert_eq!(Theme::from_ron(&ron).unwrap(), theme);
+    }
+
+    #[cfg(feature = "theme_files")]
+    #[test]
+    fn missing_fields_are_default() {
+        let theme = Theme::from_ron("(spacing: (indent: 32.0))").unwrap();
+        assert_eq!(theme.spacing.indent, 32.0);
+        assert_eq!(theme.visuals, Visuals::default());
+    }
+
+    #[cfg(feature = "default_fonts")]
+    #[test]
+    fn font_files_are_loaded_before_applying() {
+        let mut ctx = CtxRef::default();
+        let _ = ctx.run(Default::default(), |_| {});
+
+        let mut theme = Theme::from_context(&ctx);
+        theme
+            .fonts
+            .files
+            .insert("Mine".to_owned(), PathBuf::from("mine.ttf"));
+        theme
+            .fonts
+            .families
+            .insert(FontFamily::Proportional, vec!["Mine".to_owned()]);
+        assert!(ctx.set_theme(&theme).is_err(), "the file hasn't been read");
+
+        let font = FontDefinitions::default().font_data["Ubuntu-Light"].clone();
+        theme.fonts.data.insert("Mine".to_owned(), font);
+        ctx.set_theme(&theme).unwrap();
+        let _ = ctx.run(Default::default(), |_| {});
+        let definitions = ctx.font_definitions();
+        assert!(definitions.font_data.contains_key("Mine"));
+        assert_eq!(
+            definitions.fonts_for_family[&FontFamily::Proportional][0],
+            "Mine"
+        );
+    }
+}
@@ -153,6 +153,20 @@ impl Ui {
         self.style = self.ctx().style();
     }
 
+    /// Apply a [`crate::StyleClass`] registered with [`Context::set_style_class`].
+    /// Changes apply to this `Ui` and its subsequent children.
+    ///
+    /// Classes added later win over classes added earlier. Unknown class names are ignored.
+    /// See also [`Self::scope_with_class`].
+    ///
+    /// The style with the class applied is cached by the [`Context`],
+    /// so this doesn't clone the [`Style`] every frame.
+    pub fn add_style_class(&mut self, name: &str) {
+        if let Some(style) = self.ctx().style_with_class(&self.style, Id::new(name)) {
+            self.style = style;
+        }
+    }
+
     /// The current spacing options for this `Ui`.
     /// Short for `ui.style().spacing`.
     #[inline]
@@ -880,9 +894,36 @@ impl Ui {
     pub fn scroll_to_cursor(&mut self, align: Align) {
         let target = self.next_widget_position();
         for d in 0..2 {
-            self.ctx().frame_state().scroll_target[d] = Some((target[d], align));
+            self.ctx().frame_state().scroll_target[d] = Some((target[d], align, false));
         }
     }
+
+    /// Scroll the closest enclosing [`ScrollArea`] so that `rect` is visible with the specified alignment.
+    ///
+    /// `rect` is in screen coordinates, e.g. [`Response::rect`].
+    /// If `animated` is `true` it smoothly scrolls there over a short time, otherwise it jumps right away.
+    ///
+    /// To scroll a [`ScrollArea`] from outside of it, use [`ScrollArea::scroll_to_offset`]
+    /// or [`ScrollArea::scroll_to_content_rect`].
+    ///
+    /// ```
+    /// # use egui::Align;
+    /// # egui::__run_test_ui(|ui| {
+    /// egui::ScrollArea::vertical().show(ui, |ui| {
+    ///     for i in 0..1000 {
+    ///         let response = ui.label(format!("Item {}", i));
+    ///         if i == 500 {
+    ///             ui.scroll_to_rect(response.rect, Align::Center, true);
+    ///         }
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn scroll_to_rect(&self, rect: Rect, align: Align, animated: bool) {
+        self.ctx()
+            .frame_state()
+            .scroll_to_rect(rect, align, animated);
+    }
 }
 
 /// # Adding widgets
@@ -1450,6 +1491,30 @@ impl Ui {
         self.scope_dyn(Box::new(add_contents))
     }
 
+    /// Like [`Self::scope`], with a [`crate::StyleClass`] applied to the contents (see [`Self::add_style_class`]).
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.scope_with_class("toolbar", |ui| {
+    ///     ui.horizontal(|ui| {
+    ///         let _ = ui.button("Cut");
+    ///         let _ = ui.button("Copy");
+    ///         let _ = ui.button("Paste");
+    ///     });
+    /// });
+    /// # });
+    /// ```
+    pub fn scope_with_class<R>(
+        &mut self,
+        name: &str,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        self.scope(|ui| {
+            ui.add_style_class(name);
+            add_contents(ui)
+        })
+    }
+
     fn scope_dyn<'c, R>(
         &mut self,
         add_contents: Box<dyn FnOnce(&mut Ui) -> R + 'c>,
@@ -1475,6 +1540,103 @@ impl Ui {
         })
     }
 
+    /// Make the contents draggable, carrying `payload` to a drop target.
+    ///
+    /// While dragged, the contents follow the pointer.
+    /// The drag is cancelled if the user presses Escape.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// ui.dnd_drag_source(egui::Id::new("item_a"), "Item A".to_owned(), |ui| {
+    ///     ui.label("Item A");
+    /// });
+    ///
+    /// let (_, dropped) = ui.dnd_drop_zone::<String, _, _>(egui::Frame::group(ui.style()), |ui| {
+    ///     ui.label("Drop here");
+    /// });
+    /// if let Some(item) = dropped {
+    ///     // …
+    /// }
+    /// # });
+    /// ```
+    pub fn dnd_drag_source<P: std::any::Any + Send + Sync, R>(
+        &mut self,
+        id: Id,
+        payload: P,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> InnerResponse<R> {
+        let is_being_dragged =
+            self.memory().is_being_dragged(id) && self.memory().dnd_has_payload();
+
+        if is_being_dragged {
+            self.output().cursor_icon = CursorIcon::Grabbing;
+
+            // Paint the contents to a new layer, and move that layer to where the pointer is.
+            // Nothing in the tooltip layer can be interacted with, which is fine while dragging.
+            let layer_id = LayerId::new(Order::Tooltip, id);
+            let InnerResponse { inner, response } = self.with_layer_id(layer_id, add_contents);
+            if let Some(pointer_pos) = self.input().pointer.interact_pos() {
+                let delta = pointer_pos - response.rect.center();
+                self.ctx().translate_layer(layer_id, delta);
+            }
+            InnerResponse::new(inner, response)
+        } else {
+            let InnerResponse { inner, response } = self.scope(add_contents);
+            let response = self.interact(response.rect, id, Sense::drag());
+            response.dnd_set_payload(payload);
+            if response.hovered() {
+                self.output().cursor_icon = CursorIcon::Grab;
+            }
+            InnerResponse::new(inner, response)
+        }
+    }
+
+    /// A frame that accepts payloads of type `P` dropped on it.
+    ///
+    /// While a `P` is dragged, the frame is highlighted when hovered.
+    /// While something else is dragged, the frame is grayed out.
+    ///
+    /// Returns the payload if it was dropped on the frame this frame.
+    /// Use `Vec<DroppedFile>` as `P` to accept files dropped from the OS.
+    ///
+    /// See [`Self::dnd_drag_source`].
+    pub fn dnd_drop_zone<P: std::any::Any + Send + Sync, R, F: FnOnce(&mut Ui) -> R>(
+        &mut self,
+        frame: Frame,
+        add_contents: F,
+    ) -> (InnerResponse<R>, Option<std::sync::Arc<P>>) {
+        let is_anything_dragged = self.memory().dnd_has_payload();
+        let can_accept = self.memory().dnd_payload::<P>().is_some();
+
+        let mut prepared = frame.begin(self);
+        let inner = add_contents(&mut prepared.content_ui);
+        let is_pointer_over = self
+            .ctx()
+            .rect_contains_pointer(self.layer_id(), prepared.outer_rect());
+
+        let visuals = if can_accept && is_pointer_over {
+            self.visuals().widgets.active
+        } else {
+            self.visuals().widgets.inactive
+        };
+        let mut fill = visuals.bg_fill;
+        let mut stroke = visuals.bg_stroke;
+        if is_anything_dragged && !can_accept {
+            // Gray out:
+            fill = color::tint_color_towards(fill, self.visuals().window_fill());
+            stroke.color = color::tint_color_towards(stroke.color, self.visuals().window_fill());
+            if is_pointer_over {
+                self.output().cursor_icon = CursorIcon::NoDrop;
+            }
+        }
+        prepared.frame.fill = fill;
+        prepared.frame.stroke = stroke;
+
+        let response = prepared.end(self);
+        let payload = response.dnd_release_payload::<P>();
+        (InnerResponse::new(inner, response), payload)
+    }
+
     /// A [`CollapsingHeader`] that starts out collapsed.
     pub fn collapsing<R>(
         &mut self,
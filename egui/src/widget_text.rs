@@ -169,6 +169,15 @@ impl RichText {
         self
     }
 
+    /// Set the text color unless it has already been set (also with [`Self::strong`] or [`Self::weak`]).
+    #[inline]
+    pub fn fallback_text_color(mut self, color: impl Into<Color32>) -> Self {
+        if self.text_color.is_none() && !self.strong && !self.weak {
+            self.text_color = Some(color.into());
+        }
+        self
+    }
+
     /// Read the font height of the selected text style.
     pub fn font_height(&self, fonts: &epaint::text::Fonts, style: &crate::Style) -> f32 {
         let text_style = self
@@ -330,6 +339,17 @@ impl WidgetText {
         }
     }
 
+    /// Set the text color unless it has already been set, if this is a [`RichText`].
+    ///
+    /// Prefer using [`RichText`] directly!
+    #[inline]
+    pub fn fallback_text_color(self, color: impl Into<Color32>) -> Self {
+        match self {
+            Self::RichText(text) => Self::RichText(text.fallback_text_color(color)),
+            Self::LayoutJob(_) | Self::Galley(_) => self,
+        }
+    }
+
     /// Override text color if, and only if, this is a [`RichText`].
     ///
     /// Prefer using [`RichText`] directly!
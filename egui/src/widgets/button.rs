@@ -30,6 +30,7 @@ pub struct Button {
     frame: Option<bool>,
     min_size: Vec2,
     image: Option<widgets::Image>,
+    class: Option<Id>,
 }
 
 impl Button {
@@ -44,6 +45,7 @@ impl Button {
             frame: None,
             min_size: Vec2::ZERO,
             image: None,
+            class: None,
         }
     }
 
@@ -64,6 +66,7 @@ impl Button {
             wrap: None,
             min_size: Vec2::ZERO,
             image: Some(widgets::Image::new(texture_id, size)),
+            class: None,
         }
     }
 
@@ -116,6 +119,14 @@ impl Button {
         self
     }
 
+    /// Use a [`StyleClass`] registered with [`Context::set_style_class`], e.g. `"danger"`.
+    ///
+    /// [`Self::fill`] and [`Self::stroke`] still win over the class.
+    pub fn class(mut self, name: &str) -> Self {
+        self.class = Some(Id::new(name));
+        self
+    }
+
     pub(crate) fn min_size(mut self, min_size: Vec2) -> Self {
         self.min_size = min_size;
         self
@@ -134,11 +145,21 @@ impl Widget for Button {
             frame,
             min_size,
             image,
+            class,
         } = self;
 
+        let class = class.and_then(|class| ui.ctx().style_class_by_id(class));
+        let class = class.as_deref();
+        let text = match class {
+            Some(class) => class.text(text),
+            None => text,
+        };
+
         let frame = frame.unwrap_or_else(|| ui.visuals().button_frame);
 
-        let mut button_padding = ui.spacing().button_padding;
+        let mut button_padding = class
+            .and_then(|class| class.button_padding)
+            .unwrap_or(ui.spacing().button_padding);
         if small {
             button_padding.y = 0.0;
         }
@@ -162,7 +183,9 @@ impl Widget for Button {
         response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, text.text()));
 
         if ui.is_rect_visible(rect) {
-            let visuals = ui.style().interact(&response);
+            let visuals = ui
+                .style()
+                .interact_with_class(class, WidgetType::Button, &response);
             let text_pos = if let Some(image) = image {
                 let icon_spacing = ui.spacing().icon_spacing;
                 pos2(
@@ -257,7 +280,7 @@ impl<'a> Widget for Checkbox<'a> {
 
         if ui.is_rect_visible(rect) {
             // let visuals = ui.style().interact_selectable(&response, *checked); // too colorful
-            let visuals = ui.style().interact(&response);
+            let visuals = ui.style().interact_as(WidgetType::Checkbox, &response);
             let text_pos = pos2(
                 rect.min.x + button_padding.x + icon_width + icon_spacing,
                 rect.center().y - 0.5 * text.size().y,
@@ -266,8 +289,9 @@ impl<'a> Widget for Checkbox<'a> {
             ui.painter().add(epaint::RectShape {
                 rect: big_icon_rect.expand(visuals.expansion),
                 corner_radius: visuals.corner_radius,
-                fill: visuals.bg_fill,
+                fill: visuals.bg_fill.into(),
                 stroke: visuals.bg_stroke,
+                dash: None,
             });
 
             if *checked {
@@ -351,7 +375,7 @@ impl Widget for RadioButton {
             );
 
             // let visuals = ui.style().interact_selectable(&response, checked); // too colorful
-            let visuals = ui.style().interact(&response);
+            let visuals = ui.style().interact_as(WidgetType::RadioButton, &response);
 
             let (small_icon_rect, big_icon_rect) = ui.spacing().icon_rectangles(rect);
 
@@ -360,17 +384,19 @@ impl Widget for RadioButton {
             painter.add(epaint::CircleShape {
                 center: big_icon_rect.center(),
                 radius: big_icon_rect.width() / 2.0 + visuals.expansion,
-                fill: visuals.bg_fill,
+                fill: visuals.bg_fill.into(),
                 stroke: visuals.bg_stroke,
+                dash: None,
             });
 
             if checked {
                 painter.add(epaint::CircleShape {
                     center: small_icon_rect.center(),
                     radius: small_icon_rect.width() / 3.0,
-                    fill: visuals.fg_stroke.color, // Intentional to use stroke and not fill
+                    fill: visuals.fg_stroke.color.into(), // Intentional to use stroke and not fill
                     // fill: ui.visuals().selection.stroke.color, // too much color
                     stroke: Default::default(),
+                    dash: None,
                 });
             }
 
@@ -459,7 +485,7 @@ impl Widget for ImageButton {
                 let selection = ui.visuals().selection;
                 (-padding, 0.0, selection.bg_fill, selection.stroke)
             } else if frame {
-                let visuals = ui.style().interact(&response);
+                let visuals = ui.style().interact_as(WidgetType::ImageButton, &response);
                 let expansion = if response.hovered {
                     Vec2::splat(visuals.expansion) - padding
                 } else {
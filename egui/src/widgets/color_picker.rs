@@ -62,8 +62,9 @@ fn show_hsva(ui: &mut Ui, color: Hsva, desired_size: Vec2) -> Response {
             ui.painter().add(RectShape {
                 rect,
                 corner_radius: 2.0,
-                fill: color.into(),
+                fill: Brush::Solid(color.into()),
                 stroke: Stroke::new(3.0, color.to_opaque()),
+                dash: None,
             });
         }
     }
@@ -79,7 +80,7 @@ fn color_button(ui: &mut Ui, color: Color32, open: bool) -> Response {
         let visuals = if open {
             &ui.visuals().widgets.open
         } else {
-            ui.style().interact(&response)
+            ui.style().interact_as(WidgetType::ColorButton, &response)
         };
         let rect = rect.expand(visuals.expansion);
 
@@ -199,8 +200,9 @@ fn color_slider_2d(
         ui.painter().add(epaint::CircleShape {
             center: pos2(x, y),
             radius: rect.width() / 12.0,
-            fill: picked_color,
+            fill: picked_color.into(),
             stroke: Stroke::new(visuals.fg_stroke.width, contrast_color(picked_color)),
+            dash: None,
         });
     }
 
@@ -0,0 +1,191 @@
+use std::ops::RangeInclusive;
+
+use super::{month_name, Date, Weekday};
+use crate::*;
+
+/// How a calendar looks, shared by [`super::DatePickerButton`] and [`super::DateRangePickerButton`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct CalendarStyle {
+    pub first_weekday: Weekday,
+    pub week_numbers: bool,
+    pub today: Option<Date>,
+}
+
+/// Shows the month of `cursor` as a grid of days, and returns the day the user picked, if any.
+///
+/// `cursor` is the day with keyboard focus. It is moved with the arrow keys (a day or a week),
+/// page up/down (a month), and the buttons above the grid.
+/// Enter picks the cursor.
+pub(crate) fn calendar_ui(
+    ui: &mut Ui,
+    id: Id,
+    cursor: &mut Date,
+    selected: RangeInclusive<Date>,
+    style: CalendarStyle,
+) -> Option<Date> {
+    let CalendarStyle {
+        first_weekday,
+        week_numbers,
+        today,
+    } = style;
+
+    let mut picked = None;
+
+    {
+        let input = ui.input();
+        if input.key_pressed(Key::ArrowLeft) {
+            *cursor = cursor.add_days(-1);
+        }
+        if input.key_pressed(Key::ArrowRight) {
+            *cursor = cursor.add_days(1);
+        }
+        if input.key_pressed(Key::ArrowUp) {
+            *cursor = cursor.add_days(-7);
+        }
+        if input.key_pressed(Key::ArrowDown) {
+            *cursor = cursor.add_days(7);
+        }
+        if input.key_pressed(Key::PageUp) {
+            *cursor = cursor.add_months(-1);
+        }
+        if input.key_pressed(Key::PageDown) {
+            *cursor = cursor.add_months(1);
+        }
+        if input.key_pressed(Key::Enter) {
+            picked = Some(*cursor);
+        }
+    }
+
+    ui.horizontal(|ui| {
+        if ui
+            .small_button("⏪")
+            .on_hover_text("Previous year")
+            .clicked()
+        {
+            *cursor = cursor.add_months(-12);
+        }
+        if ui
+            .small_button("⏴")
+            .on_hover_text("Previous month")
+            .clicked()
+        {
+            *cursor = cursor.add_months(-1);
+        }
+        ui.label(format!("{} {}", month_name(cursor.month()), cursor.year()));
+        if ui.small_button("⏵").on_hover_text("Next month").clicked() {
+            *cursor = cursor.add_months(1);
+        }
+        if ui.small_button("⏩").on_hover_text("Next year").clicked() {
+            *cursor = cursor.add_months(12);
+        }
+    });
+
+    let cell_size = Vec2::splat(ui.spacing().interact_size.y);
+    let first_of_month = cursor.first_of_month();
+    let days_before = first_of_month.weekday().num_days_from_monday() as i64
+        - first_weekday.num_days_from_monday() as i64;
+    let grid_start = first_of_month.add_days(-days_before.rem_euclid(7));
+    // The ISO week of a row is the week of its Thursday:
+    let thursday_in_row = (Weekday::Thursday.num_days_from_monday() as i64
+        - first_weekday.num_days_from_monday() as i64)
+        .rem_euclid(7);
+
+    Grid::new(id.with("calendar"))
+        .spacing(vec2(2.0, 2.0))
+        .show(ui, |ui| {
+            if week_numbers {
+                ui.add_sized(cell_size, Label::new(RichText::new("Wk").weak()));
+            }
+            for i in 0..7 {
+                let weekday = first_weekday.add_days(i);
+                ui.add_sized(cell_size, Label::new(weekday.short_name()));
+            }
+            ui.end_row();
+
+            // Always six rows, so that the popup doesn't change size between months:
+            for week in 0..6 {
+                let row_start = grid_start.add_days(week * 7);
+                if week_numbers {
+                    let week_number = row_start.add_days(thursday_in_row).iso_week();
+                    ui.add_sized(
+                        cell_size,
+                        Label::new(RichText::new(week_number.to_string()).weak()),
+                    );
+                }
+                for i in 0..7 {
+                    let date = row_start.add_days(i);
+                    let response = day_ui(
+                        ui,
+                        cell_size,
+                        date,
+                        date.month() == cursor.month(),
+                        &selected,
+                        date == *cursor,
+                        Some(date) == today,
+                    );
+                    if response.clicked() {
+                        *cursor = date;
+                        picked = Some(date);
+                    }
+                }
+                ui.end_row();
+            }
+        });
+
+    picked
+}
+
+fn day_ui(
+    ui: &mut Ui,
+    size: Vec2,
+    date: Date,
+    in_shown_month: bool,
+    selected: &RangeInclusive<Date>,
+    is_cursor: bool,
+    is_today: bool,
+) -> Response {
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+    let is_selected = date == *selected.start() || date == *selected.end();
+    response.widget_info(|| {
+        WidgetInfo::selected(WidgetType::SelectableLabel, is_selected, date.to_string())
+    });
+
+    if ui.is_rect_visible(rect) {
+        let visuals = ui.style().interact_selectable(&response, is_selected);
+        let corner_radius = visuals.corner_radius;
+
+        if is_selected || response.hovered() {
+            ui.painter()
+                .rect(rect, corner_radius, visuals.bg_fill, visuals.bg_stroke);
+        } else if selected.contains(&date) {
+            let fill = ui.visuals().selection.bg_fill.linear_multiply(0.4);
+            ui.painter().rect_filled(rect, corner_radius, fill);
+        }
+        if is_today {
+            ui.painter().rect_stroke(
+                rect.shrink(1.0),
+                corner_radius,
+                ui.visuals().widgets.noninteractive.fg_stroke,
+            );
+        }
+        if is_cursor {
+            ui.painter()
+                .rect_stroke(rect, corner_radius, ui.visuals().selection.stroke);
+        }
+
+        let text_color = if is_selected || in_shown_month {
+            visuals.text_color()
+        } else {
+            ui.visuals().weak_text_color()
+        };
+        ui.painter().text(
+            rect.center(),
+            Align2::CENTER_CENTER,
+            date.day(),
+            TextStyle::Button,
+            text_color,
+        );
+    }
+
+    response
+}
@@ -0,0 +1,581 @@
+use std::fmt;
+
+/// A day of the week.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    /// Monday first.
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// Days since Monday, `0..=6`.
+    pub fn num_days_from_monday(self) -> u32 {
+        self as u32
+    }
+
+    /// `n` days later, wrapping around.
+    pub fn add_days(self, n: i64) -> Self {
+        Self::ALL[(self as i64 + n).rem_euclid(7) as usize]
+    }
+
+    /// "Mo", "Tu", …
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Self::Monday => "Mo",
+            Self::Tuesday => "Tu",
+            Self::Wednesday => "We",
+            Self::Thursday => "Th",
+            Self::Friday => "Fr",
+            Self::Saturday => "Sa",
+            Self::Sunday => "Su",
+        }
+    }
+
+    /// The first day of the week in the region of the given locale, e.g. `"en-US"` or `"sv_SE.UTF-8"`.
+    ///
+    /// Most regions start the week on a Monday, which is also what is returned if the locale has no region.
+    pub fn first_day_of_week(locale: &str) -> Self {
+        let region = locale
+            .split(|c: char| c == '.' || c == '@')
+            .next()
+            .unwrap_or_default()
+            .split(|c: char| c == '-' || c == '_')
+            .skip(1)
+            .find(|part| part.len() == 2 && part.chars().all(|c| c.is_ascii_alphabetic()))
+            .map(|region| region.to_ascii_uppercase());
+        let region = match &region {
+            Some(region) => region.as_str(),
+            None => return Self::Monday,
+        };
+
+        // From the Unicode CLDR (`weekData`).
+        const SUNDAY: &[&str] = &[
+            "AG", "AS", "BD", "BR", "BS", "BT", "BW", "BZ", "CA", "CN", "CO", "DM", "DO", "ET",
+            "GT", "GU", "HK", "HN", "ID", "IL", "IN", "JM", "JP", "KE", "KH", "KR", "LA", "MH",
+            "MM", "MO", "MT", "MX", "MZ", "NI", "NP", "PA", "PE", "PH", "PK", "PR", "PT", "PY",
+            "SA", "SG", "SV", "TH", "TT", "TW", "UM", "US", "VE", "VI", "WS", "YE", "ZA", "ZW",
+        ];
+        const SATURDAY: &[&str] = &[
+            "AE", "AF", "BH", "DJ", "DZ", "EG", "IQ", "IR", "JO", "KW", "LY", "OM", "QA", "SD",
+            "SY",
+        ];
+        if SUNDAY.contains(&region) {
+            Self::Sunday
+        } else if SATURDAY.contains(&region) {
+            Self::Saturday
+        } else if region == "MV" {
+            Self::Friday
+        } else {
+            Self::Monday
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A date in the proleptic Gregorian calendar, without a time zone.
+///
+/// Convert to and from `chrono::NaiveDate` with the `chrono` feature,
+/// and to and from `time::Date` with the `time` feature.
+/// Conversions that can fail use [`TryFrom`], with an [`OutOfRangeError`].
+///
+/// ```
+/// # use egui::date_picker::{Date, Weekday};
+/// let date = Date::new(2022, 1, 31).unwrap();
+/// assert_eq!(date.weekday(), Weekday::Monday);
+/// assert_eq!(date.add_months(1), Date::new(2022, 2, 28).unwrap());
+/// assert_eq!(date.to_string(), "2022-01-31");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "DateFields"))]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+/// What a [`Date`] is deserialized from, so that it can be checked with [`Date::new`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct DateFields {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<DateFields> for Date {
+    type Error = OutOfRangeError;
+
+    fn try_from(fields: DateFields) -> Result<Self, Self::Error> {
+        Date::new(fields.year, fields.month, fields.day).ok_or(OutOfRangeError)
+    }
+}
+
+/// A [`Date`] or [`TimeOfDay`] could not be converted, because it is out of range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OutOfRangeError;
+
+impl fmt::Display for OutOfRangeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("date or time out of range")
+    }
+}
+
+impl std::error::Error for OutOfRangeError {}
+
+impl Default for Date {
+    /// 1970-01-01
+    fn default() -> Self {
+        Self::from_days_since_epoch(0)
+    }
+}
+
+impl Date {
+    /// The earliest supported year.
+    pub const MIN_YEAR: i32 = -9999;
+
+    /// The last supported year.
+    pub const MAX_YEAR: i32 = 9999;
+
+    /// `month` is `1..=12`, and `day` starts at `1`.
+    ///
+    /// Returns `None` if there is no such date,
+    /// or if the year is outside of [`Self::MIN_YEAR`]`..=`[`Self::MAX_YEAR`].
+    pub fn new(year: i32, month: u32, day: u32) -> Option<Self> {
+        if (Self::MIN_YEAR..=Self::MAX_YEAR).contains(&year)
+            && (1..=12).contains(&month)
+            && (1..=days_in_month(year, month)).contains(&day)
+        {
+            Some(Self { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    pub fn year(self) -> i32 {
+        self.year
+    }
+
+    /// `1..=12`
+    pub fn month(self) -> u32 {
+        self.month
+    }
+
+    /// `1..=31`
+    pub fn day(self) -> u32 {
+        self.day
+    }
+
+    /// The number of days since 1970-01-01, which can be negative.
+    pub fn days_since_epoch(self) -> i64 {
+        // http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let year = self.year as i64 - (self.month <= 2) as i64;
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The inverse of [`Self::days_since_epoch`]. Clamped to the supported years.
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+        let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+        if year < Self::MIN_YEAR as i64 {
+            Self {
+                year: Self::MIN_YEAR,
+                month: 1,
+                day: 1,
+            }
+        } else if year > Self::MAX_YEAR as i64 {
+            Self {
+                year: Self::MAX_YEAR,
+                month: 12,
+                day: 31,
+            }
+        } else {
+            Self {
+                year: year as i32,
+                month,
+                day,
+            }
+        }
+    }
+
+    pub fn weekday(self) -> Weekday {
+        // 1970-01-01 was a Thursday.
+        Weekday::Thursday.add_days(self.days_since_epoch())
+    }
+
+    /// `n` days later (or earlier, if negative).
+    pub fn add_days(self, n: i64) -> Self {
+        Self::from_days_since_epoch(self.days_since_epoch() + n)
+    }
+
+    /// The same day `n` months later (or earlier, if negative),
+    /// or the last day of that month if it is shorter.
+    pub fn add_months(self, n: i32) -> Self {
+        let months = self.year as i64 * 12 + self.month as i64 - 1 + n as i64;
+        let year = months
+            .div_euclid(12)
+            .clamp(Self::MIN_YEAR as i64, Self::MAX_YEAR as i64) as i32;
+        let month = months.rem_euclid(12) as u32 + 1;
+        Self {
+            year,
+            month,
+            day: self.day.min(days_in_month(year, month)),
+        }
+    }
+
+    /// The first day of the month.
+    pub fn first_of_month(self) -> Self {
+        Self { day: 1, ..self }
+    }
+
+    /// Day of the year, starting at `1` for January 1.
+    pub fn ordinal(self) -> u32 {
+        (self.days_since_epoch() - self.with_month_and_day(1, 1).days_since_epoch()) as u32 + 1
+    }
+
+    /// The ISO 8601 week number, `1..=53`.
+    ///
+    /// Weeks start on Monday, and week 1 is the week with the year's first Thursday.
+    pub fn iso_week(self) -> u32 {
+        let weekday = self.weekday().num_days_from_monday() as i64;
+        let week = (self.ordinal() as i64 - weekday + 9) / 7;
+        if week < 1 {
+            iso_weeks_in_year(self.year - 1)
+        } else if week > iso_weeks_in_year(self.year) as i64 {
+            1
+        } else {
+            week as u32
+        }
+    }
+
+    fn with_month_and_day(self, month: u32, day: u32) -> Self {
+        Self {
+            year: self.year,
+            month,
+            day,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    /// ISO 8601, e.g. `2022-01-31`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+/// `month` is `1..=12`.
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// "January", "February", … for `month` in `1..=12`.
+pub fn month_name(month: u32) -> &'static str {
+    const NAMES: [&str; 12] = [
+        "January",
+        "February",
+        "March",
+        "April",
+        "May",
+        "June",
+        "July",
+        "August",
+        "September",
+        "October",
+        "November",
+        "December",
+    ];
+    NAMES[(month.clamp(1, 12) - 1) as usize]
+}
+
+fn iso_weeks_in_year(year: i32) -> u32 {
+    let january_first = Date {
+        year,
+        month: 1,
+        day: 1,
+    }
+    .weekday();
+    if january_first == Weekday::Thursday
+        || (is_leap_year(year) && january_first == Weekday::Wednesday)
+    {
+        53
+    } else {
+        52
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A time of day, without a time zone.
+///
+/// Convert to and from `chrono::NaiveTime` with the `chrono` feature,
+/// and to and from `time::Time` with the `time` feature.
+/// Converting to them fails with an [`OutOfRangeError`] if a field is out of range.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TimeOfDay {
+    /// `0..=23`
+    pub hour: u32,
+    /// `0..=59`
+    pub minute: u32,
+    /// `0..=59`
+    pub second: u32,
+}
+
+impl TimeOfDay {
+    /// Returns `None` if any of the values are out of range.
+    pub fn new(hour: u32, minute: u32, second: u32) -> Option<Self> {
+        if hour < 24 && minute < 60 && second < 60 {
+            Some(Self {
+                hour,
+                minute,
+                second,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The hour on a 12-hour clock (`1..=12`), and whether it is after noon.
+    pub fn hour12(self) -> (u32, bool) {
+        let pm = self.hour >= 12;
+        let hour = match self.hour % 12 {
+            0 => 12,
+            hour => hour,
+        };
+        (hour, pm)
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    /// 24-hour clock, e.g. `13:05:00`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(feature = "chrono")]
+mod chrono_impl {
+    use super::{Date, OutOfRangeError, TimeOfDay};
+    use chrono::{Datelike as _, Timelike as _};
+
+    impl From<chrono::NaiveDate> for Date {
+        /// Clamped to the years supported by [`Date`].
+        fn from(date: chrono::NaiveDate) -> Self {
+            Date::new(date.year(), date.month(), date.day()).unwrap_or_else(|| {
+                Date::from_days_since_epoch(date.num_days_from_ce() as i64 - 719_163)
+            })
+        }
+    }
+
+    impl TryFrom<Date> for chrono::NaiveDate {
+        type Error = OutOfRangeError;
+
+        fn try_from(date: Date) -> Result<Self, Self::Error> {
+            chrono::NaiveDate::from_ymd_opt(date.year(), date.month(), date.day())
+                .ok_or(OutOfRangeError)
+        }
+    }
+
+    impl From<chrono::NaiveTime> for TimeOfDay {
+        /// Drops fractions of a second, and leap seconds.
+        fn from(time: chrono::NaiveTime) -> Self {
+            TimeOfDay {
+                hour: time.hour(),
+                minute: time.minute(),
+                second: time.second().min(59),
+            }
+        }
+    }
+
+    impl TryFrom<TimeOfDay> for chrono::NaiveTime {
+        type Error = OutOfRangeError;
+
+        fn try_from(time: TimeOfDay) -> Result<Self, Self::Error> {
+            chrono::NaiveTime::from_hms_opt(time.hour, time.minute, time.second)
+                .ok_or(OutOfRangeError)
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impl {
+    use super::{Date, OutOfRangeError, TimeOfDay};
+
+    impl TryFrom<time::Date> for Date {
+        type Error = OutOfRangeError;
+
+        /// Fails for years outside of [`Date::MIN_YEAR`]`..=`[`Date::MAX_YEAR`],
+        /// which `time` supports with its `large-dates` feature.
+        fn try_from(date: time::Date) -> Result<Self, Self::Error> {
+            Date::new(
+                date.year(),
+                u8::from(date.month()) as u32,
+                date.day() as u32,
+            )
+            .ok_or(OutOfRangeError)
+        }
+    }
+
+    impl TryFrom<Date> for time::Date {
+        type Error = OutOfRangeError;
+
+        fn try_from(date: Date) -> Result<Self, Self::Error> {
+            let month =
+                time::Month::try_from(date.month() as u8).map_err(|_err| OutOfRangeError)?;
+            time::Date::from_calendar_date(date.year(), month, date.day() as u8)
+                .map_err(|_err| OutOfRangeError)
+        }
+    }
+
+    impl From<time::Time> for TimeOfDay {
+        /// Drops fractions of a second.
+        fn from(time: time::Time) -> Self {
+            TimeOfDay {
+                hour: time.hour() as u32,
+                minute: time.minute() as u32,
+                second: time.second() as u32,
+            }
+        }
+    }
+
+    impl TryFrom<TimeOfDay> for time::Time {
+        type Error = OutOfRangeError;
+
+        /// Fails if any of the (public) fields of the [`TimeOfDay`] are out of range.
+        fn try_from(time: TimeOfDay) -> Result<Self, Self::Error> {
+            let component = |value: u32| u8::try_from(value).map_err(|_err| OutOfRangeError);
+            time::Time::from_hms(
+                component(time.hour)?,
+                component(time.minute)?,
+                component(time.second)?,
+            )
+            .map_err(|_err| OutOfRangeError)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days_since_epoch_round_trips() {
+        for days in -800_000..800_000 {
+            let date = Date::from_days_since_epoch(days);
+            assert_eq!(date.days_since_epoch(), days, "{}", date);
+            assert!(Date::new(date.year(), date.month(), date.day()).is_some());
+        }
+        assert_eq!(Date::from_days_since_epoch(0).to_string(), "1970-01-01");
+        assert_eq!(Date::new(2000, 3, 1).unwrap().days_since_epoch(), 11_017);
+    }
+
+    #[test]
+    fn iso_weeks() {
+        let week = |y, m, d| Date::new(y, m, d).unwrap().iso_week();
+        assert_eq!(week(2021, 1, 3), 53); // Sunday, still in the last week of 2020
+        assert_eq!(week(2021, 1, 4), 1);
+        assert_eq!(week(2024, 12, 30), 1); // Monday of the first week of 2025
+        assert_eq!(week(2026, 12, 31), 53);
+        assert_eq!(week(2022, 6, 15), 24);
+    }
+
+    #[test]
+    fn first_day_of_week() {
+        assert_eq!(Weekday::first_day_of_week("en-US"), Weekday::Sunday);
+        assert_eq!(Weekday::first_day_of_week("sv_SE.UTF-8"), Weekday::Monday);
+        assert_eq!(Weekday::first_day_of_week("ar-EG"), Weekday::Saturday);
+        assert_eq!(Weekday::first_day_of_week("zh-Hant-TW"), Weekday::Sunday);
+        assert_eq!(Weekday::first_day_of_week("de"), Weekday::Monday);
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn deserialize_checks_the_date() {
+        let date: Date = ron::from_str("(year: 2024, month: 2, day: 29)").unwrap();
+        assert_eq!(date, Date::new(2024, 2, 29).unwrap());
+        assert_eq!(ron::to_string(&date).unwrap(), "(year:2024,month:2,day:29)");
+
+        assert!(ron::from_str::<Date>("(year: 2023, month: 2, day: 29)").is_err());
+        assert!(ron::from_str::<Date>("(year: 2023, month: 13, day: 1)").is_err());
+        assert!(ron::from_str::<Date>("(year: 20000, month: 1, day: 1)").is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_conversions() {
+        let date = Date::new(2022, 1, 31).unwrap();
+        let naive = chrono::NaiveDate::try_from(date).unwrap();
+        assert_eq!(Date::from(naive), date);
+
+        let time = TimeOfDay::new(13, 5, 0).unwrap();
+        assert_eq!(
+            TimeOfDay::from(chrono::NaiveTime::try_from(time).unwrap()),
+            time
+        );
+        let invalid = TimeOfDay { hour: 24, ..time };
+        assert_eq!(chrono::NaiveTime::try_from(invalid), Err(OutOfRangeError));
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time_conversions() {
+        let date = Date::new(2022, 1, 31).unwrap();
+        let time_date = time::Date::try_from(date).unwrap();
+        assert_eq!(Date::try_from(time_date), Ok(date));
+
+        let time = TimeOfDay::new(13, 5, 0).unwrap();
+        assert_eq!(TimeOfDay::from(time::Time::try_from(time).unwrap()), time);
+        let invalid = TimeOfDay {
+            second: 300,
+            ..time
+        };
+        assert_eq!(time::Time::try_from(invalid), Err(OutOfRangeError));
+    }
+}
@@ -0,0 +1,287 @@
+//! Date and time pickers.
+//!
+//! The widgets work with the plain [`Date`] and [`TimeOfDay`] types.
+//! Enable the `chrono` or `time` feature to convert them to and from the types of those crates.
+
+use crate::*;
+use calendar::{calendar_ui, CalendarStyle};
+
+pub use date::{
+    days_in_month, is_leap_year, month_name, Date, OutOfRangeError, TimeOfDay, Weekday,
+};
+pub use time_edit::TimeEdit;
+
+mod calendar;
+mod date;
+mod time_edit;
+
+/// A button showing a date, which opens a calendar when clicked.
+///
+/// The calendar can be navigated with the arrow keys and page up/down. Enter picks a date.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::date_picker::{Date, DatePickerButton, Weekday};
+/// # let mut date = Date::new(2022, 1, 31).unwrap();
+/// let response = ui.add(
+///     DatePickerButton::new(&mut date)
+///         .id_source("due_date")
+///         .first_weekday(Weekday::first_day_of_week("en-US")),
+/// );
+/// if response.changed() {
+///     // …
+/// }
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct DatePickerButton<'a> {
+    date: &'a mut Date,
+    id_source: Option<Id>,
+    first_weekday: Weekday,
+    week_numbers: bool,
+    today: Option<Date>,
+}
+
+impl<'a> DatePickerButton<'a> {
+    pub fn new(date: &'a mut Date) -> Self {
+        Self {
+            date,
+            id_source: None,
+            first_weekday: Weekday::Monday,
+            week_numbers: true,
+            today: None,
+        }
+    }
+
+    /// Needed if you have more than one date picker in the same [`Ui`].
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id_source = Some(Id::new(id_source));
+        self
+    }
+
+    /// The day the weeks start on. Default: [`Weekday::Monday`].
+    ///
+    /// Use [`Weekday::first_day_of_week`] to get it from the user's locale.
+    pub fn first_weekday(mut self, first_weekday: Weekday) -> Self {
+        self.first_weekday = first_weekday;
+        self
+    }
+
+    /// Show ISO 8601 week numbers. Default: `true`.
+    pub fn week_numbers(mut self, week_numbers: bool) -> Self {
+        self.week_numbers = week_numbers;
+        self
+    }
+
+    /// Mark this day in the calendar, and show a button for jumping to it.
+    ///
+    /// egui doesn't know the time zone of the user, so this is not shown by default.
+    pub fn today(mut self, today: Date) -> Self {
+        self.today = Some(today);
+        self
+    }
+}
+
+impl<'a> Widget for DatePickerButton<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            date,
+            id_source,
+            first_weekday,
+            week_numbers,
+            today,
+        } = self;
+
+        let id = ui.make_persistent_id(id_source.unwrap_or_else(|| Id::new("date_picker")));
+        let popup_id = id.with("popup");
+        let style = CalendarStyle {
+            first_weekday,
+            week_numbers,
+            today,
+        };
+
+        let mut response = ui.button(format!("📅 {}", date));
+        if response.clicked() {
+            ui.memory().toggle_popup(popup_id);
+            // Start out showing the current date:
+            ui.memory().data.insert_temp(popup_id, *date);
+        }
+
+        let picked = popup::popup_below_widget_with(
+            ui,
+            popup_id,
+            &response,
+            popup::PopupCloseBehavior::CloseOnClickOutside,
+            |ui| {
+                let mut cursor = ui.memory().data.get_temp(popup_id).unwrap_or(*date);
+                let mut picked = calendar_ui(ui, id, &mut cursor, *date..=*date, style);
+                if let Some(today) = today {
+                    if ui.button("Today").clicked() {
+                        picked = Some(today);
+                    }
+                }
+                ui.memory().data.insert_temp(popup_id, cursor);
+                picked
+            },
+        )
+        .flatten();
+
+        if let Some(picked) = picked {
+            if *date != picked {
+                *date = picked;
+                response.mark_changed();
+            }
+            ui.memory().close_popup();
+        }
+
+        response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug)]
+struct RangeState {
+    cursor: Date,
+    /// The first end of the range, picked with the first click.
+    first_pick: Option<Date>,
+}
+
+/// A button showing a range of dates, which opens a calendar when clicked.
+///
+/// The first day picked in the calendar is one end of the range, and the second is the other.
+/// `start` is always before or equal to `end` afterwards.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::date_picker::{Date, DateRangePickerButton};
+/// # let mut start = Date::new(2022, 1, 3).unwrap();
+/// # let mut end = Date::new(2022, 1, 7).unwrap();
+/// ui.add(DateRangePickerButton::new(&mut start, &mut end).id_source("vacation"));
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct DateRangePickerButton<'a> {
+    start: &'a mut Date,
+    end: &'a mut Date,
+    id_source: Option<Id>,
+    first_weekday: Weekday,
+    week_numbers: bool,
+    today: Option<Date>,
+}
+
+impl<'a> DateRangePickerButton<'a> {
+    pub fn new(start: &'a mut Date, end: &'a mut Date) -> Self {
+        Self {
+            start,
+            end,
+            id_source: None,
+            first_weekday: Weekday::Monday,
+            week_numbers: true,
+            today: None,
+        }
+    }
+
+    /// Needed if you have more than one date range picker in the same [`Ui`].
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id_source = Some(Id::new(id_source));
+        self
+    }
+
+    /// The day the weeks start on. Default: [`Weekday::Monday`].
+    ///
+    /// Use [`Weekday::first_day_of_week`] to get it from the user's locale.
+    pub fn first_weekday(mut self, first_weekday: Weekday) -> Self {
+        self.first_weekday = first_weekday;
+        self
+    }
+
+    /// Show ISO 8601 week numbers. Default: `true`.
+    pub fn week_numbers(mut self, week_numbers: bool) -> Self {
+        self.week_numbers = week_numbers;
+        self
+    }
+
+    /// Mark this day in the calendar.
+    pub fn today(mut self, today: Date) -> Self {
+        self.today = Some(today);
+        self
+    }
+}
+
+impl<'a> Widget for DateRangePickerButton<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            start,
+            end,
+            id_source,
+            first_weekday,
+            week_numbers,
+            today,
+        } = self;
+
+        let id = ui.make_persistent_id(id_source.unwrap_or_else(|| Id::new("date_range_picker")));
+        let popup_id = id.with("popup");
+        let style = CalendarStyle {
+            first_weekday,
+            week_numbers,
+            today,
+        };
+
+        let mut response = ui.button(format!("📅 {} – {}", start, end));
+        if response.clicked() {
+            ui.memory().toggle_popup(popup_id);
+            let state = RangeState {
+                cursor: *start,
+                first_pick: None,
+            };
+            ui.memory().data.insert_temp(popup_id, state);
+        }
+
+        let picked = popup::popup_below_widget_with(
+            ui,
+            popup_id,
+            &response,
+            popup::PopupCloseBehavior::CloseOnClickOutside,
+            |ui| {
+                let mut state = ui.memory().data.get_temp(popup_id).unwrap_or(RangeState {
+                    cursor: *start,
+                    first_pick: None,
+                });
+                let selected = match state.first_pick {
+                    Some(first_pick) => first_pick..=first_pick,
+                    None => *start..=*end,
+                };
+                let mut picked = None;
+                if let Some(date) = calendar_ui(ui, id, &mut state.cursor, selected, style) {
+                    match state.first_pick.take() {
+                        Some(first_pick) => {
+                            picked = Some((first_pick.min(date), first_pick.max(date)));
+                        }
+                        None => state.first_pick = Some(date),
+                    }
+                }
+                let hint = if state.first_pick.is_some() {
+                    "Pick the other end of the range"
+                } else {
+                    "Pick the first day of the range"
+                };
+                ui.label(RichText::new(hint).weak());
+                ui.memory().data.insert_temp(popup_id, state);
+                picked
+            },
+        )
+        .flatten();
+
+        if let Some((new_start, new_end)) = picked {
+            if (*start, *end) != (new_start, new_end) {
+                *start = new_start;
+                *end = new_end;
+                response.mark_changed();
+            }
+            ui.memory().close_popup();
+        }
+
+        response
+    }
+}
@@ -0,0 +1,81 @@
+use super::TimeOfDay;
+use crate::*;
+
+/// Edit a [`TimeOfDay`] with one spinner ([`DragValue`]) each for hours, minutes and (optionally) seconds.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut time = egui::date_picker::TimeOfDay::default();
+/// ui.add(egui::date_picker::TimeEdit::new(&mut time).twelve_hour(true));
+/// # });
+/// ```
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct TimeEdit<'a> {
+    time: &'a mut TimeOfDay,
+    twelve_hour: bool,
+    seconds: bool,
+}
+
+impl<'a> TimeEdit<'a> {
+    pub fn new(time: &'a mut TimeOfDay) -> Self {
+        Self {
+            time,
+            twelve_hour: false,
+            seconds: false,
+        }
+    }
+
+    /// Show a 12-hour clock with an AM/PM toggle, instead of a 24-hour clock (the default).
+    pub fn twelve_hour(mut self, twelve_hour: bool) -> Self {
+        self.twelve_hour = twelve_hour;
+        self
+    }
+
+    /// Show and edit seconds. Default: `false`.
+    pub fn seconds(mut self, seconds: bool) -> Self {
+        self.seconds = seconds;
+        self
+    }
+}
+
+impl<'a> Widget for TimeEdit<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            time,
+            twelve_hour,
+            seconds,
+        } = self;
+
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 2.0;
+
+            if twelve_hour {
+                let (mut hour, mut pm) = time.hour12();
+                let mut response = ui.add(DragValue::new(&mut hour).clamp_range(1..=12));
+                ui.label(":");
+                response |= ui.add(DragValue::new(&mut time.minute).clamp_range(0..=59));
+                if seconds {
+                    ui.label(":");
+                    response |= ui.add(DragValue::new(&mut time.second).clamp_range(0..=59));
+                }
+                let am_pm = ui.button(if pm { "PM" } else { "AM" });
+                if am_pm.clicked() {
+                    pm = !pm;
+                    response.mark_changed();
+                }
+                time.hour = hour % 12 + if pm { 12 } else { 0 };
+                response | am_pm
+            } else {
+                let mut response = ui.add(DragValue::new(&mut time.hour).clamp_range(0..=23));
+                ui.label(":");
+                response |= ui.add(DragValue::new(&mut time.minute).clamp_range(0..=59));
+                if seconds {
+                    ui.label(":");
+                    response |= ui.add(DragValue::new(&mut time.second).clamp_range(0..=59));
+                }
+                response
+            }
+        })
+        .inner
+    }
+}
@@ -67,7 +67,7 @@ impl Widget for Hyperlink {
 
         if ui.is_rect_visible(response.rect) {
             let color = ui.visuals().hyperlink_color;
-            let visuals = ui.style().interact(&response);
+            let visuals = ui.style().interact_as(WidgetType::Hyperlink, &response);
 
             let underline = if response.hovered() || response.has_focus() {
                 Stroke::new(visuals.fg_stroke.width, color)
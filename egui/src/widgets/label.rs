@@ -15,6 +15,7 @@ pub struct Label {
     text: WidgetText,
     wrap: Option<bool>,
     sense: Sense,
+    class: Option<Id>,
 }
 
 impl Label {
@@ -23,6 +24,7 @@ impl Label {
             text: text.into(),
             wrap: None,
             sense: Sense::focusable_noninteractive(),
+            class: None,
         }
     }
 
@@ -61,12 +63,36 @@ impl Label {
         self.sense = sense;
         self
     }
+
+    /// Use a [`StyleClass`] registered with [`Context::set_style_class`], e.g. `"danger"`.
+    ///
+    /// The color and text style of a [`RichText`] still win over the class.
+    pub fn class(mut self, name: &str) -> Self {
+        self.class = Some(Id::new(name));
+        self
+    }
 }
 
 impl Label {
     /// Do layout and position the galley in the ui, without painting it or adding widget info.
     pub fn layout_in_ui(self, ui: &mut Ui) -> (Pos2, WidgetTextGalley, Response) {
-        if let WidgetText::Galley(galley) = self.text {
+        let class = self
+            .class
+            .and_then(|class| ui.ctx().style_class_by_id(class));
+        self.layout_with_class(ui, class.as_deref())
+    }
+
+    fn layout_with_class(
+        self,
+        ui: &mut Ui,
+        class: Option<&StyleClass>,
+    ) -> (Pos2, WidgetTextGalley, Response) {
+        let text = match class {
+            Some(class) => class.text(self.text),
+            None => self.text,
+        };
+
+        if let WidgetText::Galley(galley) = text {
             // If the user said "use this specific galley", then just use it:
             let (rect, response) = ui.allocate_exact_size(galley.size(), self.sense);
             let pos = match galley.job.halign {
@@ -82,7 +108,7 @@ impl Label {
         }
 
         let valign = ui.layout().vertical_align();
-        let mut text_job = self.text.into_text_job(ui.style(), TextStyle::Body, valign);
+        let mut text_job = text.into_text_job(ui.style(), TextStyle::Body, valign);
 
         let should_wrap = self.wrap.unwrap_or_else(|| ui.wrap_text());
         let available_width = ui.available_width();
@@ -153,11 +179,18 @@ impl Label {
 
 impl Widget for Label {
     fn ui(self, ui: &mut Ui) -> Response {
-        let (pos, text_galley, response) = self.layout_in_ui(ui);
+        let class = self
+            .class
+            .and_then(|class| ui.ctx().style_class_by_id(class));
+        let class = class.as_deref();
+        let (pos, text_galley, response) = self.layout_with_class(ui, class);
         response.widget_info(|| WidgetInfo::labeled(WidgetType::Label, text_galley.text()));
 
         if ui.is_rect_visible(response.rect) {
-            let response_color = ui.style().interact(&response).text_color();
+            let response_color = ui
+                .style()
+                .interact_with_class(class, WidgetType::Label, &response)
+                .text_color();
 
             let underline = if response.has_focus() {
                 Stroke::new(1.0, response_color)
@@ -8,6 +8,7 @@ use crate::*;
 
 mod button;
 pub mod color_picker;
+pub mod date_picker;
 pub(crate) mod drag_value;
 mod hyperlink;
 mod image;
@@ -108,7 +109,7 @@ pub fn reset_button_with<T: PartialEq>(ui: &mut Ui, value: &mut T, reset_value:
 // ----------------------------------------------------------------------------
 
 pub fn stroke_ui(ui: &mut crate::Ui, stroke: &mut epaint::Stroke, text: &str) {
-    let epaint::Stroke { width, color } = stroke;
+    let epaint::Stroke { width, color, .. } = stroke;
     ui.horizontal(|ui| {
         ui.add(DragValue::new(width).speed(0.1).clamp_range(0.0..=5.0))
             .on_hover_text("Width");
@@ -130,8 +130,9 @@ impl Bar {
         let rect = Shape::Rect(RectShape {
             rect,
             corner_radius: 0.0,
-            fill,
+            fill: fill.into(),
             stroke,
+            dash: None,
         });
 
         shapes.push(rect);
@@ -153,8 +153,9 @@ impl BoxElem {
         let rect = Shape::Rect(RectShape {
             rect,
             corner_radius: 0.0,
-            fill,
+            fill: fill.into(),
             stroke,
+            dash: None,
         });
         shapes.push(rect);
 
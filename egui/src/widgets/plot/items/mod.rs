@@ -856,8 +856,9 @@ impl PlotItem for Points {
                         shapes.push(Shape::Circle(epaint::CircleShape {
                             center,
                             radius,
-                            fill,
+                            fill: fill.into(),
                             stroke,
+                            dash: None,
                         }));
                     }
                     MarkerShape::Diamond => {
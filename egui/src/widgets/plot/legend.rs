@@ -119,8 +119,9 @@ impl LegendEntry {
         painter.add(epaint::CircleShape {
             center: icon_rect.center(),
             radius: icon_size * 0.5,
-            fill: visuals.bg_fill,
+            fill: visuals.bg_fill.into(),
             stroke: visuals.bg_stroke,
+            dash: None,
         });
 
         if *checked {
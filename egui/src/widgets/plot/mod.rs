@@ -357,8 +357,9 @@ impl Plot {
             ui.painter().sub_region(rect).add(epaint::RectShape {
                 rect,
                 corner_radius: 2.0,
-                fill: ui.visuals().extreme_bg_color,
+                fill: ui.visuals().extreme_bg_color.into(),
                 stroke: ui.visuals().widgets.noninteractive.bg_stroke,
+                dash: None,
             });
         }
 
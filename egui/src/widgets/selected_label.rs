@@ -59,7 +59,9 @@ impl Widget for SelectableLabel {
                 .align_size_within_rect(text.size(), rect.shrink2(button_padding))
                 .min;
 
-            let visuals = ui.style().interact_selectable(&response, selected);
+            let visuals =
+                ui.style()
+                    .interact_selectable_as(WidgetType::SelectableLabel, &response, selected);
 
             if selected || response.hovered() || response.has_focus() {
                 let rect = rect.expand(visuals.expansion);
@@ -73,6 +73,7 @@ pub struct Slider<'a> {
     text_color: Option<Color32>,
     min_decimals: usize,
     max_decimals: Option<usize>,
+    class: Option<Id>,
 }
 
 impl<'a> Slider<'a> {
@@ -115,6 +116,7 @@ impl<'a> Slider<'a> {
             text_color: None,
             min_decimals: 0,
             max_decimals: None,
+            class: None,
         }
     }
 
@@ -125,6 +127,14 @@ impl<'a> Slider<'a> {
         self
     }
 
+    /// Use a [`StyleClass`] registered with [`Context::set_style_class`], e.g. `"primary"`.
+    ///
+    /// This styles the slider and its text, but not the [`DragValue`] showing the value.
+    pub fn class(mut self, name: &str) -> Self {
+        self.class = Some(Id::new(name));
+        self
+    }
+
     /// Show a prefix before the number, e.g. "x: "
     pub fn prefix(mut self, prefix: impl ToString) -> Self {
         self.prefix = prefix.to_string();
@@ -293,7 +303,7 @@ impl<'a> Slider<'a> {
     }
 
     /// Just the slider, no text
-    fn slider_ui(&mut self, ui: &mut Ui, response: &Response) {
+    fn slider_ui(&mut self, ui: &mut Ui, response: &Response, class: Option<&StyleClass>) {
         let rect = &response.rect;
         let position_range = self.position_range(rect);
 
@@ -352,16 +362,23 @@ impl<'a> Slider<'a> {
 
             let position_1d = self.position_from_value(value, position_range);
 
-            let visuals = ui.style().interact(response);
+            let visuals = ui
+                .style()
+                .interact_with_class(class, WidgetType::Slider, response);
+            let rail_visuals = match class.and_then(|class| class.widgets.as_ref()) {
+                Some(widgets) => &widgets.inactive,
+                None => &ui.visuals().widgets_for(WidgetType::Slider).inactive,
+            };
             ui.painter().add(epaint::RectShape {
                 rect: rail_rect,
-                corner_radius: ui.visuals().widgets.inactive.corner_radius,
-                fill: ui.visuals().widgets.inactive.bg_fill,
+                corner_radius: rail_visuals.corner_radius,
+                fill: rail_visuals.bg_fill.into(),
                 // fill: visuals.bg_fill,
                 // fill: ui.visuals().extreme_bg_color,
                 stroke: Default::default(),
                 // stroke: visuals.bg_stroke,
                 // stroke: ui.visuals().widgets.inactive.bg_stroke,
+                dash: None,
             });
 
             let center = self.marker_center(position_1d, &rail_rect);
@@ -369,8 +386,9 @@ impl<'a> Slider<'a> {
             ui.painter().add(epaint::CircleShape {
                 center,
                 radius: self.handle_radius(rect) + visuals.expansion,
-                fill: visuals.bg_fill,
+                fill: visuals.bg_fill.into(),
                 stroke: visuals.fg_stroke,
+                dash: None,
             });
         }
     }
@@ -429,9 +447,12 @@ impl<'a> Slider<'a> {
         }
     }
 
-    fn label_ui(&mut self, ui: &mut Ui) {
+    fn label_ui(&mut self, ui: &mut Ui, class: Option<&StyleClass>) {
         if !self.text.is_empty() {
-            let text_color = self.text_color.unwrap_or_else(|| ui.visuals().text_color());
+            let text_color = self
+                .text_color
+                .or_else(|| class.and_then(|class| class.text_color))
+                .unwrap_or_else(|| ui.visuals().text_color());
             let text = RichText::new(&self.text).color(text_color);
             ui.add(Label::new(text).wrap(false));
         }
@@ -465,14 +486,14 @@ impl<'a> Slider<'a> {
         right_value - left_value
     }
 
-    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+    fn add_contents(&mut self, ui: &mut Ui, class: Option<&StyleClass>) -> Response {
         let text_style = TextStyle::Button;
         let perpendicular = ui
             .fonts()
             .row_height(text_style)
             .at_least(ui.spacing().interact_size.y);
         let slider_response = self.allocate_slider_space(ui, perpendicular);
-        self.slider_ui(ui, &slider_response);
+        self.slider_ui(ui, &slider_response, class);
 
         if self.show_value {
             let position_range = self.position_range(&slider_response.rect);
@@ -480,7 +501,7 @@ impl<'a> Slider<'a> {
         }
 
         if !self.text.is_empty() {
-            self.label_ui(ui);
+            self.label_ui(ui, class);
         }
         slider_response
     }
@@ -489,10 +510,14 @@ impl<'a> Slider<'a> {
 impl<'a> Widget for Slider<'a> {
     fn ui(mut self, ui: &mut Ui) -> Response {
         let old_value = self.get_value();
+        let class = self
+            .class
+            .and_then(|class| ui.ctx().style_class_by_id(class));
+        let class = class.as_deref();
 
         let inner_response = match self.orientation {
-            SliderOrientation::Horizontal => ui.horizontal(|ui| self.add_contents(ui)),
-            SliderOrientation::Vertical => ui.vertical(|ui| self.add_contents(ui)),
+            SliderOrientation::Horizontal => ui.horizontal(|ui| self.add_contents(ui, class)),
+            SliderOrientation::Vertical => ui.vertical(|ui| self.add_contents(ui, class)),
         };
 
         let mut response = inner_response.inner | inner_response.response;
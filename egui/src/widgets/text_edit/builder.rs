@@ -4,7 +4,12 @@ use epaint::text::{cursor::*, Galley, LayoutJob};
 
 use crate::{output::OutputEvent, *};
 
-use super::{CCursorRange, CursorRange, TextEditOutput, TextEditState};
+use super::{
+    completion::{CompletionPopup, CompletionResponse},
+    gutter::FoldedText,
+    CCursorRange, CompletionProvider, CursorRange, Diagnostic, DiagnosticsProvider, Gutter,
+    TextEditOutput, TextEditState,
+};
 
 /// A text region that the user can edit the contents of.
 ///
@@ -55,6 +60,9 @@ pub struct TextEdit<'t> {
     text_style: Option<TextStyle>,
     text_color: Option<Color32>,
     layouter: Option<&'t mut dyn FnMut(&Ui, &str, f32) -> Arc<Galley>>,
+    diagnostics: Option<&'t mut dyn DiagnosticsProvider>,
+    completions: Option<&'t mut dyn CompletionProvider>,
+    gutter: Option<&'t mut Gutter>,
     password: bool,
     frame: bool,
     multiline: bool,
@@ -99,6 +107,9 @@ impl<'t> TextEdit<'t> {
             text_style: None,
             text_color: None,
             layouter: None,
+            diagnostics: None,
+            completions: None,
+            gutter: None,
             password: false,
             frame: true,
             multiline: true,
@@ -186,6 +197,76 @@ impl<'t> TextEdit<'t> {
         self
     }
 
+    /// Show squiggly underlines below problematic parts of the text,
+    /// e.g. spelling mistakes or compiler errors.
+    ///
+    /// Hovering an underlined range shows its message,
+    /// and right-clicking it shows a context menu with suggested replacements.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut my_text = String::new();
+    /// use egui::text_edit::Diagnostic;
+    /// let mut spell_checker = |text: &str| -> Vec<Diagnostic> {
+    ///     let mut diagnostics = vec![];
+    ///     if let Some(byte_index) = text.find("teh") {
+    ///         let start = text[..byte_index].chars().count();
+    ///         diagnostics.push(
+    ///             Diagnostic::error(start..start + 3, "Unknown word").suggestion("the"),
+    ///         );
+    ///     }
+    ///     diagnostics
+    /// };
+    /// ui.add(egui::TextEdit::multiline(&mut my_text).diagnostics(&mut spell_checker));
+    /// # });
+    /// ```
+    pub fn diagnostics(mut self, diagnostics: &'t mut dyn DiagnosticsProvider) -> Self {
+        self.diagnostics = Some(diagnostics);
+        self
+    }
+
+    /// Show a popup with suggestions for how to complete the word being typed.
+    ///
+    /// Use the arrow keys to pick a candidate, and enter or tab to accept it.
+    /// Escape closes the popup. The [`TextEdit`] keeps the keyboard focus throughout.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut my_code = String::new();
+    /// let keywords = ["break", "continue", "else", "false", "for", "if", "true", "while"];
+    /// let mut completions = |word: &str| -> Vec<String> {
+    ///     keywords
+    ///         .iter()
+    ///         .filter(|keyword| keyword.starts_with(word) && **keyword != word)
+    ///         .map(|keyword| keyword.to_string())
+    ///         .collect()
+    /// };
+    /// ui.add(egui::TextEdit::multiline(&mut my_code).completions(&mut completions));
+    /// # });
+    /// ```
+    pub fn completions(mut self, completions: &'t mut dyn CompletionProvider) -> Self {
+        self.completions = Some(completions);
+        self
+    }
+
+    /// Show a [`Gutter`] with line numbers, breakpoints and code folding to the left of the text.
+    ///
+    /// When some lines are folded, the [`TextEditOutput::galley`] and [`TextEditOutput::cursor_range`]
+    /// refer to the visible text, without the folded lines.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut my_code = String::new();
+    /// # let mut gutter = egui::text_edit::Gutter::default();
+    /// gutter.fold_regions = vec![1..4];
+    /// ui.add(egui::TextEdit::multiline(&mut my_code).code_editor().gutter(&mut gutter));
+    /// # });
+    /// ```
+    pub fn gutter(mut self, gutter: &'t mut Gutter) -> Self {
+        self.gutter = Some(gutter);
+        self
+    }
+
     /// Default is `true`. If set to `false` then you cannot interact with the text (neither edit or select it).
     ///
     /// Consider using [`Ui::add_enabled`] instead to also give the `TextEdit` a greyed out look.
@@ -258,18 +339,52 @@ impl<'t> TextEdit<'t> {
     /// }
     /// # });
     /// ```
-    pub fn show(self, ui: &mut Ui) -> TextEditOutput {
+    pub fn show(mut self, ui: &mut Ui) -> TextEditOutput {
         let is_mutable = self.text.is_mutable();
         let frame = self.frame;
         let interactive = self.interactive;
         let where_to_put_background = ui.painter().add(Shape::Noop);
+        let where_to_put_line_highlight = ui.painter().add(Shape::Noop);
+
+        let mut gutter = self.gutter.take();
+        let text_style = self
+            .text_style
+            .or(ui.style().override_text_style)
+            .unwrap_or_else(|| ui.style().body_text_style);
+        let num_lines = self.text.as_str().split('\n').count();
+        let gutter_width = gutter
+            .as_ref()
+            .map_or(0.0, |gutter| gutter.width(ui, text_style, num_lines));
 
         let margin = Vec2::new(4.0, 2.0);
-        let max_rect = ui.available_rect_before_wrap().shrink2(margin);
+        let mut max_rect = ui.available_rect_before_wrap().shrink2(margin);
+        max_rect.min.x += gutter_width;
         let mut content_ui = ui.child_ui(max_rect, *ui.layout());
-        let mut output = self.show_content(&mut content_ui);
+        let mut output = self.show_content(&mut content_ui, gutter.as_deref_mut());
         let id = output.response.id;
-        let frame_rect = output.response.rect.expand2(margin);
+        let mut frame_rect = output.response.rect.expand2(margin);
+        frame_rect.min.x -= gutter_width;
+
+        if let Some(gutter) = &mut gutter {
+            let text_rect = output.response.rect;
+            let gutter_rect = Rect::from_x_y_ranges(
+                text_rect.left() - gutter_width..=text_rect.left(),
+                text_rect.y_range(),
+            );
+            gutter.ui(
+                ui,
+                id,
+                gutter_rect,
+                text_style,
+                &output.galley,
+                output.text_draw_pos,
+                text_rect,
+                num_lines,
+                output.cursor_range,
+                where_to_put_line_highlight,
+            );
+        }
+
         ui.allocate_space(frame_rect.size());
         if interactive {
             output.response |= ui.interact(frame_rect, id, Sense::click());
@@ -279,7 +394,9 @@ impl<'t> TextEdit<'t> {
         }
 
         if frame {
-            let visuals = ui.style().interact(&output.response);
+            let visuals = ui
+                .style()
+                .interact_as(WidgetType::TextEdit, &output.response);
             let frame_rect = frame_rect.expand(visuals.expansion);
             let shape = if is_mutable {
                 if output.response.has_focus() {
@@ -287,15 +404,17 @@ impl<'t> TextEdit<'t> {
                         rect: frame_rect,
                         corner_radius: visuals.corner_radius,
                         // fill: ui.visuals().selection.bg_fill,
-                        fill: ui.visuals().extreme_bg_color,
+                        fill: ui.visuals().extreme_bg_color.into(),
                         stroke: ui.visuals().selection.stroke,
+                        dash: None,
                     }
                 } else {
                     epaint::RectShape {
                         rect: frame_rect,
                         corner_radius: visuals.corner_radius,
-                        fill: ui.visuals().extreme_bg_color,
+                        fill: ui.visuals().extreme_bg_color.into(),
                         stroke: visuals.bg_stroke, // TODO: we want to show something here, or a text-edit field doesn't "pop".
+                        dash: None,
                     }
                 }
             } else {
@@ -305,8 +424,9 @@ impl<'t> TextEdit<'t> {
                     corner_radius: visuals.corner_radius,
                     // fill: ui.visuals().extreme_bg_color,
                     // fill: visuals.bg_fill,
-                    fill: Color32::TRANSPARENT,
+                    fill: Color32::TRANSPARENT.into(),
                     stroke: visuals.bg_stroke, // TODO: we want to show something here, or a text-edit field doesn't "pop".
+                    dash: None,
                 }
             };
 
@@ -316,7 +436,7 @@ impl<'t> TextEdit<'t> {
         output
     }
 
-    fn show_content(self, ui: &mut Ui) -> TextEditOutput {
+    fn show_content(self, ui: &mut Ui, gutter: Option<&mut Gutter>) -> TextEditOutput {
         let TextEdit {
             text,
             hint_text,
@@ -325,6 +445,9 @@ impl<'t> TextEdit<'t> {
             text_style,
             text_color,
             layouter,
+            mut diagnostics,
+            mut completions,
+            gutter: _,
             password,
             frame: _,
             multiline,
@@ -335,6 +458,22 @@ impl<'t> TextEdit<'t> {
             cursor_at_end,
         } = self;
 
+        // The gutter needs the full text (including folded lines) to move its breakpoints and folds along with the edits:
+        let full_text = text;
+        let prev_full_text = gutter.is_some().then(|| full_text.as_str().to_owned());
+        let hidden_lines = gutter
+            .as_ref()
+            .map(|gutter| gutter.hidden_lines())
+            .unwrap_or_default();
+
+        let mut folded_text;
+        let text: &mut dyn TextBuffer = if hidden_lines.is_empty() {
+            &mut *full_text
+        } else {
+            folded_text = FoldedText::new(&mut *full_text, &hidden_lines);
+            &mut folded_text
+        };
+
         let text_color = text_color
             .or(ui.visuals().override_text_color)
             // .unwrap_or_else(|| ui.style().interact(&response).text_color()); // too bright
@@ -403,6 +542,15 @@ impl<'t> TextEdit<'t> {
         };
         let mut response = ui.interact(rect, id, sense);
         let text_clip_rect = rect;
+
+        if response.lost_focus() && state.completion_word_start.is_some() {
+            // Clicking the completion popup should not take away our focus:
+            let popup_layer = LayerId::new(Order::Foreground, id.with("completion"));
+            let pointer_pos = ui.input().pointer.interact_pos();
+            if pointer_pos.and_then(|pos| ui.ctx().layer_id_at(pos)) == Some(popup_layer) {
+                ui.memory().request_focus(id);
+            }
+        }
         let painter = ui.painter_at(text_clip_rect);
 
         if interactive {
@@ -470,6 +618,7 @@ impl<'t> TextEdit<'t> {
         }
 
         let mut cursor_range = None;
+        let mut completion_popup = None;
         let prev_cursor_range = state.cursor_range(&*galley);
         if ui.memory().has_focus(id) && interactive {
             ui.memory().lock_focus(id, lock_focus);
@@ -480,12 +629,20 @@ impl<'t> TextEdit<'t> {
                 CursorRange::default()
             };
 
+            if let (Some(provider), Some(prev_cursor_range)) =
+                (completions.as_mut(), &prev_cursor_range)
+            {
+                completion_popup =
+                    completion_popup_at(&mut state, *provider, text.as_str(), prev_cursor_range);
+            }
+
             let (changed, new_cursor_range) = events(
                 ui,
                 &mut state,
                 text,
                 &mut galley,
                 layouter,
+                &mut completion_popup,
                 id,
                 wrap_width,
                 multiline,
@@ -497,6 +654,23 @@ impl<'t> TextEdit<'t> {
                 response.mark_changed();
             }
             cursor_range = Some(new_cursor_range);
+
+            completion_popup = None;
+            if let Some(provider) = completions.as_mut() {
+                if text.is_mutable() {
+                    completion_popup = completion_popup_at(
+                        &mut state,
+                        *provider,
+                        text.as_str(),
+                        &new_cursor_range,
+                    );
+                }
+            }
+
+            // While the completion popup is open it uses tab and escape:
+            let popup_is_open = completion_popup.is_some();
+            ui.memory().lock_focus(id, lock_focus || popup_is_open);
+            ui.memory().lock_focus_on_escape(id, popup_is_open);
         }
 
         let mut text_draw_pos = response.rect.min;
@@ -527,9 +701,66 @@ impl<'t> TextEdit<'t> {
             text_draw_pos -= vec2(offset_x, 0.0);
         }
 
+        let mut diagnostics_list = diagnostics
+            .as_mut()
+            .map(|provider| provider.diagnostics(text.as_str()))
+            .unwrap_or_default();
+
+        if interactive && !diagnostics_list.is_empty() {
+            let replacement =
+                diagnostics_interaction(ui, &response, text_draw_pos, &galley, &diagnostics_list);
+            if let Some((char_range, replacement)) = replacement {
+                if text.is_mutable() {
+                    text.delete_char_range(char_range.clone());
+                    let num_chars = text.insert_text(&replacement, char_range.start);
+                    let ccursor_range =
+                        CCursorRange::one(CCursor::new(char_range.start + num_chars));
+                    galley = layouter(ui, text.as_ref(), wrap_width);
+                    let new_cursor_range = CursorRange {
+                        primary: galley.from_ccursor(ccursor_range.primary),
+                        secondary: galley.from_ccursor(ccursor_range.secondary),
+                    };
+                    state.set_cursor_range(Some(new_cursor_range));
+                    if cursor_range.is_some() {
+                        cursor_range = Some(new_cursor_range);
+                    }
+                    response.mark_changed();
+
+                    if let Some(provider) = diagnostics.as_mut() {
+                        diagnostics_list = provider.diagnostics(text.as_str());
+                    }
+                }
+            }
+        }
+
+        if let (Some(mut popup), Some(current_cursor_range)) = (completion_popup, cursor_range) {
+            let caret_rect = galley
+                .pos_from_cursor(&current_cursor_range.primary)
+                .translate(text_draw_pos.to_vec2());
+            if popup.show(ui.ctx(), id.with("completion"), caret_rect.left_bottom()) {
+                // A candidate was clicked:
+                let ccursor_range = popup.accept(text);
+                galley = layouter(ui, text.as_ref(), wrap_width);
+                let new_cursor_range = CursorRange {
+                    primary: galley.from_ccursor(ccursor_range.primary),
+                    secondary: galley.from_ccursor(ccursor_range.secondary),
+                };
+                state.set_cursor_range(Some(new_cursor_range));
+                state.completion_word_start = None;
+                cursor_range = Some(new_cursor_range);
+                response.mark_changed();
+            } else {
+                state.completion_selected = popup.selected;
+            }
+        }
+
         if ui.is_rect_visible(rect) {
             painter.galley(text_draw_pos, galley.clone());
 
+            for diagnostic in &diagnostics_list {
+                paint_diagnostic(&painter, text_draw_pos, &galley, diagnostic);
+            }
+
             if text.as_ref().is_empty() && !hint_text.is_empty() {
                 let hint_text_color = ui.visuals().weak_text_color();
                 let galley = if multiline {
@@ -607,6 +838,10 @@ impl<'t> TextEdit<'t> {
             });
         }
 
+        if let (Some(gutter), Some(prev_full_text)) = (gutter, prev_full_text) {
+            gutter.on_text_changed(&prev_full_text, full_text.as_str());
+        }
+
         TextEditOutput {
             response,
             galley,
@@ -642,6 +877,7 @@ fn events(
     text: &mut dyn TextBuffer,
     galley: &mut Arc<Galley>,
     layouter: &mut dyn FnMut(&Ui, &str, f32) -> Arc<Galley>,
+    completion: &mut Option<CompletionPopup>,
     id: Id,
     wrap_width: f32,
     multiline: bool,
@@ -664,9 +900,27 @@ fn events(
     };
 
     let mut any_change = false;
+    let mut typed = false;
 
     for event in &ui.input().events {
+        let completion_response = completion
+            .as_mut()
+            .map_or(CompletionResponse::Ignored, |popup| popup.on_event(event));
+
         let did_mutate_text = match event {
+            // The completion popup gets the first go at the keys it uses:
+            _ if completion_response == CompletionResponse::Handled => None,
+            _ if completion_response == CompletionResponse::Close => {
+                *completion = None;
+                state.completion_word_start = None;
+                None
+            }
+            _ if completion_response == CompletionResponse::Accept => {
+                typed = false;
+                state.completion_word_start = None;
+                completion.take().map(|popup| popup.accept(text))
+            }
+
             Event::Copy => {
                 if cursor_range.is_empty() {
                     copy_if_not_password(ui, text.as_ref().to_owned());
@@ -689,6 +943,7 @@ fn events(
                 if !text_to_insert.is_empty() && text_to_insert != "\n" && text_to_insert != "\r" {
                     let mut ccursor = delete_selected(text, &cursor_range);
                     insert_text(&mut ccursor, text, text_to_insert);
+                    typed = true;
                     Some(CCursorRange::one(ccursor))
                 } else {
                     None
@@ -802,6 +1057,14 @@ fn events(
 
     state.set_cursor_range(Some(cursor_range));
 
+    if typed {
+        // Start suggesting completions for the word being typed:
+        state.completion_word_start = cursor_range
+            .single()
+            .and_then(|cursor| word_before_cursor(text.as_ref(), cursor.ccursor))
+            .map(|word_range| word_range.start);
+    }
+
     state.undoer.lock().feed_state(
         ui.input().time,
         &(cursor_range.as_ccursor_range(), text.as_ref().to_owned()),
@@ -854,6 +1117,145 @@ fn paint_cursor_selection(
     }
 }
 
+/// The completion popup for the word to the left of the cursor, if any.
+fn completion_popup_at(
+    state: &mut TextEditState,
+    provider: &mut dyn CompletionProvider,
+    text: &str,
+    cursor_range: &CursorRange,
+) -> Option<CompletionPopup> {
+    let word_range = cursor_range
+        .single()
+        .and_then(|cursor| word_before_cursor(text, cursor.ccursor));
+
+    if word_range.as_ref().map(|word_range| word_range.start) != state.completion_word_start {
+        // The cursor has left the word we were completing.
+        state.completion_word_start = None;
+        state.completion_selected = 0;
+        return None;
+    }
+
+    let word_range = word_range?;
+    let word: String = text
+        .chars()
+        .skip(word_range.start)
+        .take(word_range.len())
+        .collect();
+    let candidates = provider.completions(text, &word);
+    if candidates.is_empty() {
+        return None;
+    }
+
+    let selected = state.completion_selected.min(candidates.len() - 1);
+    Some(CompletionPopup {
+        word_range,
+        candidates,
+        selected,
+    })
+}
+
+/// The screen rectangles covered by the given character range, one per row.
+fn char_range_rects(pos: Pos2, galley: &Galley, char_range: &std::ops::Range<usize>) -> Vec<Rect> {
+    if char_range.start >= char_range.end {
+        return vec![];
+    }
+
+    let min = galley.from_ccursor(CCursor::new(char_range.start)).rcursor;
+    let max = galley.from_ccursor(CCursor::new(char_range.end)).rcursor;
+
+    (min.row..=max.row)
+        .filter_map(|ri| {
+            let row = &galley.rows[ri];
+            let left = if ri == min.row {
+                row.x_offset(min.column)
+            } else {
+                row.rect.left()
+            };
+            let right = if ri == max.row {
+                row.x_offset(max.column)
+            } else {
+                row.rect.right()
+            };
+            (left < right).then(|| {
+                Rect::from_min_max(
+                    pos + vec2(left, row.min_y()),
+                    pos + vec2(right, row.max_y()),
+                )
+            })
+        })
+        .collect()
+}
+
+fn paint_diagnostic(painter: &Painter, pos: Pos2, galley: &Galley, diagnostic: &Diagnostic) {
+    let stroke = Stroke::new(1.0, diagnostic.underline_color());
+    let amplitude = 1.0;
+    let wavelength = 4.0;
+    for rect in char_range_rects(pos, galley, &diagnostic.char_range) {
+        let y = rect.bottom() - amplitude;
+        painter.add(Shape::wavy_line(
+            [pos2(rect.left(), y), pos2(rect.right(), y)],
+            stroke,
+            amplitude,
+            wavelength,
+        ));
+    }
+}
+
+/// Show the message of a hovered diagnostic, and the context menu for suggested replacements.
+///
+/// Returns the character range to replace, and what to replace it with,
+/// if the user picked one of the suggestions.
+fn diagnostics_interaction(
+    ui: &Ui,
+    response: &Response,
+    pos: Pos2,
+    galley: &Galley,
+    diagnostics: &[Diagnostic],
+) -> Option<(std::ops::Range<usize>, String)> {
+    let hover_pos = response.hover_pos();
+    let mut replacement = None;
+
+    for (i, diagnostic) in diagnostics.iter().enumerate() {
+        let rects = char_range_rects(pos, galley, &diagnostic.char_range);
+        if rects.is_empty() {
+            continue;
+        }
+
+        let hovered = hover_pos.map_or(false, |hover_pos| {
+            rects.iter().any(|rect| rect.contains(hover_pos))
+        });
+        let rect = rects.iter().fold(Rect::NOTHING, |a, b| a.union(*b));
+
+        let diagnostic_response = ui.ctx().interact_with_hovered(
+            ui.layer_id(),
+            response.id.with(("diagnostic", i)),
+            rect,
+            Sense::hover(),
+            ui.is_enabled(),
+            hovered,
+        );
+
+        let diagnostic_response = if diagnostic.message.is_empty() {
+            diagnostic_response
+        } else {
+            diagnostic_response.on_hover_text(diagnostic.message.as_str())
+        };
+
+        if !diagnostic.suggestions.is_empty() {
+            diagnostic_response.context_menu(|ui| {
+                for suggestion in &diagnostic.suggestions {
+                    if ui.button(suggestion.as_str()).clicked() {
+                        replacement = Some((diagnostic.char_range.clone(), suggestion.clone()));
+                        ui.close_menu();
+                    }
+                }
+            });
+        }
+    }
+
+    replacement
+}
+
 fn paint_cursor_end(
     ui: &mut Ui,
     row_height: f32,
@@ -1166,6 +1568,20 @@ fn select_word_at(text: &str, ccursor: CCursor) -> CCursorRange {
     }
 }
 
+/// The character range of the (partial) word directly to the left of the cursor.
+///
+/// Unlike word navigation, this counts all alphanumeric characters (not only ASCII) as part of a word.
+fn word_before_cursor(text: &str, ccursor: CCursor) -> Option<std::ops::Range<usize>> {
+    let chars_before: Vec<char> = text.chars().take(ccursor.index).collect();
+    let word_len = chars_before
+        .iter()
+        .rev()
+        .take_while(|c| c.is_alphanumeric() || **c == '_')
+        .count();
+    let end = chars_before.len();
+    (word_len > 0).then(|| end - word_len..end)
+}
+
 fn ccursor_next_word(text: &str, ccursor: CCursor) -> CCursor {
     CCursor {
         index: next_word_boundary_char_index(text.chars(), ccursor.index),
@@ -1247,3 +1663,89 @@ fn decrease_identation(ccursor: &mut CCursor, text: &mut dyn TextBuffer) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn galley(text: &str) -> Arc<Galley> {
+        let fonts = epaint::text::Fonts::new(1.0, FontDefinitions::default());
+        fonts.layout_no_wrap(text.to_owned(), TextStyle::Monospace, Color32::WHITE)
+    }
+
+    #[test]
+    fn empty_char_range_has_no_rects() {
+        let galley = galley("hello");
+        assert!(char_range_rects(Pos2::ZERO, &galley, &(2..2)).is_empty());
+    }
+
+    #[test]
+    fn char_range_rects_on_one_row() {
+        let galley = galley("hello world");
+        let rects = char_range_rects(pos2(10.0, 20.0), &galley, &(6..11));
+        assert_eq!(rects.len(), 1);
+
+        let row = &galley.rows[0];
+        let rect = rects[0];
+        assert_eq!(rect.left(), 10.0 + row.x_offset(6));
+        assert_eq!(rect.right(), 10.0 + row.x_offset(11));
+        assert_eq!(rect.top(), 20.0 + row.min_y());
+        assert_eq!(rect.bottom(), 20.0 + row.max_y());
+    }
+
+    #[test]
+    fn char_range_rects_spanning_rows() {
+        let galley = galley("first\nsecond\nthird");
+        // From the 'r' in "first" to the 'h' in "third":
+        let rects = char_range_rects(Pos2::ZERO, &galley, &(2..15));
+        assert_eq!(rects.len(), 3);
+
+        let rows = &galley.rows;
+        assert_eq!(rects[0].left(), rows[0].x_offset(2));
+        assert_eq!(rects[0].right(), rows[0].rect.right());
+        assert_eq!(rects[1].left(), rows[1].rect.left());
+        assert_eq!(rects[1].right(), rows[1].rect.right());
+        assert_eq!(rects[2].left(), rows[2].rect.left());
+        assert_eq!(rects[2].right(), rows[2].x_offset(2));
+
+        // One rect per row, top to bottom, without overlap:
+        assert!(rects[0].bottom() <= rects[1].top());
+        assert!(rects[1].bottom() <= rects[2].top());
+    }
+
+    #[test]
+    fn char_range_ending_at_start_of_row_skips_that_row() {
+        let galley = galley("first\nsecond");
+        // "rst\n" ends at column 0 of the second row:
+        let rects = char_range_rects(Pos2::ZERO, &galley, &(2..6));
+        assert_eq!(rects.len(), 1);
+        assert_eq!(rects[0].right(), galley.rows[0].rect.right());
+    }
+
+    fn word_before(text: &str, index: usize) -> Option<std::ops::Range<usize>> {
+        word_before_cursor(text, CCursor::new(index))
+    }
+
+    #[test]
+    fn word_before_cursor_ascii() {
+        assert_eq!(word_before("let foo_bar", 11), Some(4..11));
+        assert_eq!(word_before("let foo_bar", 6), Some(4..6));
+        assert_eq!(word_before("foo.bar", 4), None);
+        assert_eq!(word_before("foo ", 4), None);
+    }
+
+    #[test]
+    fn word_before_cursor_at_start() {
+        assert_eq!(word_before("", 0), None);
+        assert_eq!(word_before("foo", 0), None);
+    }
+
+    #[test]
+    fn word_before_cursor_unicode() {
+        // Character offsets, not byte offsets:
+        assert_eq!(word_before("ø grüß", 6), Some(2..6));
+        assert_eq!(word_before("日本語 テキスト", 8), Some(4..8));
+        assert_eq!(word_before("🙂 abc", 5), Some(2..5));
+        assert_eq!(word_before("abc🙂", 4), None);
+    }
+}
@@ -0,0 +1,227 @@
+use std::ops::Range;
+
+use epaint::text::cursor::CCursor;
+
+use crate::*;
+
+use super::{CCursorRange, TextBuffer};
+
+/// Something that can suggest how to complete the word being typed in a [`crate::TextEdit`].
+///
+/// Use with [`crate::TextEdit::completions`].
+///
+/// Any `FnMut(&str) -> Vec<String>` implements this trait.
+/// It is given the partial word to the left of the cursor.
+pub trait CompletionProvider {
+    /// Return the candidates for `word`, the partial word to the left of the cursor.
+    ///
+    /// `text` is the full contents of the [`crate::TextEdit`].
+    ///
+    /// This will be called every frame the completion popup is open,
+    /// so consider caching the results if it is expensive.
+    fn completions(&mut self, text: &str, word: &str) -> Vec<String>;
+}
+
+impl<F> CompletionProvider for F
+where
+    F: FnMut(&str) -> Vec<String>,
+{
+    fn completions(&mut self, _text: &str, word: &str) -> Vec<String> {
+        self(word)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CompletionResponse {
+    /// The event was not for us.
+    Ignored,
+    /// The event was used by the popup.
+    Handled,
+    /// The selected candidate should replace the word.
+    Accept,
+    /// The user dismissed the popup.
+    Close,
+}
+
+/// An open completion popup.
+pub(crate) struct CompletionPopup {
+    /// The characters that will be replaced by the accepted candidate.
+    pub word_range: Range<usize>,
+    pub candidates: Vec<String>,
+    pub selected: usize,
+}
+
+impl CompletionPopup {
+    /// Arrow keys select, enter and tab accepts, and escape closes the popup.
+    pub fn on_event(&mut self, event: &Event) -> CompletionResponse {
+        let num_candidates = self.candidates.len();
+        match event {
+            Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+            } if modifiers.is_none() => match key {
+                Key::ArrowDown => {
+                    self.selected = (self.selected + 1) % num_candidates;
+                    CompletionResponse::Handled
+                }
+                Key::ArrowUp => {
+                    self.selected = (self.selected + num_candidates - 1) % num_candidates;
+                    CompletionResponse::Handled
+                }
+                Key::Enter | Key::Tab => CompletionResponse::Accept,
+                Key::Escape => CompletionResponse::Close,
+                _ => CompletionResponse::Ignored,
+            },
+            _ => CompletionResponse::Ignored,
+        }
+    }
+
+    /// Replace the word with the selected candidate.
+    ///
+    /// Returns where the cursor should go.
+    pub fn accept(&self, text: &mut dyn TextBuffer) -> CCursorRange {
+        text.delete_char_range(self.word_range.clone());
+        let num_chars = text.insert_text(&self.candidates[self.selected], self.word_range.start);
+        CCursorRange::one(CCursor::new(self.word_range.start + num_chars))
+    }
+
+    /// Show the popup with its top-left corner at `pos`.
+    ///
+    /// Returns `true` if a candidate was clicked.
+    pub fn show(&mut self, ctx: &CtxRef, id: Id, pos: Pos2) -> bool {
+        let scroll_to_selected =
+            ctx.input().key_pressed(Key::ArrowDown) || ctx.input().key_pressed(Key::ArrowUp);
+
+        Area::new(id)
+            .order(Order::Foreground)
+            .fixed_pos(pos)
+            .show(ctx, |ui| {
+                Frame::popup(ui.style())
+                    .show(ui, |ui| {
+                        ScrollArea::vertical()
+                            .max_height(ui.spacing().combo_height)
+                            .show(ui, |ui| {
+                                let mut clicked = false;
+                                for (i, candidate) in self.candidates.iter().enumerate() {
+                                    let response =
+                                        ui.selectable_label(i == self.selected, candidate.as_str());
+                                    if i == self.selected && scroll_to_selected {
+                                        response.scroll_to_me(Align::Center);
+                                    }
+                                    if response.clicked() {
+                                        self.selected = i;
+                                        clicked = true;
+                                    }
+                                }
+                                clicked
+                            })
+                            .inner
+                    })
+                    .inner
+            })
+            .inner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn popup() -> CompletionPopup {
+        CompletionPopup {
+            word_range: 4..6,
+            candidates: vec!["foo".to_owned(), "food".to_owned(), "fool".to_owned()],
+            selected: 0,
+        }
+    }
+
+    fn key(key: Key) -> Event {
+        Event::Key {
+            key,
+            pressed: true,
+            modifiers: Modifiers::default(),
+        }
+    }
+
+    #[test]
+    fn arrows_select_and_wrap_around() {
+        let mut popup = popup();
+        assert_eq!(
+            popup.on_event(&key(Key::ArrowDown)),
+            CompletionResponse::Handled
+        );
+        assert_eq!(popup.selected, 1);
+        assert_eq!(
+            popup.on_event(&key(Key::ArrowDown)),
+            CompletionResponse::Handled
+        );
+        assert_eq!(
+            popup.on_event(&key(Key::ArrowDown)),
+            CompletionResponse::Handled
+        );
+        assert_eq!(popup.selected, 0);
+        assert_eq!(
+            popup.on_event(&key(Key::ArrowUp)),
+            CompletionResponse::Handled
+        );
+        assert_eq!(popup.selected, 2);
+    }
+
+    #[test]
+    fn enter_and_tab_accept_escape_closes() {
+        let mut popup = popup();
+        assert_eq!(popup.on_event(&key(Key::Enter)), CompletionResponse::Accept);
+        assert_eq!(popup.on_event(&key(Key::Tab)), CompletionResponse::Accept);
+        assert_eq!(popup.on_event(&key(Key::Escape)), CompletionResponse::Close);
+        assert_eq!(popup.selected, 0);
+    }
+
+    #[test]
+    fn other_events_are_ignored() {
+        let mut popup = popup();
+        let ignored = [
+            key(Key::A),
+            key(Key::ArrowLeft),
+            Event::Key {
+                key: Key::ArrowDown,
+                pressed: false,
+                modifiers: Modifiers::default(),
+            },
+            Event::Key {
+                key: Key::ArrowDown,
+                pressed: true,
+                modifiers: Modifiers {
+                    shift: true,
+                    ..Default::default()
+                },
+            },
+            Event::Text("x".to_owned()),
+        ];
+        for event in &ignored {
+            assert_eq!(popup.on_event(event), CompletionResponse::Ignored);
+        }
+        assert_eq!(popup.selected, 0);
+    }
+
+    #[test]
+    fn accept_replaces_the_word() {
+        let mut popup = popup();
+        popup.selected = 2;
+        let mut text = "let fo = 1;".to_owned();
+        let cursor = popup.accept(&mut text);
+        assert_eq!(text, "let fool = 1;");
+        assert_eq!(cursor, CCursorRange::one(CCursor::new(8)));
+    }
+
+    #[test]
+    fn accept_with_unicode_before_the_word() {
+        let popup = popup();
+        let mut text = "日本語 fo".to_owned();
+        let cursor = popup.accept(&mut text);
+        assert_eq!(text, "日本語 foo");
+        assert_eq!(cursor, CCursorRange::one(CCursor::new(7)));
+    }
+}
@@ -0,0 +1,112 @@
+use std::ops::Range;
+
+use crate::Color32;
+
+/// How serious a [`Diagnostic`] is. Decides the color of its squiggly underline.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Severity {
+    Hint,
+    Info,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// The default color of the squiggly underline.
+    pub fn color(self) -> Color32 {
+        match self {
+            Self::Hint => Color32::GRAY,
+            Self::Info => Color32::LIGHT_BLUE,
+            Self::Warning => Color32::GOLD,
+            Self::Error => Color32::RED,
+        }
+    }
+}
+
+/// A problem with some range of the text in a [`crate::TextEdit`],
+/// e.g. a misspelled word or a compiler error.
+///
+/// It is shown as a squiggly underline below the text.
+/// Hovering it shows the [`Self::message`], and right-clicking it
+/// opens a context menu with the [`Self::suggestions`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Diagnostic {
+    /// The character range (NOT byte range!) this diagnostic applies to.
+    pub char_range: Range<usize>,
+
+    pub severity: Severity,
+
+    /// Shown in a tooltip when hovering the underlined text.
+    pub message: String,
+
+    /// Replacements for the text in [`Self::char_range`], offered in the context menu.
+    pub suggestions: Vec<String>,
+
+    /// Override the color given by the [`Self::severity`].
+    pub color: Option<Color32>,
+}
+
+impl Diagnostic {
+    pub fn new(char_range: Range<usize>, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            char_range,
+            severity,
+            message: message.into(),
+            suggestions: Default::default(),
+            color: None,
+        }
+    }
+
+    /// Shorthand for an [`Severity::Error`].
+    pub fn error(char_range: Range<usize>, message: impl Into<String>) -> Self {
+        Self::new(char_range, Severity::Error, message)
+    }
+
+    /// Shorthand for an [`Severity::Warning`].
+    pub fn warning(char_range: Range<usize>, message: impl Into<String>) -> Self {
+        Self::new(char_range, Severity::Warning, message)
+    }
+
+    /// Add a replacement that will be offered in the context menu.
+    pub fn suggestion(mut self, suggestion: impl Into<String>) -> Self {
+        self.suggestions.push(suggestion.into());
+        self
+    }
+
+    /// Override the color of the underline.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+
+    /// The color of the squiggly underline.
+    pub fn underline_color(&self) -> Color32 {
+        self.color.unwrap_or_else(|| self.severity.color())
+    }
+}
+
+/// Something that can find problems in the text of a [`crate::TextEdit`],
+/// e.g. a spell checker or a compiler.
+///
+/// Use with [`crate::TextEdit::diagnostics`].
+///
+/// Any `FnMut(&str) -> Vec<Diagnostic>` implements this trait.
+pub trait DiagnosticsProvider {
+    /// Return all the problems in the given text.
+    ///
+    /// This will be called at least once per frame,
+    /// so it is strongly suggested that you cache the results
+    /// so as not to waste CPU checking the same text every frame.
+    fn diagnostics(&mut self, text: &str) -> Vec<Diagnostic>;
+}
+
+impl<F> DiagnosticsProvider for F
+where
+    F: FnMut(&str) -> Vec<Diagnostic>,
+{
+    fn diagnostics(&mut self, text: &str) -> Vec<Diagnostic> {
+        self(text)
+    }
+}
@@ -0,0 +1,618 @@
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use crate::*;
+
+use super::{CursorRange, TextBuffer};
+
+/// Line numbers, breakpoints and code folding to the left of a [`crate::TextEdit`].
+///
+/// Use with [`crate::TextEdit::gutter`]. You need to store this between frames.
+///
+/// All line numbers are zero-based, and count the lines of the full text (including folded lines).
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Gutter {
+    /// Show the number of each line. Default: `true`.
+    pub line_numbers: bool,
+
+    /// Show a column where breakpoints can be toggled by clicking. Default: `false`.
+    pub show_breakpoints: bool,
+
+    /// The lines that have a breakpoint.
+    pub breakpoints: BTreeSet<usize>,
+
+    /// Ranges of lines that can be folded.
+    ///
+    /// The first line of a region stays visible when it is folded,
+    /// and gets a marker in the gutter for folding and unfolding it.
+    pub fold_regions: Vec<Range<usize>>,
+
+    /// The first lines of the regions in [`Self::fold_regions`] that are folded.
+    pub folded: BTreeSet<usize>,
+
+    /// Paint a background behind the line with the text cursor. Default: `true`.
+    pub highlight_current_line: bool,
+}
+
+impl Default for Gutter {
+    fn default() -> Self {
+        Self {
+            line_numbers: true,
+            show_breakpoints: false,
+            breakpoints: Default::default(),
+            fold_regions: Default::default(),
+            folded: Default::default(),
+            highlight_current_line: true,
+        }
+    }
+}
+
+impl Gutter {
+    /// Fold or unfold the region starting on this line.
+    pub fn toggle_fold(&mut self, line: usize) {
+        if !self.folded.remove(&line) {
+            self.folded.insert(line);
+        }
+    }
+
+    /// Add or remove a breakpoint on this line.
+    pub fn toggle_breakpoint(&mut self, line: usize) {
+        if !self.breakpoints.remove(&line) {
+            self.breakpoints.insert(line);
+        }
+    }
+
+    /// Move the breakpoints, folds and fold regions along with their lines
+    /// when the text changes from `old_text` to `new_text`.
+    ///
+    /// Breakpoints and folds on deleted lines are removed.
+    /// [`crate::TextEdit`] calls this for its own edits,
+    /// so you only need to call it when you change the text yourself.
+    pub fn on_text_changed(&mut self, old_text: &str, new_text: &str) {
+        let edit = match LineEdit::new(old_text, new_text) {
+            Some(edit) => edit,
+            None => return,
+        };
+        self.breakpoints = self
+            .breakpoints
+            .iter()
+            .filter_map(|&line| edit.line(line))
+            .collect();
+        self.folded = self
+            .folded
+            .iter()
+            .filter_map(|&line| edit.line(line))
+            .collect();
+        self.fold_regions = self
+            .fold_regions
+            .iter()
+            .filter_map(|region| Some(edit.line(region.start)?..edit.end(region.end)))
+            .collect();
+    }
+
+    fn is_fold_start(&self, line: usize) -> bool {
+        self.fold_regions
+            .iter()
+            .any(|region| region.start == line && region.len() > 1)
+    }
+
+    /// The lines that are currently hidden by folding, sorted and non-overlapping.
+    pub fn hidden_lines(&self) -> Vec<Range<usize>> {
+        let mut hidden: Vec<Range<usize>> = self
+            .fold_regions
+            .iter()
+            .filter(|region| region.len() > 1 && self.folded.contains(&region.start))
+            .map(|region| region.start + 1..region.end)
+            .collect();
+        hidden.sort_by_key(|range| range.start);
+
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(hidden.len());
+        for range in hidden {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+
+    fn num_digits(num_lines: usize) -> usize {
+        num_lines.max(1).to_string().len().max(2)
+    }
+
+    /// How wide the gutter will be.
+    pub(crate) fn width(&self, ui: &Ui, text_style: TextStyle, num_lines: usize) -> f32 {
+        let row_height = ui.fonts().row_height(text_style);
+        let mut width = 0.0;
+        if self.show_breakpoints {
+            width += row_height;
+        }
+        if self.line_numbers {
+            let digit_width = ui.fonts().glyph_width(text_style, '0');
+            width += Self::num_digits(num_lines) as f32 * digit_width;
+        }
+        if !self.fold_regions.is_empty() {
+            width += row_height;
+        }
+        width + ui.spacing().item_spacing.x
+    }
+
+    /// Paint the gutter and handle clicks on it.
+    ///
+    /// `gutter_rect` is the area to the left of the text,
+    /// and `line_highlight_idx` is where to put the current-line highlight (behind the text).
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn ui(
+        &mut self,
+        ui: &mut Ui,
+        id: Id,
+        gutter_rect: Rect,
+        text_style: TextStyle,
+        galley: &Galley,
+        text_draw_pos: Pos2,
+        text_rect: Rect,
+        num_lines: usize,
+        cursor_range: Option<CursorRange>,
+        line_highlight_idx: layers::ShapeIdx,
+    ) {
+        let row_height = ui.fonts().row_height(text_style);
+        let hidden_lines = self.hidden_lines();
+        let visible_lines = visible_lines(num_lines, &hidden_lines);
+        let current_paragraph =
+            cursor_range.map(|cursor_range| cursor_range.primary.pcursor.paragraph);
+
+        let breakpoint_x = gutter_rect.left()..=gutter_rect.left() + row_height;
+        let fold_x = gutter_rect.right() - ui.spacing().item_spacing.x - row_height
+            ..=gutter_rect.right() - ui.spacing().item_spacing.x;
+
+        let response = ui.interact(gutter_rect, id.with("gutter"), Sense::click());
+        let clicked_pos = if response.clicked() {
+            response.interact_pointer_pos()
+        } else {
+            None
+        };
+
+        let painter = ui.painter_at(gutter_rect);
+        let weak_color = ui.visuals().weak_text_color();
+        let strong_color = ui.visuals().strong_text_color();
+
+        let mut line_highlight = vec![];
+        let mut paragraph = 0;
+        let mut first_row_of_paragraph = true;
+        for row in &galley.rows {
+            let line = visible_lines.get(paragraph).copied().unwrap_or(num_lines);
+            let y_range = text_draw_pos.y + row.min_y()..=text_draw_pos.y + row.max_y();
+            let is_current = current_paragraph == Some(paragraph);
+
+            if is_current && self.highlight_current_line {
+                line_highlight.push(Shape::rect_filled(
+                    Rect::from_x_y_ranges(gutter_rect.left()..=text_rect.right(), y_range.clone()),
+                    0.0,
+                    ui.visuals().faint_bg_color,
+                ));
+            }
+
+            if first_row_of_paragraph {
+                if let Some(pos) = clicked_pos {
+                    if y_range.contains(&pos.y) {
+                        if self.show_breakpoints && breakpoint_x.contains(&pos.x) {
+                            self.toggle_breakpoint(line);
+                        } else if fold_x.contains(&pos.x) && self.is_fold_start(line) {
+                            self.toggle_fold(line);
+                        }
+                    }
+                }
+
+                if self.show_breakpoints && self.breakpoints.contains(&line) {
+                    let center = pos2(
+                        (breakpoint_x.start() + breakpoint_x.end()) / 2.0,
+                        (y_range.start() + y_range.end()) / 2.0,
+                    );
+                    painter.circle_filled(center, 0.3 * row_height, Color32::RED);
+                }
+
+                if self.line_numbers {
+                    let color = if is_current { strong_color } else { weak_color };
+                    let right = if self.fold_regions.is_empty() {
+                        gutter_rect.right() - ui.spacing().item_spacing.x
+                    } else {
+                        *fold_x.start()
+                    };
+                    painter.text(
+                        pos2(right, *y_range.start()),
+                        Align2::RIGHT_TOP,
+                        line + 1,
+                        text_style,
+                        color,
+                    );
+                }
+
+                if self.is_fold_start(line) {
+                    let rect = Rect::from_x_y_ranges(fold_x.clone(), y_range.clone());
+                    let rect = Rect::from_center_size(rect.center(), Vec2::splat(0.4 * row_height));
+                    let points = if self.folded.contains(&line) {
+                        vec![rect.left_top(), rect.right_center(), rect.left_bottom()]
+                    } else {
+                        vec![rect.left_top(), rect.right_top(), rect.center_bottom()]
+                    };
+                    painter.add(Shape::convex_polygon(points, weak_color, Stroke::none()));
+                }
+            }
+
+            first_row_of_paragraph = row.ends_with_newline;
+            if row.ends_with_newline {
+                paragraph += 1;
+            }
+        }
+
+        ui.painter()
+            .set(line_highlight_idx, Shape::Vec(line_highlight));
+
+        if response.hovered() {
+            ui.output().cursor_icon = CursorIcon::PointingHand;
+        }
+    }
+}
+
+/// The lines of the full text that are visible, given the hidden lines.
+fn visible_lines(num_lines: usize, hidden_lines: &[Range<usize>]) -> Vec<usize> {
+    (0..num_lines)
+        .filter(|line| !hidden_lines.iter().any(|hidden| hidden.contains(line)))
+        .collect()
+}
+
+// ----------------------------------------------------------------------------
+
+/// How an edit changed the lines of a text:
+/// the lines `first..first + removed` were replaced by `first..first + inserted`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct LineEdit {
+    first: usize,
+    removed: usize,
+    inserted: usize,
+    /// The edit started in the middle of the line before `first`, which keeps its number.
+    splits_previous_line: bool,
+}
+
+impl LineEdit {
+    /// `None` if the texts are equal.
+    fn new(old_text: &str, new_text: &str) -> Option<Self> {
+        let old: Vec<char> = old_text.chars().collect();
+        let new: Vec<char> = new_text.chars().collect();
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let num_removed = old.len() - suffix - prefix;
+        let num_inserted = new.len() - suffix - prefix;
+        if num_removed == 0 && num_inserted == 0 {
+            return None;
+        }
+
+        // A pure insertion or deletion can often be moved without changing the result
+        // (deleting the first line of "fn a\nfn b" could also be deleting "a\nfn "),
+        // so move it to the start of a line if we can:
+        let mut prefix = prefix;
+        if (num_removed == 0) != (num_inserted == 0) {
+            let longer = if num_removed == 0 { &new } else { &old };
+            let len = num_removed + num_inserted;
+            let mut start = prefix;
+            while start > 0
+                && longer[start - 1] != '\n'
+                && longer[start - 1] == longer[start + len - 1]
+            {
+                start -= 1;
+            }
+            if start == 0 || longer[start - 1] == '\n' {
+                prefix = start;
+            }
+        }
+        let removed = &old[prefix..prefix + num_removed];
+        let inserted = &new[prefix..prefix + num_inserted];
+
+        let num_newlines = |chars: &[char]| chars.iter().filter(|&&c| c == '\n').count();
+        let whole_lines = |chars: &[char]| chars.last().map_or(true, |&c| c == '\n');
+        let line = num_newlines(&old[..prefix]);
+        let at_line_start = prefix == 0 || old[prefix - 1] == '\n';
+        let splits_previous_line =
+            !(at_line_start && whole_lines(removed) && whole_lines(inserted));
+
+        Some(Self {
+            first: if splits_previous_line { line + 1 } else { line },
+            removed: num_newlines(removed),
+            inserted: num_newlines(inserted),
+            splits_previous_line,
+        })
+    }
+
+    /// Where a line ended up, or `None` if it was deleted.
+    fn line(&self, line: usize) -> Option<usize> {
+        if line < self.first {
+            Some(line)
+        } else if line >= self.first + self.removed {
+            Some(line - self.removed + self.inserted)
+        } else {
+            None
+        }
+    }
+
+    /// Where the (exclusive) end of a range of lines ended up.
+    ///
+    /// A range that ends with the line that was split grows with it.
+    fn end(&self, end: usize) -> usize {
+        if end < self.first || (end == self.first && !self.splits_previous_line) {
+            end
+        } else if end >= self.first + self.removed {
+            end - self.removed + self.inserted
+        } else {
+            self.first + self.inserted
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A [`TextBuffer`] that shows some other text, minus the folded lines.
+///
+/// Edits are forwarded to the full text.
+/// Deleting a range that spans a folded region also deletes the folded lines.
+pub(crate) struct FoldedText<'a> {
+    text: &'a mut dyn TextBuffer,
+
+    /// Character ranges of the full text that are hidden. Sorted and non-overlapping.
+    hidden: Vec<Range<usize>>,
+
+    /// The text without the hidden ranges.
+    visible: String,
+}
+
+impl<'a> FoldedText<'a> {
+    pub fn new(text: &'a mut dyn TextBuffer, hidden_lines: &[Range<usize>]) -> Self {
+        let mut line_starts = vec![0];
+        for (i, c) in text.as_str().chars().enumerate() {
+            if c == '\n' {
+                line_starts.push(i + 1);
+            }
+        }
+        let num_chars = text.as_str().chars().count();
+        let line_start = |line: usize| line_starts.get(line).copied().unwrap_or(num_chars);
+
+        let hidden = hidden_lines
+            .iter()
+            .map(|lines| line_start(lines.start)..line_start(lines.end))
+            .filter(|range| !range.is_empty())
+            .map(|range| {
+                if range.end == num_chars {
+                    // Also hide the newline before a fold that reaches the end of the text,
+                    // or there would be an empty line after the fold:
+                    range.start - 1..range.end
+                } else {
+                    range
+                }
+            })
+            .collect();
+
+        let mut folded_text = Self {
+            text,
+            hidden,
+            visible: Default::default(),
+        };
+        folded_text.update_visible();
+        folded_text
+    }
+
+    fn update_visible(&mut self) {
+        let hidden = &self.hidden;
+        self.visible = self
+            .text
+            .as_str()
+            .chars()
+            .enumerate()
+            .filter(|(i, _)| !hidden.iter().any(|range| range.contains(i)))
+            .map(|(_, c)| c)
+            .collect();
+    }
+
+    /// Convert a character index in the visible text to one in the full text.
+    ///
+    /// An index where hidden text starts maps to after the hidden text,
+    /// except at the end of the visible text (the hidden text then reaches the end of the full text).
+    fn full_index(&self, visible_index: usize) -> usize {
+        let at_end = visible_index >= self.visible.chars().count();
+        let mut index = visible_index;
+        for range in &self.hidden {
+            if range.start < index || (range.start == index && !at_end) {
+                index += range.len();
+            } else {
+                break;
+            }
+        }
+        index
+    }
+}
+
+impl<'a> AsRef<str> for FoldedText<'a> {
+    fn as_ref(&self) -> &str {
+        &self.visible
+    }
+}
+
+impl<'a> TextBuffer for FoldedText<'a> {
+    fn is_mutable(&self) -> bool {
+        self.text.is_mutable()
+    }
+
+    fn insert_text(&mut self, text: &str, char_index: usize) -> usize {
+        let index = self.full_index(char_index);
+        let num_chars = self.text.insert_text(text, index);
+        for range in &mut self.hidden {
+            if range.start >= index {
+                range.start += num_chars;
+                range.end += num_chars;
+            }
+        }
+        self.update_visible();
+        num_chars
+    }
+
+    fn delete_char_range(&mut self, char_range: Range<usize>) {
+        let start = self.full_index(char_range.start);
+        let end = self.full_index(char_range.end).max(start);
+        self.text.delete_char_range(start..end);
+
+        let shift = |index: usize| {
+            if index <= start {
+                index
+            } else if index >= end {
+                index - (end - start)
+            } else {
+                start
+            }
+        };
+        for range in &mut self.hidden {
+            *range = shift(range.start)..shift(range.end);
+        }
+        self.hidden.retain(|range| !range.is_empty());
+        self.update_visible();
+    }
+
+    /// Only change what differs, so that we don't lose the folded lines.
+    fn replace(&mut self, text: &str) {
+        let old: Vec<char> = self.visible.chars().collect();
+        let new: Vec<char> = text.chars().collect();
+        let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+        let suffix = old[prefix..]
+            .iter()
+            .rev()
+            .zip(new[prefix..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        self.delete_char_range(prefix..old.len() - suffix);
+        let inserted: String = new[prefix..new.len() - suffix].iter().collect();
+        self.insert_text(&inserted, prefix);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: &str = "fn a() {\n    x\n}\nfn b() {\n    y\n}";
+
+    #[test]
+    fn folded_text_hides_lines() {
+        let mut text = CODE.to_owned();
+        let folded = FoldedText::new(&mut text, &[1..3]);
+        assert_eq!(folded.as_ref(), "fn a() {\nfn b() {\n    y\n}");
+
+        // Indices before the fold are unchanged, the rest skip the folded lines:
+        assert_eq!(folded.full_index(0), 0);
+        assert_eq!(folded.full_index(8), 8);
+        assert_eq!(folded.full_index(9), 17);
+        assert_eq!(folded.full_index(12), 20);
+    }
+
+    #[test]
+    fn folded_text_edits() {
+        let mut text = CODE.to_owned();
+        let mut folded = FoldedText::new(&mut text, &[1..3]);
+        assert_eq!(folded.insert_text("// b\n", 9), 5);
+        assert_eq!(folded.as_ref(), "fn a() {\n// b\nfn b() {\n    y\n}");
+        folded.delete_char_range(0..3);
+        assert_eq!(folded.as_ref(), "a() {\n// b\nfn b() {\n    y\n}");
+        drop(folded);
+        assert_eq!(text, "a() {\n    x\n}\n// b\nfn b() {\n    y\n}");
+    }
+
+    #[test]
+    fn deleting_across_a_fold_deletes_the_folded_lines() {
+        let mut text = CODE.to_owned();
+        let mut folded = FoldedText::new(&mut text, &[1..3]);
+        // The newline at the end of the first line, up to the start of `fn b`:
+        folded.delete_char_range(8..9);
+        assert_eq!(folded.as_ref(), "fn a() {fn b() {\n    y\n}");
+        drop(folded);
+        assert_eq!(text, "fn a() {fn b() {\n    y\n}");
+    }
+
+    #[test]
+    fn fold_at_end_of_text() {
+        let mut text = CODE.to_owned();
+        let mut folded = FoldedText::new(&mut text, &[4..6]);
+        // No empty line after the fold:
+        assert_eq!(folded.as_ref(), "fn a() {\n    x\n}\nfn b() {");
+        assert_eq!(folded.full_index(25), 25);
+
+        // Typing at the end goes on the visible line, not into the folded lines:
+        folded.insert_text("X", 25);
+        assert_eq!(folded.as_ref(), "fn a() {\n    x\n}\nfn b() {X");
+        drop(folded);
+        assert_eq!(text, "fn a() {\n    x\n}\nfn b() {X\n    y\n}");
+    }
+
+    #[test]
+    fn fold_at_end_of_text_ending_with_newline() {
+        let mut text = "a {\nb\n}\n".to_owned();
+        let mut folded = FoldedText::new(&mut text, &[1..4]);
+        assert_eq!(folded.as_ref(), "a {");
+        folded.replace("a {}");
+        drop(folded);
+        assert_eq!(text, "a {}\nb\n}\n");
+    }
+
+    #[test]
+    fn visible_lines_skip_hidden_lines() {
+        assert_eq!(visible_lines(6, &[1..3, 4..5]), vec![0, 3, 5]);
+    }
+
+    fn gutter() -> Gutter {
+        Gutter {
+            breakpoints: [1, 4].into_iter().collect(),
+            fold_regions: vec![0..3, 3..6],
+            folded: [3].into_iter().collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn inserting_lines_moves_breakpoints_and_folds() {
+        let mut gutter = gutter();
+        let new_text = format!("// comment\n{}", CODE);
+        gutter.on_text_changed(CODE, &new_text);
+        assert_eq!(gutter.breakpoints, [2, 5].into_iter().collect());
+        assert_eq!(gutter.folded, [4].into_iter().collect());
+        assert_eq!(gutter.fold_regions, vec![1..4, 4..7]);
+    }
+
+    #[test]
+    fn deleting_lines_removes_their_breakpoints() {
+        let mut gutter = gutter();
+        gutter.on_text_changed(CODE, "fn b() {\n    y\n}");
+        assert_eq!(gutter.breakpoints, [1].into_iter().collect());
+        assert_eq!(gutter.folded, [0].into_iter().collect());
+        assert_eq!(gutter.fold_regions, vec![0..3]);
+    }
+
+    #[test]
+    fn splitting_a_line_keeps_its_breakpoint() {
+        let mut gutter = gutter();
+        // Press enter in the middle of line 1:
+        gutter.on_text_changed(CODE, "fn a() {\n    \nx\n}\nfn b() {\n    y\n}");
+        assert_eq!(gutter.breakpoints, [1, 5].into_iter().collect());
+        assert_eq!(gutter.folded, [4].into_iter().collect());
+        assert_eq!(gutter.fold_regions, vec![0..4, 4..7]);
+    }
+
+    #[test]
+    fn editing_within_a_line_changes_nothing() {
+        let mut gutter = gutter();
+        gutter.on_text_changed(CODE, &CODE.replace('x', "xyz"));
+        assert_eq!(gutter, self::gutter());
+    }
+}
@@ -1,10 +1,19 @@
 mod builder;
+mod completion;
 mod cursor_range;
+mod diagnostics;
+mod gutter;
 mod output;
 mod state;
 mod text_buffer;
 
 pub use {
-    builder::TextEdit, cursor_range::*, output::TextEditOutput, state::TextEditState,
+    builder::TextEdit,
+    completion::CompletionProvider,
+    cursor_range::*,
+    diagnostics::{Diagnostic, DiagnosticsProvider, Severity},
+    gutter::Gutter,
+    output::TextEditOutput,
+    state::TextEditState,
     text_buffer::TextBuffer,
 };
@@ -30,6 +30,14 @@ pub struct TextEditState {
     // Visual offset when editing singleline text bigger than the width.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) singleline_offset: f32,
+
+    // The start of the word we are showing completions for, if any.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) completion_word_start: Option<usize>,
+
+    // Which completion candidate is selected.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) completion_selected: usize,
 }
 
 impl TextEditState {
@@ -31,9 +31,17 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         let (_output, shapes) = ctx.run(raw_input.clone(), |ctx| {
             demo_windows.ui(ctx);
         });
+        // Nothing changes between frames, so every layer is reused:
         c.bench_function("demo_only_tessellate", |b| {
             b.iter(|| ctx.tessellate(shapes.clone()))
         });
+
+        // What `demo_only_tessellate` would cost without the tessellation cache:
+        let options = ctx.memory().options.tessellation_options;
+        let font_image_size = ctx.fonts().font_image().size();
+        c.bench_function("demo_only_tessellate_uncached", |b| {
+            b.iter(|| egui::epaint::tessellate_shapes(shapes.clone(), options, font_image_size))
+        });
     }
 
     if false {
@@ -5,6 +5,7 @@
 pub struct CodeEditor {
     language: String,
     code: String,
+    gutter: egui::text_edit::Gutter,
 }
 
 impl Default for CodeEditor {
@@ -17,6 +18,10 @@ fn main() {\n\
 }\n\
 "
             .into(),
+            gutter: egui::text_edit::Gutter {
+                show_breakpoints: true,
+                ..Default::default()
+            },
         }
     }
 }
@@ -37,7 +42,11 @@ impl super::Demo for CodeEditor {
 
 impl super::View for CodeEditor {
     fn ui(&mut self, ui: &mut egui::Ui) {
-        let Self { language, code } = self;
+        let Self {
+            language,
+            code,
+            gutter,
+        } = self;
 
         ui.horizontal(|ui| {
             ui.set_height(0.0);
@@ -82,6 +91,15 @@ impl super::View for CodeEditor {
             ui.fonts().layout_job(layout_job)
         };
 
+        ui.label("Start typing the name of something already in the code to get suggestions.");
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut gutter.line_numbers, "Line numbers");
+            ui.checkbox(&mut gutter.show_breakpoints, "Breakpoints");
+            ui.checkbox(&mut gutter.highlight_current_line, "Highlight current line");
+        });
+        gutter.fold_regions = brace_regions(code);
+
         egui::ScrollArea::vertical().show(ui, |ui| {
             ui.add(
                 egui::TextEdit::multiline(code)
@@ -90,8 +108,50 @@ impl super::View for CodeEditor {
                     .desired_rows(10)
                     .lock_focus(true)
                     .desired_width(f32::INFINITY)
-                    .layouter(&mut layouter),
+                    .layouter(&mut layouter)
+                    .completions(&mut WordCompleter)
+                    .gutter(gutter),
             );
         });
     }
 }
+
+// ----------------------------------------------------------------------------
+
+/// Suggests words that are already used somewhere in the code.
+struct WordCompleter;
+
+impl egui::text_edit::CompletionProvider for WordCompleter {
+    fn completions(&mut self, text: &str, word: &str) -> Vec<String> {
+        let mut words: Vec<String> = text
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .filter(|candidate| candidate.len() > word.len() && candidate.starts_with(word))
+            .map(ToOwned::to_owned)
+            .collect();
+        words.sort();
+        words.dedup();
+        words
+    }
+}
+
+/// Each `{` that is closed by a `}` on a later line makes a region that can be folded.
+fn brace_regions(code: &str) -> Vec<std::ops::Range<usize>> {
+    let mut open = vec![];
+    let mut regions = vec![];
+    for (line, text) in code.lines().enumerate() {
+        for c in text.chars() {
+            match c {
+                '{' => open.push(line),
+                '}' => {
+                    if let Some(start) = open.pop() {
+                        if start < line {
+                            regions.push(start..line);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    regions
+}
@@ -303,6 +303,36 @@ impl BoxPainting {
                 );
             }
         });
+
+        ui.label("Gradient fills:");
+        ui.horizontal(|ui| {
+            let (rect, _response) = ui.allocate_at_least(self.size, Sense::hover());
+            ui.painter().add(epaint::RectShape {
+                rect,
+                corner_radius: self.corner_radius,
+                fill: Brush::linear_gradient(
+                    [rect.left_center(), rect.right_center()],
+                    Color32::RED,
+                    Color32::BLUE,
+                ),
+                stroke: Stroke::new(self.stroke_width, Color32::WHITE),
+                dash: None,
+            });
+
+            let (rect, _response) = ui.allocate_at_least(self.size, Sense::hover());
+            ui.painter().add(epaint::RectShape {
+                rect,
+                corner_radius: self.corner_radius,
+                fill: Brush::radial_gradient(
+                    rect.center(),
+                    rect.size().min_elem() / 2.0,
+                    Color32::YELLOW,
+                    Color32::from_rgb(0, 64, 0),
+                ),
+                stroke: Stroke::new(self.stroke_width, Color32::WHITE),
+                dash: None,
+            });
+        });
     }
 }
 
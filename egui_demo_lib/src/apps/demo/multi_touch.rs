@@ -1,6 +1,6 @@
 use egui::{
     emath::{RectTransform, Rot2},
-    vec2, Color32, Frame, Pos2, Rect, Sense, Stroke, Vec2,
+    vec2, Align2, Color32, Frame, Pos2, Rect, Sense, Stroke, TextStyle, Vec2,
 };
 
 pub struct MultiTouch {
@@ -8,6 +8,8 @@ pub struct MultiTouch {
     translation: Vec2,
     zoom: f32,
     last_touch_time: f64,
+    /// Description of the last single-finger gesture.
+    last_gesture: String,
 }
 
 impl Default for MultiTouch {
@@ -17,6 +19,7 @@ impl Default for MultiTouch {
             translation: Vec2::ZERO,
             zoom: 1.,
             last_touch_time: 0.0,
+            last_gesture: String::new(),
         }
     }
 }
@@ -48,6 +51,7 @@ impl super::View for MultiTouch {
         );
         ui.separator();
         ui.label("Try touch gestures Pinch/Stretch, Rotation, and Pressure with 2+ fingers.");
+        ui.label("With one finger, try long-press, swipe and fling.");
 
         let num_touches = ui.input().multi_touch().map_or(0, |mt| mt.num_touches);
         ui.label(format!("Current touches: {}", num_touches));
@@ -66,6 +70,28 @@ impl super::View for MultiTouch {
             let (response, painter) =
                 ui.allocate_painter(ui.available_size_before_wrap(), Sense::drag());
 
+            if response.long_pressed() {
+                self.last_gesture = "Long-press".to_owned();
+            }
+            if let Some(swipe) = response.swiped() {
+                self.last_gesture = format!(
+                    "Swipe {:?} at {:.0} points/s",
+                    swipe.direction,
+                    swipe.velocity.length()
+                );
+            } else if let Some(velocity) = ui.input().fling_velocity() {
+                if response.drag_released() {
+                    self.last_gesture = format!("Fling at {:.0} points/s", velocity.length());
+                }
+            }
+            painter.text(
+                response.rect.left_bottom() + vec2(4.0, -4.0),
+                Align2::LEFT_BOTTOM,
+                &self.last_gesture,
+                TextStyle::Body,
+                Color32::GRAY,
+            );
+
             // normalize painter coordinates to ±1 units in each direction with [0,0] in the center:
             let painter_proportions = response.rect.square_proportions();
             let to_screen = RectTransform::from_to(
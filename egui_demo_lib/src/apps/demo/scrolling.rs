@@ -149,6 +149,8 @@ struct ScrollTo {
     track_item: usize,
     tack_item_align: Align,
     offset: f32,
+    animated: bool,
+    smooth_scrolling: bool,
 }
 
 impl Default for ScrollTo {
@@ -157,6 +159,8 @@ impl Default for ScrollTo {
             track_item: 25,
             tack_item_align: Align::Center,
             offset: 0.0,
+            animated: true,
+            smooth_scrolling: true,
         }
     }
 }
@@ -202,45 +206,52 @@ impl super::View for ScrollTo {
             scroll_bottom |= ui.button("Scroll to bottom").clicked();
         });
 
-        let mut scroll_area = ScrollArea::vertical()
-            .max_height(200.0)
-            .auto_shrink([false; 2]);
-        if go_to_scroll_offset {
-            scroll_area = scroll_area.vertical_scroll_offset(self.offset);
-        }
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.animated, "Animated");
+            ui.checkbox(&mut self.smooth_scrolling, "Smooth mouse wheel");
+        });
 
         ui.separator();
-        let (current_scroll, max_scroll) = scroll_area.show(ui, |ui| {
-            if scroll_top {
-                ui.scroll_to_cursor(Align::TOP);
-            }
-            ui.vertical(|ui| {
-                for item in 1..=50 {
-                    if track_item && item == self.track_item {
-                        let response =
-                            ui.colored_label(Color32::YELLOW, format!("This is item {}", item));
-                        response.scroll_to_me(self.tack_item_align);
-                    } else {
-                        ui.label(format!("This is item {}", item));
+        let output = ScrollArea::vertical()
+            .max_height(200.0)
+            .auto_shrink([false; 2])
+            .smooth_scrolling(self.smooth_scrolling)
+            .show(ui, |ui| {
+                if scroll_top {
+                    ui.scroll_to_cursor(Align::TOP);
+                }
+                ui.vertical(|ui| {
+                    for item in 1..=50 {
+                        if track_item && item == self.track_item {
+                            let response =
+                                ui.colored_label(Color32::YELLOW, format!("This is item {}", item));
+                            ui.scroll_to_rect(response.rect, self.tack_item_align, self.animated);
+                        } else {
+                            ui.label(format!("This is item {}", item));
+                        }
                     }
+                });
+
+                if scroll_bottom {
+                    ui.scroll_to_cursor(Align::BOTTOM);
                 }
             });
-
-            if scroll_bottom {
-                ui.scroll_to_cursor(Align::BOTTOM);
-            }
-
-            let margin = ui.visuals().clip_rect_margin;
-
-            let current_scroll = ui.clip_rect().top() - ui.min_rect().top() + margin;
-            let max_scroll = ui.min_rect().height() - ui.clip_rect().height() + 2.0 * margin;
-            (current_scroll, max_scroll)
-        });
         ui.separator();
 
+        if go_to_scroll_offset {
+            // Scrolling from outside of the scroll area takes effect next frame:
+            ScrollArea::scroll_to_offset(
+                ui.ctx(),
+                output.id,
+                vec2(0.0, self.offset),
+                self.animated,
+            );
+        }
+
         ui.label(format!(
             "Scroll offset: {:.0}/{:.0} px",
-            current_scroll, max_scroll
+            output.offset().y,
+            output.max_offset().y
         ));
 
         ui.separator();
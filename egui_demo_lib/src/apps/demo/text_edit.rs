@@ -9,7 +9,7 @@ pub struct TextEdit {
 impl Default for TextEdit {
     fn default() -> Self {
         Self {
-            text: "Edit this text".to_owned(),
+            text: "Edit this this text".to_owned(),
         }
     }
 }
@@ -43,8 +43,11 @@ impl super::View for TextEdit {
 
         let output = egui::TextEdit::multiline(text)
             .hint_text("Type something!")
+            .diagnostics(&mut repeated_words)
             .show(ui);
 
+        ui.label("Repeated words are underlined. Right-click them to fix them.");
+
         ui.horizontal(|ui| {
             ui.spacing_mut().item_spacing.x = 0.0;
             ui.label("Selected text: ");
@@ -81,3 +84,29 @@ impl super::View for TextEdit {
         }
     }
 }
+
+/// A tiny "spell checker" that finds words that are repeated, like "the the".
+fn repeated_words(text: &str) -> Vec<egui::text_edit::Diagnostic> {
+    let mut diagnostics = vec![];
+    let mut previous_word: Option<(usize, &str)> = None;
+    let mut char_index = 0;
+    for word in text.split(' ') {
+        let num_chars = word.chars().count();
+        if !word.is_empty() {
+            if let Some((previous_start, previous_word)) = previous_word {
+                if previous_word.eq_ignore_ascii_case(word) {
+                    diagnostics.push(
+                        egui::text_edit::Diagnostic::warning(
+                            previous_start..char_index + num_chars,
+                            format!("Repeated word: {:?}", word),
+                        )
+                        .suggestion(previous_word),
+                    );
+                }
+            }
+        }
+        previous_word = Some((char_index, word));
+        char_index += num_chars + 1;
+    }
+    diagnostics
+}
@@ -16,6 +16,7 @@ pub struct WidgetGallery {
     scalar: f32,
     string: String,
     color: egui::Color32,
+    date: egui::date_picker::Date,
     animate_progress_bar: bool,
 }
 
@@ -29,6 +30,7 @@ impl Default for WidgetGallery {
             scalar: 42.0,
             string: Default::default(),
             color: egui::Color32::LIGHT_BLUE.linear_multiply(0.5),
+            date: egui::date_picker::Date::new(2022, 1, 1).unwrap(),
             animate_progress_bar: false,
         }
     }
@@ -98,6 +100,7 @@ impl WidgetGallery {
             scalar,
             string,
             color,
+            date,
             animate_progress_bar,
         } = self;
 
@@ -180,6 +183,10 @@ impl WidgetGallery {
         ui.color_edit_button_srgba(color);
         ui.end_row();
 
+        ui.add(doc_link_label("Date picker", "date_picker"));
+        ui.add(egui::date_picker::DatePickerButton::new(date));
+        ui.end_row();
+
         ui.add(doc_link_label("Image", "Image"));
         ui.image(egui::TextureId::Egui, [24.0, 16.0])
             .on_hover_text("The egui font texture was the convenient choice to show here.");
@@ -77,8 +77,9 @@ impl FrameHistory {
         shapes.push(Shape::Rect(epaint::RectShape {
             rect,
             corner_radius: style.corner_radius,
-            fill: ui.visuals().extreme_bg_color,
+            fill: ui.visuals().extreme_bg_color.into(),
             stroke: ui.style().noninteractive().bg_stroke,
+            dash: None,
         }));
 
         let rect = rect.shrink(4.0);
@@ -153,6 +153,71 @@ fn test_egui_e2e() {
     }
 }
 
+#[test]
+fn test_tessellation_cache() {
+    let mut demo_windows = crate::DemoWindows::default();
+    let mut ctx = egui::CtxRef::default();
+    let raw_input = egui::RawInput::default();
+
+    // Meshes of neighboring layers should be merged as if all shapes were tessellated together.
+    // The `Noop` added below gets an empty mesh of its own, which we ignore.
+    type Meshes = Vec<(egui::Rect, Vec<u32>, Vec<egui::epaint::Vertex>)>;
+    let meshes = |clipped_meshes: Vec<egui::ClippedMesh>| -> Meshes {
+        clipped_meshes
+            .into_iter()
+            .filter(|egui::ClippedMesh(_, mesh)| !mesh.is_empty())
+            .map(|egui::ClippedMesh(clip_rect, mesh)| (clip_rect, mesh.indices, mesh.vertices))
+            .collect()
+    };
+
+    const NUM_FRAMES: usize = 5;
+    for _ in 0..NUM_FRAMES {
+        let (_output, shapes) = ctx.run(raw_input.clone(), |ctx| {
+            demo_windows.ui(ctx);
+        });
+
+        // Adding a shape means the layers are unknown, so nothing is cached:
+        let mut uncached_shapes = shapes.clone();
+        uncached_shapes.push(egui::epaint::ClippedShape(
+            egui::Rect::EVERYTHING,
+            egui::Shape::Noop,
+        ));
+
+        let cached = meshes(ctx.tessellate(shapes));
+        let uncached = meshes(ctx.tessellate(uncached_shapes));
+        assert_eq!(cached, uncached);
+    }
+
+    // Stop the clock, so that animations (e.g. spinners) don't change any layers:
+    let raw_input = egui::RawInput {
+        time: Some(0.0),
+        ..Default::default()
+    };
+    let mut run_frame = |text: &str| -> egui::epaint::PaintStats {
+        let (_output, shapes) = ctx.run(raw_input.clone(), |ctx| {
+            demo_windows.ui(ctx);
+            egui::Area::new("tessellation_cache_test").show(ctx, |ui| ui.label(text));
+        });
+        ctx.tessellate(shapes);
+        ctx.paint_stats()
+    };
+
+    for _ in 0..NUM_FRAMES {
+        run_frame("before");
+    }
+
+    // Nothing changed, so every layer is reused:
+    let stats = run_frame("before");
+    assert_eq!(stats.tessellated_layers, 0);
+    assert!(stats.cached_layers > 1);
+    let num_layers = stats.cached_layers;
+
+    // Only the layer of the `Area` changed:
+    let stats = run_frame("after");
+    assert_eq!(stats.tessellated_layers, 1);
+    assert_eq!(stats.cached_layers, num_layers - 1);
+}
+
 #[test]
 fn test_egui_zero_window_size() {
     let mut demo_windows = crate::DemoWindows::default();
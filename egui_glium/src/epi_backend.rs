@@ -47,6 +47,8 @@ pub fn run(app: Box<dyn epi::App>, native_options: &epi::NativeOptions) -> ! {
     let mut painter = crate::Painter::new(&display);
     let mut integration = egui_winit::epi::EpiIntegration::new(
         "egui_glium",
+        false, // Extra native windows (`epi::Frame::show_window`) are only supported by egui_glow
+        native_options,
         display.gl_window().window(),
         repaint_signal,
         persistence,
@@ -68,7 +70,7 @@ pub fn run(app: Box<dyn epi::App>, native_options: &epi::NativeOptions) -> ! {
 
             let (needs_repaint, mut tex_allocation_data, shapes) =
                 integration.update(display.gl_window().window());
-            let clipped_meshes = integration.egui_ctx.tessellate(shapes);
+            let clipped_primitives = integration.egui_ctx.tessellate_primitives(shapes);
 
             for (id, image) in tex_allocation_data.creations {
                 painter.set_texture(&display, id, &image);
@@ -81,11 +83,11 @@ pub fn run(app: Box<dyn epi::App>, native_options: &epi::NativeOptions) -> ! {
                 let color = integration.app.clear_color();
                 target.clear_color(color[0], color[1], color[2], color[3]);
 
-                painter.paint_meshes(
+                painter.paint_primitives(
                     &display,
                     &mut target,
                     integration.egui_ctx.pixels_per_point(),
-                    clipped_meshes,
+                    &clipped_primitives,
                     &integration.egui_ctx.font_image(),
                 );
 
@@ -0,0 +1,263 @@
+#![allow(deprecated)] // legacy implement_vertex macro
+#![allow(semicolon_in_expressions_from_macros)] // glium::program! macro
+
+use {
+    egui::epaint::{GroupEffects, Rgba},
+    glium::{
+        framebuffer::SimpleFrameBuffer,
+        implement_vertex,
+        index::{NoIndices, PrimitiveType},
+        program,
+        texture::{srgb_texture2d::SrgbTexture2d, MipmapsOption, SrgbFormat},
+        uniform,
+        uniforms::{MagnifySamplerFilter, MinifySamplerFilter, SamplerWrapFunction},
+        Surface as _,
+    },
+    std::rc::Rc,
+};
+
+/// Must match `MAX_RADIUS` in `shader/group_fragment.glsl`.
+const MAX_BLUR_RADIUS: f32 = 32.0;
+
+#[derive(Copy, Clone)]
+struct QuadVertex {
+    a_pos: [f32; 2],
+}
+implement_vertex!(QuadVertex, a_pos);
+
+/// The settings of one full-screen draw call of the group program.
+struct Pass {
+    /// Distance between blur samples, in pixels, along x or y.
+    step: [f32; 2],
+    /// In samples.
+    sigma: f32,
+    /// In pixels (y up, as in OpenGL).
+    offset: [f32; 2],
+    tint: Option<Rgba>,
+    opacity: f32,
+}
+
+/// Paints [`egui::epaint::GroupPrimitive`]s by rendering their content into a render target,
+/// and then compositing that with the [`GroupEffects`].
+///
+/// The render targets are [`SrgbTexture2d`]s, so the content is stored (and blurred) as linear colors,
+/// just like the screen.
+pub(crate) struct GroupCompositor {
+    program: glium::Program,
+    quad: glium::VertexBuffer<QuadVertex>,
+
+    /// Two for each nesting level of groups: one for the content, and one for blurring.
+    targets: Vec<Rc<SrgbTexture2d>>,
+}
+
+impl GroupCompositor {
+    pub(crate) fn new(facade: &dyn glium::backend::Facade) -> Self {
+        let vertex = include_str!("shader/group_vertex.glsl");
+        let fragment = include_str!("shader/group_fragment.glsl");
+        let new_interface = "#define NEW_SHADER_INTERFACE";
+        let program = program! {
+            facade,
+            120 => {
+                vertex: &format!("#version 120\n{}", vertex),
+                fragment: &format!("#version 120\n{}", fragment),
+            },
+            140 => {
+                vertex: &format!("#version 140\n{}\n{}", new_interface, vertex),
+                fragment: &format!("#version 140\n{}\n{}", new_interface, fragment),
+            },
+            100 es => {
+                vertex: &format!("#version 100\n{}", vertex),
+                fragment: &format!("#version 100\n{}", fragment),
+            },
+            300 es => {
+                vertex: &format!("#version 300 es\n{}\n{}", new_interface, vertex),
+                fragment: &format!("#version 300 es\n{}\n{}", new_interface, fragment),
+            },
+        }
+        .expect("Failed to compile group shader");
+
+        let corners = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0], [1.0, 1.0]];
+        let vertices: Vec<QuadVertex> = corners.iter().map(|&a_pos| QuadVertex { a_pos }).collect();
+        let quad = glium::VertexBuffer::new(facade, &vertices).unwrap();
+
+        Self {
+            program,
+            quad,
+            targets: Vec::new(),
+        }
+    }
+
+    /// Clear the render target for the content of a group at this nesting depth.
+    ///
+    /// Returns the texture to paint the content into.
+    pub(crate) fn begin(
+        &mut self,
+        facade: &dyn glium::backend::Facade,
+        (width, height): (u32, u32),
+        depth: usize,
+    ) -> Rc<SrgbTexture2d> {
+        let new_target = || {
+            let format = SrgbFormat::U8U8U8U8;
+            let texture = SrgbTexture2d::empty_with_format(
+                facade,
+                format,
+                MipmapsOption::NoMipmap,
+                width,
+                height,
+            );
+            match texture {
+                Ok(texture) => Rc::new(texture),
+                Err(err) => panic!("Failed to create render target: {}", err),
+            }
+        };
+
+        while self.targets.len() < 2 * (depth + 1) {
+            self.targets.push(new_target());
+        }
+        for target in &mut self.targets[2 * depth..2 * (depth + 1)] {
+            if target.dimensions() != (width, height) {
+                *target = new_target();
+            }
+        }
+
+        let content = self.targets[2 * depth].clone();
+        SimpleFrameBuffer::new(facade, &*content)
+            .unwrap()
+            .clear_color(0.0, 0.0, 0.0, 0.0);
+        content
+    }
+
+    /// Composite the content of the group at this nesting depth into `target`,
+    /// clipped to `scissor`.
+    pub(crate) fn end<T: glium::Surface>(
+        &self,
+        facade: &dyn glium::backend::Facade,
+        target: &mut T,
+        pixels_per_point: f32,
+        depth: usize,
+        effects: &GroupEffects,
+        scissor: glium::Rect,
+    ) {
+        let opacity = effects.opacity.clamp(0.0, 1.0);
+
+        if let Some(shadow) = effects.shadow {
+            let offset = shadow.offset * pixels_per_point;
+            self.blur_into(
+                facade,
+                target,
+                depth,
+                shadow.blur * pixels_per_point,
+                scissor,
+                [offset.x, -offset.y],
+                Some(Rgba::from(shadow.color)),
+                opacity,
+            );
+        }
+
+        self.blur_into(
+            facade,
+            target,
+            depth,
+            effects.blur * pixels_per_point,
+            scissor,
+            [0.0, 0.0],
+            None,
+            opacity,
+        );
+    }
+
+    /// Blur the content (with `sigma` in pixels) in two passes: horizontally into the scratch target,
+    /// and then vertically into `target`, where the offset, tint and opacity are also applied.
+    #[allow(clippy::too_many_arguments)]
+    fn blur_into<T: glium::Surface>(
+        &self,
+        facade: &dyn glium::backend::Facade,
+        target: &mut T,
+        depth: usize,
+        sigma: f32,
+        scissor: glium::Rect,
+        offset: [f32; 2],
+        tint: Option<Rgba>,
+        opacity: f32,
+    ) {
+        let content = &self.targets[2 * depth];
+        let scratch = &self.targets[2 * depth + 1];
+
+        // Spread out the samples if the blur is wider than the shader can handle:
+        let step = (3.0 * sigma / MAX_BLUR_RADIUS).max(1.0);
+        let sigma = sigma.max(0.0) / step;
+
+        let source = if sigma > 0.0 {
+            let mut framebuffer = SimpleFrameBuffer::new(facade, &**scratch).unwrap();
+            framebuffer.clear_color(0.0, 0.0, 0.0, 0.0);
+            self.draw(
+                &mut framebuffer,
+                content,
+                &Pass {
+                    step: [step, 0.0],
+                    sigma,
+                    offset: [0.0, 0.0],
+                    tint: None,
+                    opacity: 1.0,
+                },
+                &glium::DrawParameters::default(),
+            );
+            scratch
+        } else {
+            content
+        };
+
+        let params = glium::DrawParameters {
+            blend: crate::painter::premultiplied_blend(),
+            scissor: Some(scissor),
+            ..Default::default()
+        };
+        self.draw(
+            target,
+            source,
+            &Pass {
+                step: [0.0, step],
+                sigma,
+                offset,
+                tint,
+                opacity,
+            },
+            &params,
+        );
+    }
+
+    /// Draw a full-screen quad sampling from `source`.
+    fn draw<T: glium::Surface>(
+        &self,
+        target: &mut T,
+        source: &SrgbTexture2d,
+        pass: &Pass,
+        params: &glium::DrawParameters<'_>,
+    ) {
+        let (width, height) = source.dimensions();
+        let (width, height) = (width as f32, height as f32);
+        let tint = pass.tint.unwrap_or(Rgba::WHITE);
+        let uniforms = uniform! {
+            u_sampler: source
+                .sampled()
+                .magnify_filter(MagnifySamplerFilter::Nearest)
+                .minify_filter(MinifySamplerFilter::Nearest)
+                .wrap_function(SamplerWrapFunction::Clamp),
+            u_step: [pass.step[0] / width, pass.step[1] / height],
+            u_sigma: pass.sigma,
+            u_offset: [pass.offset[0] / width, pass.offset[1] / height],
+            u_use_tint: if pass.tint.is_some() { 1.0f32 } else { 0.0 },
+            u_tint: [tint.r(), tint.g(), tint.b(), tint.a()],
+            u_opacity: pass.opacity,
+        };
+        target
+            .draw(
+                &self.quad,
+                NoIndices(PrimitiveType::TriangleStrip),
+                &self.program,
+                &uniforms,
+                params,
+            )
+            .unwrap();
+    }
+}
@@ -87,6 +87,7 @@
 #![allow(clippy::float_cmp)]
 #![allow(clippy::manual_range_contains)]
 
+mod group_compositor;
 mod painter;
 pub use painter::Painter;
 
@@ -147,12 +148,12 @@ impl EguiGlium {
         target: &mut T,
         shapes: Vec<egui::epaint::ClippedShape>,
     ) {
-        let clipped_meshes = self.egui_ctx.tessellate(shapes);
-        self.painter.paint_meshes(
+        let clipped_primitives = self.egui_ctx.tessellate_primitives(shapes);
+        self.painter.paint_primitives(
             display,
             target,
             self.egui_ctx.pixels_per_point(),
-            clipped_meshes,
+            &clipped_primitives,
             &self.egui_ctx.font_image(),
         );
     }
@@ -2,11 +2,13 @@
 #![allow(semicolon_in_expressions_from_macros)] // glium::program! macro
 
 use {
+    crate::group_compositor::GroupCompositor,
     egui::{
         emath::Rect,
-        epaint::{Color32, Mesh},
+        epaint::{ClippedPrimitive, Color32, Mesh, Primitive},
     },
     glium::{
+        framebuffer::SimpleFrameBuffer,
         implement_vertex,
         index::PrimitiveType,
         program,
@@ -19,6 +21,7 @@ use {
 
 pub struct Painter {
     program: glium::Program,
+    group_compositor: GroupCompositor,
     egui_texture: Option<SrgbTexture2d>,
     egui_texture_version: Option<u64>,
 
@@ -54,6 +57,7 @@ impl Painter {
 
         Painter {
             program,
+            group_compositor: GroupCompositor::new(facade),
             egui_texture: None,
             egui_texture_version: None,
             user_textures: Default::default(),
@@ -91,6 +95,11 @@ impl Painter {
     /// Main entry-point for painting a frame.
     /// You should call `target.clear_color(..)` before
     /// and `target.finish()` after this.
+    ///
+    /// The meshes come from [`egui::Context::tessellate`], so shape groups
+    /// ([`egui::epaint::GroupShape`]) are only approximated:
+    /// there is no blur, and overlapping shapes in a faded group show through each other.
+    /// Use [`Self::paint_primitives`] if you need them composited properly.
     pub fn paint_meshes<T: glium::Surface>(
         &mut self,
         display: &glium::Display,
@@ -106,6 +115,62 @@ impl Painter {
         }
     }
 
+    /// Like [`Self::paint_meshes`], but also paints [`egui::epaint::GroupPrimitive`]s
+    /// (see [`egui::Context::tessellate_primitives`]), by rendering them into offscreen render targets.
+    pub fn paint_primitives<T: glium::Surface>(
+        &mut self,
+        display: &glium::Display,
+        target: &mut T,
+        pixels_per_point: f32,
+        clipped_primitives: &[ClippedPrimitive],
+        font_image: &egui::FontImage,
+    ) {
+        self.upload_egui_texture(display, font_image);
+        self.paint_clipped_primitives(display, target, pixels_per_point, clipped_primitives, 0);
+    }
+
+    /// `depth` is how many groups we are inside.
+    fn paint_clipped_primitives<T: glium::Surface>(
+        &mut self,
+        display: &glium::Display,
+        target: &mut T,
+        pixels_per_point: f32,
+        clipped_primitives: &[ClippedPrimitive],
+        depth: usize,
+    ) {
+        for ClippedPrimitive(clip_rect, primitive) in clipped_primitives {
+            match primitive {
+                Primitive::Mesh(mesh) => {
+                    self.paint_mesh(target, display, pixels_per_point, *clip_rect, mesh);
+                }
+                Primitive::Group(group) => {
+                    let size = display.get_framebuffer_dimensions();
+                    let content = self.group_compositor.begin(display, size, depth);
+                    {
+                        let mut framebuffer = SimpleFrameBuffer::new(display, &*content).unwrap();
+                        self.paint_clipped_primitives(
+                            display,
+                            &mut framebuffer,
+                            pixels_per_point,
+                            &group.primitives,
+                            depth + 1,
+                        );
+                    }
+
+                    let scissor = scissor_rect(size, pixels_per_point, *clip_rect);
+                    self.group_compositor.end(
+                        display,
+                        target,
+                        pixels_per_point,
+                        depth,
+                        &group.effects,
+                        scissor,
+                    );
+                }
+            }
+        }
+    }
+
     #[inline(never)] // Easier profiling
     fn paint_mesh<T: glium::Surface>(
         &mut self,
@@ -158,54 +223,17 @@ impl Painter {
                 u_sampler: texture.sampled().magnify_filter(filter).wrap_function(SamplerWrapFunction::Clamp),
             };
 
-            // egui outputs colors with premultiplied alpha:
-            let color_blend_func = glium::BlendingFunction::Addition {
-                source: glium::LinearBlendingFactor::One,
-                destination: glium::LinearBlendingFactor::OneMinusSourceAlpha,
-            };
-
-            // Less important, but this is technically the correct alpha blend function
-            // when you want to make use of the framebuffer alpha (for screenshots, compositing, etc).
-            let alpha_blend_func = glium::BlendingFunction::Addition {
-                source: glium::LinearBlendingFactor::OneMinusDestinationAlpha,
-                destination: glium::LinearBlendingFactor::One,
-            };
-
-            let blend = glium::Blend {
-                color: color_blend_func,
-                alpha: alpha_blend_func,
-                ..Default::default()
-            };
-
             // egui outputs mesh in both winding orders:
             let backface_culling = glium::BackfaceCullingMode::CullingDisabled;
 
-            // Transform clip rect to physical pixels:
-            let clip_min_x = pixels_per_point * clip_rect.min.x;
-            let clip_min_y = pixels_per_point * clip_rect.min.y;
-            let clip_max_x = pixels_per_point * clip_rect.max.x;
-            let clip_max_y = pixels_per_point * clip_rect.max.y;
-
-            // Make sure clip rect can fit within a `u32`:
-            let clip_min_x = clip_min_x.clamp(0.0, width_in_pixels as f32);
-            let clip_min_y = clip_min_y.clamp(0.0, height_in_pixels as f32);
-            let clip_max_x = clip_max_x.clamp(clip_min_x, width_in_pixels as f32);
-            let clip_max_y = clip_max_y.clamp(clip_min_y, height_in_pixels as f32);
-
-            let clip_min_x = clip_min_x.round() as u32;
-            let clip_min_y = clip_min_y.round() as u32;
-            let clip_max_x = clip_max_x.round() as u32;
-            let clip_max_y = clip_max_y.round() as u32;
-
             let params = glium::DrawParameters {
-                blend,
+                blend: premultiplied_blend(),
                 backface_culling,
-                scissor: Some(glium::Rect {
-                    left: clip_min_x,
-                    bottom: height_in_pixels - clip_max_y,
-                    width: clip_max_x - clip_min_x,
-                    height: clip_max_y - clip_min_y,
-                }),
+                scissor: Some(scissor_rect(
+                    (width_in_pixels, height_in_pixels),
+                    pixels_per_point,
+                    clip_rect,
+                )),
                 ..Default::default()
             };
 
@@ -261,6 +289,57 @@ impl Painter {
     }
 }
 
+/// The blending for egui colors, which have premultiplied alpha.
+pub(crate) fn premultiplied_blend() -> glium::Blend {
+    let color_blend_func = glium::BlendingFunction::Addition {
+        source: glium::LinearBlendingFactor::One,
+        destination: glium::LinearBlendingFactor::OneMinusSourceAlpha,
+    };
+
+    // Less important, but this is technically the correct alpha blend function
+    // when you want to make use of the framebuffer alpha (for screenshots, compositing, etc).
+    let alpha_blend_func = glium::BlendingFunction::Addition {
+        source: glium::LinearBlendingFactor::OneMinusDestinationAlpha,
+        destination: glium::LinearBlendingFactor::One,
+    };
+
+    glium::Blend {
+        color: color_blend_func,
+        alpha: alpha_blend_func,
+        ..Default::default()
+    }
+}
+
+/// Transform a clip rect in points to a scissor rect in physical pixels.
+fn scissor_rect(
+    (width_in_pixels, height_in_pixels): (u32, u32),
+    pixels_per_point: f32,
+    clip_rect: Rect,
+) -> glium::Rect {
+    let clip_min_x = pixels_per_point * clip_rect.min.x;
+    let clip_min_y = pixels_per_point * clip_rect.min.y;
+    let clip_max_x = pixels_per_point * clip_rect.max.x;
+    let clip_max_y = pixels_per_point * clip_rect.max.y;
+
+    // Make sure clip rect can fit within a `u32`:
+    let clip_min_x = clip_min_x.clamp(0.0, width_in_pixels as f32);
+    let clip_min_y = clip_min_y.clamp(0.0, height_in_pixels as f32);
+    let clip_max_x = clip_max_x.clamp(clip_min_x, width_in_pixels as f32);
+    let clip_max_y = clip_max_y.clamp(clip_min_y, height_in_pixels as f32);
+
+    let clip_min_x = clip_min_x.round() as u32;
+    let clip_min_y = clip_min_y.round() as u32;
+    let clip_max_x = clip_max_x.round() as u32;
+    let clip_max_y = clip_max_y.round() as u32;
+
+    glium::Rect {
+        left: clip_min_x,
+        bottom: height_in_pixels - clip_max_y,
+        width: clip_max_x - clip_min_x,
+        height: clip_max_y - clip_min_y,
+    }
+}
+
 #[cfg(feature = "epi")]
 impl epi::NativeTexture for Painter {
     type Texture = Rc<SrgbTexture2d>;
@@ -13,7 +13,7 @@ impl epi::backend::RepaintSignal for GlowRepaintSignal {
 #[allow(unsafe_code)]
 fn create_display(
     window_builder: glutin::window::WindowBuilder,
-    event_loop: &glutin::event_loop::EventLoop<RequestRepaintEvent>,
+    event_loop: &glutin::event_loop::EventLoopWindowTarget<RequestRepaintEvent>,
 ) -> (
     glutin::WindowedContext<glutin::PossiblyCurrent>,
     glow::Context,
@@ -40,6 +40,36 @@ fn create_display(
     (gl_window, gl)
 }
 
+/// Make the OpenGL context of the window current, so that we can paint to it.
+///
+/// Only needed when there is more than one window.
+#[allow(unsafe_code)]
+fn make_current(gl_window: &mut Option<glutin::WindowedContext<glutin::PossiblyCurrent>>) {
+    if let Some(context) = gl_window.take() {
+        *gl_window = Some(match unsafe { context.make_current() } {
+            Ok(context) => context,
+            Err((context, err)) => {
+                eprintln!("Failed to make OpenGL context current: {}", err);
+                context
+            }
+        });
+    }
+}
+
+/// An extra native window, see [`epi::Frame::show_window`].
+struct ExtraWindow {
+    /// Only `None` while switching the current context.
+    gl_window: Option<glutin::WindowedContext<glutin::PossiblyCurrent>>,
+    gl: glow::Context,
+    painter: crate::Painter,
+}
+
+impl ExtraWindow {
+    fn window(&self) -> &glutin::window::Window {
+        self.gl_window.as_ref().unwrap().window()
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 pub use epi::NativeOptions;
@@ -53,6 +83,7 @@ pub fn run(app: Box<dyn epi::App>, native_options: &epi::NativeOptions) -> ! {
         egui_winit::epi::window_builder(native_options, &window_settings).with_title(app.name());
     let event_loop = glutin::event_loop::EventLoop::with_user_event();
     let (gl_window, gl) = create_display(window_builder, &event_loop);
+    let mut gl_window = Some(gl_window);
 
     let repaint_signal = std::sync::Arc::new(GlowRepaintSignal(std::sync::Mutex::new(
         event_loop.create_proxy(),
@@ -63,15 +94,21 @@ pub fn run(app: Box<dyn epi::App>, native_options: &epi::NativeOptions) -> ! {
         .unwrap();
     let mut integration = egui_winit::epi::EpiIntegration::new(
         "egui_glow",
-        gl_window.window(),
+        true,
+        native_options,
+        gl_window.as_ref().unwrap().window(),
         repaint_signal,
         persistence,
         app,
     );
 
     let mut is_focused = true;
+    let mut extra_windows: std::collections::HashMap<egui::Id, ExtraWindow> = Default::default();
+    // All user textures, for uploading to extra windows when they are created.
+    let mut user_images: std::collections::HashMap<u64, epi::Image> = Default::default();
 
-    event_loop.run(move |event, _, control_flow| {
+    event_loop.run(move |event, event_loop, control_flow| {
+        let main_window_id = gl_window.as_ref().unwrap().window().id();
         let mut redraw = || {
             if !is_focused {
                 // On Mac, a minimized Window uses up all CPU: https://github.com/emilk/egui/issues/325
@@ -82,12 +119,21 @@ pub fn run(app: Box<dyn epi::App>, native_options: &epi::NativeOptions) -> ! {
                 std::thread::sleep(std::time::Duration::from_millis(10));
             }
 
-            let (needs_repaint, mut tex_allocation_data, shapes) =
-                integration.update(gl_window.window());
-            let clipped_meshes = integration.egui_ctx.tessellate(shapes);
+            let native_windows: Vec<_> = extra_windows
+                .iter()
+                .map(|(id, extra_window)| (*id, extra_window.window()))
+                .collect();
+            let (mut needs_repaint, mut tex_allocation_data, shapes, extra_window_outputs) =
+                integration
+                    .update_with_windows(gl_window.as_ref().unwrap().window(), &native_windows);
+            let clipped_primitives = integration.egui_ctx.tessellate_primitives(shapes);
 
-            for (id, image) in tex_allocation_data.creations {
-                painter.set_texture(&gl, id, &image);
+            if !extra_windows.is_empty() {
+                make_current(&mut gl_window);
+            }
+
+            for (id, image) in &tex_allocation_data.creations {
+                painter.set_texture(&gl, *id, image);
             }
 
             // paint:
@@ -100,32 +146,107 @@ pub fn run(app: Box<dyn epi::App>, native_options: &epi::NativeOptions) -> ! {
                     gl.clear(glow::COLOR_BUFFER_BIT);
                 }
                 painter.upload_egui_texture(&gl, &integration.egui_ctx.font_image());
-                painter.paint_meshes(
+                painter.paint_primitives(
                     &gl,
-                    gl_window.window().inner_size().into(),
+                    gl_window.as_ref().unwrap().window().inner_size().into(),
                     integration.egui_ctx.pixels_per_point(),
-                    clipped_meshes,
+                    &clipped_primitives,
                 );
 
+                gl_window.as_ref().unwrap().swap_buffers().unwrap();
+            }
+
+            // Close the extra windows the app no longer shows:
+            let shown_ids: std::collections::HashSet<egui::Id> = extra_window_outputs
+                .iter()
+                .map(|extra_window_output| extra_window_output.id)
+                .collect();
+            extra_windows.retain(|id, extra_window| {
+                let keep = shown_ids.contains(id);
+                if !keep {
+                    make_current(&mut extra_window.gl_window);
+                    extra_window.painter.destroy(&extra_window.gl);
+                }
+                keep
+            });
+
+            for extra_window_output in extra_window_outputs {
+                let extra_window =
+                    extra_windows
+                        .entry(extra_window_output.id)
+                        .or_insert_with(|| {
+                            let window_builder =
+                                egui_winit::epi::extra_window_builder(&extra_window_output.options);
+                            let (gl_window, gl) = create_display(window_builder, event_loop);
+                            let mut painter = crate::Painter::new(&gl, None, "")
+                                .map_err(|error| {
+                                    eprintln!("some OpenGL error occurred {}\n", error);
+                                })
+                                .unwrap();
+                            // Textures created this frame are uploaded below.
+                            for (id, image) in &user_images {
+                                painter.set_texture(&gl, *id, image);
+                            }
+                            ExtraWindow {
+                                gl_window: Some(gl_window),
+                                gl,
+                                painter,
+                            }
+                        });
+                make_current(&mut extra_window.gl_window);
+                let ExtraWindow {
+                    gl_window,
+                    gl,
+                    painter,
+                } = extra_window;
+                let gl_window = gl_window.as_ref().unwrap();
+
+                for (id, image) in &tex_allocation_data.creations {
+                    painter.set_texture(gl, *id, image);
+                }
+
+                let egui_ctx = &extra_window_output.egui_ctx;
+                let clipped_primitives = egui_ctx.tessellate_primitives(extra_window_output.shapes);
+                let color = integration.app.clear_color();
+                unsafe {
+                    use glow::HasContext as _;
+                    gl.disable(glow::SCISSOR_TEST);
+                    gl.clear_color(color[0], color[1], color[2], color[3]);
+                    gl.clear(glow::COLOR_BUFFER_BIT);
+                }
+                painter.upload_egui_texture(gl, &egui_ctx.font_image());
+                painter.paint_primitives(
+                    gl,
+                    gl_window.window().inner_size().into(),
+                    egui_ctx.pixels_per_point(),
+                    &clipped_primitives,
+                );
                 gl_window.swap_buffers().unwrap();
+
+                needs_repaint |= extra_window_output.needs_repaint;
             }
 
+            user_images.extend(tex_allocation_data.creations.drain());
             for id in tex_allocation_data.destructions.drain(..) {
                 painter.free_texture(id);
+                for extra_window in extra_windows.values_mut() {
+                    extra_window.painter.free_texture(id);
+                }
+                user_images.remove(&id);
             }
 
             {
                 *control_flow = if integration.should_quit() {
                     glutin::event_loop::ControlFlow::Exit
                 } else if needs_repaint {
-                    gl_window.window().request_redraw();
+                    gl_window.as_ref().unwrap().window().request_redraw();
                     glutin::event_loop::ControlFlow::Poll
                 } else {
                     glutin::event_loop::ControlFlow::Wait
                 };
             }
 
-            integration.maybe_autosave(gl_window.window());
+            integration.maybe_autosave(gl_window.as_ref().unwrap().window());
         };
 
         match event {
@@ -133,7 +254,31 @@ pub fn run(app: Box<dyn epi::App>, native_options: &epi::NativeOptions) -> ! {
             // See: https://github.com/rust-windowing/winit/issues/987
             // See: https://github.com/rust-windowing/winit/issues/1619
             glutin::event::Event::RedrawEventsCleared if cfg!(windows) => redraw(),
-            glutin::event::Event::RedrawRequested(_) if !cfg!(windows) => redraw(),
+            glutin::event::Event::RedrawRequested(window_id)
+                if !cfg!(windows) && window_id == main_window_id =>
+            {
+                redraw();
+            }
+
+            glutin::event::Event::WindowEvent { window_id, event }
+                if window_id != main_window_id =>
+            {
+                // An event for an extra window. They are all painted together with the main window.
+                if let Some((id, extra_window)) = extra_windows
+                    .iter()
+                    .find(|(_, extra_window)| extra_window.window().id() == window_id)
+                {
+                    if let glutin::event::WindowEvent::Resized(physical_size) = event {
+                        extra_window
+                            .gl_window
+                            .as_ref()
+                            .unwrap()
+                            .resize(physical_size);
+                    }
+                    integration.on_extra_window_event(*id, &event);
+                }
+                gl_window.as_ref().unwrap().window().request_redraw();
+            }
 
             glutin::event::Event::WindowEvent { event, .. } => {
                 if let glutin::event::WindowEvent::Focused(new_focused) = event {
@@ -141,7 +286,7 @@ pub fn run(app: Box<dyn epi::App>, native_options: &epi::NativeOptions) -> ! {
                 }
 
                 if let glutin::event::WindowEvent::Resized(physical_size) = event {
-                    gl_window.resize(physical_size);
+                    gl_window.as_ref().unwrap().resize(physical_size);
                 }
 
                 integration.on_event(&event);
@@ -149,14 +294,19 @@ pub fn run(app: Box<dyn epi::App>, native_options: &epi::NativeOptions) -> ! {
                     *control_flow = glutin::event_loop::ControlFlow::Exit;
                 }
 
-                gl_window.window().request_redraw(); // TODO: ask egui if the events warrants a repaint instead
+                gl_window.as_ref().unwrap().window().request_redraw(); // TODO: ask egui if the events warrants a repaint instead
             }
             glutin::event::Event::LoopDestroyed => {
-                integration.on_exit(gl_window.window());
+                integration.on_exit(gl_window.as_ref().unwrap().window());
+                for extra_window in extra_windows.values_mut() {
+                    make_current(&mut extra_window.gl_window);
+                    extra_window.painter.destroy(&extra_window.gl);
+                }
+                make_current(&mut gl_window);
                 painter.destroy(&gl);
             }
             glutin::event::Event::UserEvent(RequestRepaintEvent) => {
-                gl_window.window().request_redraw();
+                gl_window.as_ref().unwrap().window().request_redraw();
             }
             _ => (),
         }
@@ -0,0 +1,402 @@
+#![allow(unsafe_code)]
+use crate::misc_util::{compile_shader, link_program};
+use crate::shader_version::ShaderVersion;
+use crate::vao_emulate::BufferInfo;
+use egui::epaint::{GroupEffects, Rgba};
+use glow::HasContext;
+
+/// Must match `MAX_RADIUS` in `shader/group_fragment.glsl`.
+const MAX_BLUR_RADIUS: f32 = 32.0;
+
+/// A texture the size of the screen that we can render into.
+struct RenderTarget {
+    fbo: glow::Framebuffer,
+    texture: glow::Texture,
+    size: (i32, i32),
+}
+
+impl RenderTarget {
+    unsafe fn new(
+        gl: &glow::Context,
+        (internal_format, format): (u32, u32),
+        size: (i32, i32),
+    ) -> Result<Self, String> {
+        let fbo = gl.create_framebuffer()?;
+        let texture = gl.create_texture()?;
+
+        gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+        for (parameter, value) in [
+            (glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE),
+            (glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE),
+            (glow::TEXTURE_MIN_FILTER, glow::NEAREST),
+            (glow::TEXTURE_MAG_FILTER, glow::NEAREST),
+        ] {
+            gl.tex_parameter_i32(glow::TEXTURE_2D, parameter, value as i32);
+        }
+
+        let mut target = Self { fbo, texture, size };
+        target.allocate(gl, (internal_format, format));
+
+        gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+        gl.framebuffer_texture_2d(
+            glow::FRAMEBUFFER,
+            glow::COLOR_ATTACHMENT0,
+            glow::TEXTURE_2D,
+            Some(texture),
+            0,
+        );
+        gl.bind_texture(glow::TEXTURE_2D, None);
+        Ok(target)
+    }
+
+    unsafe fn allocate(&mut self, gl: &glow::Context, (internal_format, format): (u32, u32)) {
+        gl.bind_texture(glow::TEXTURE_2D, Some(self.texture));
+        gl.tex_image_2d(
+            glow::TEXTURE_2D,
+            0,
+            internal_format as i32,
+            self.size.0,
+            self.size.1,
+            0,
+            format,
+            glow::UNSIGNED_BYTE,
+            None,
+        );
+    }
+
+    unsafe fn destroy(&self, gl: &glow::Context) {
+        gl.delete_framebuffer(self.fbo);
+        gl.delete_texture(self.texture);
+    }
+}
+
+/// The settings of one full-screen draw call of the group program.
+struct Pass {
+    /// Distance between blur samples, in pixels, along x or y.
+    step: [f32; 2],
+    /// In samples.
+    sigma: f32,
+    /// In pixels (y up, as in OpenGL).
+    offset: [f32; 2],
+    tint: Option<Rgba>,
+    opacity: f32,
+}
+
+/// Paints [`egui::epaint::GroupPrimitive`]s by rendering their content into a render target,
+/// and then compositing that with the [`GroupEffects`].
+pub(crate) struct GroupCompositor {
+    program: glow::Program,
+    u_sampler: glow::UniformLocation,
+    u_step: glow::UniformLocation,
+    u_sigma: glow::UniformLocation,
+    u_offset: glow::UniformLocation,
+    u_use_tint: glow::UniformLocation,
+    u_tint: glow::UniformLocation,
+    u_opacity: glow::UniformLocation,
+    pos_buffer: glow::Buffer,
+    index_buffer: glow::Buffer,
+    vertex_array: crate::misc_util::VAO,
+
+    /// `(internal_format, format)` of the render targets.
+    texture_format: (u32, u32),
+
+    /// If false, the render targets store gamma space colors.
+    linear_targets: bool,
+
+    /// Two for each nesting level of groups: one for the content, and one for blurring.
+    targets: Vec<RenderTarget>,
+}
+
+impl GroupCompositor {
+    /// `linear_targets` should be true if the egui shader outputs linear colors
+    /// (i.e. when `SRGB_SUPPORTED` is defined).
+    pub(crate) unsafe fn new(
+        gl: &glow::Context,
+        shader_version: ShaderVersion,
+        shader_prefix: &str,
+        need_to_emulate_vao: bool,
+        linear_targets: bool,
+        is_webgl_1: bool,
+    ) -> Result<GroupCompositor, String> {
+        let header = format!(
+            "{}\n{}\n{}",
+            shader_version.version(),
+            shader_prefix,
+            shader_version.is_new_shader_interface()
+        );
+        let vert_shader = compile_shader(
+            gl,
+            glow::VERTEX_SHADER,
+            &format!("{}\n{}", header, include_str!("shader/group_vertex.glsl")),
+        )?;
+        let frag_shader = compile_shader(
+            gl,
+            glow::FRAGMENT_SHADER,
+            &format!("{}\n{}", header, include_str!("shader/group_fragment.glsl")),
+        )?;
+        let program = link_program(gl, [vert_shader, frag_shader].iter())?;
+        gl.detach_shader(program, vert_shader);
+        gl.detach_shader(program, frag_shader);
+        gl.delete_shader(vert_shader);
+        gl.delete_shader(frag_shader);
+
+        let uniform = |name: &str| {
+            gl.get_uniform_location(program, name)
+                .ok_or_else(|| format!("failed to get location of {}", name))
+        };
+
+        let positions = vec![0.0f32, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let indices = vec![0u8, 1, 2, 1, 2, 3];
+
+        let pos_buffer = gl.create_buffer()?;
+        gl.bind_buffer(glow::ARRAY_BUFFER, Some(pos_buffer));
+        gl.buffer_data_u8_slice(
+            glow::ARRAY_BUFFER,
+            crate::misc_util::as_u8_slice(&positions),
+            glow::STATIC_DRAW,
+        );
+
+        let a_pos_loc = gl
+            .get_attrib_location(program, "a_pos")
+            .ok_or_else(|| "failed to get location of a_pos".to_string())?;
+        let mut vertex_array = if need_to_emulate_vao {
+            crate::misc_util::VAO::emulated()
+        } else {
+            crate::misc_util::VAO::native(gl)
+        };
+        vertex_array.bind_vertex_array(gl);
+        vertex_array.bind_buffer(gl, &pos_buffer);
+        vertex_array.add_new_attribute(
+            gl,
+            BufferInfo {
+                location: a_pos_loc,
+                vector_size: 2,
+                data_type: glow::FLOAT,
+                normalized: false,
+                stride: 0,
+                offset: 0,
+            },
+        );
+        vertex_array.unbind_vertex_array(gl);
+
+        let index_buffer = gl.create_buffer()?;
+        gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(index_buffer));
+        gl.buffer_data_u8_slice(glow::ELEMENT_ARRAY_BUFFER, &indices, glow::STATIC_DRAW);
+        gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, None);
+
+        let texture_format = match (linear_targets, is_webgl_1) {
+            (true, true) => (glow::SRGB_ALPHA, glow::SRGB_ALPHA),
+            (true, false) => (glow::SRGB8_ALPHA8, glow::RGBA),
+            (false, _) => (glow::RGBA, glow::RGBA),
+        };
+
+        let error_code = gl.get_error();
+        assert_eq!(
+            error_code,
+            glow::NO_ERROR,
+            "Error occurred in group compositor initialization. code : 0x{:x}",
+            error_code
+        );
+
+        Ok(GroupCompositor {
+            program,
+            u_sampler: uniform("u_sampler")?,
+            u_step: uniform("u_step")?,
+            u_sigma: uniform("u_sigma")?,
+            u_offset: uniform("u_offset")?,
+            u_use_tint: uniform("u_use_tint")?,
+            u_tint: uniform("u_tint")?,
+            u_opacity: uniform("u_opacity")?,
+            pos_buffer,
+            index_buffer,
+            vertex_array,
+            texture_format,
+            linear_targets,
+            targets: Vec::new(),
+        })
+    }
+
+    /// Bind and clear the render target for the content of a group at this nesting depth.
+    ///
+    /// Returns the framebuffer to paint the content into.
+    pub(crate) unsafe fn begin(
+        &mut self,
+        gl: &glow::Context,
+        [width, height]: [u32; 2],
+        depth: usize,
+    ) -> glow::Framebuffer {
+        let size = (width as i32, height as i32);
+        while self.targets.len() < 2 * (depth + 1) {
+            match RenderTarget::new(gl, self.texture_format, size) {
+                Ok(target) => self.targets.push(target),
+                Err(err) => panic!("Failed to create render target: {}", err),
+            }
+        }
+        for target in &mut self.targets[2 * depth..2 * (depth + 1)] {
+            if target.size != size {
+                target.size = size;
+                target.allocate(gl, self.texture_format);
+            }
+        }
+
+        let content = &self.targets[2 * depth];
+        gl.bind_framebuffer(glow::FRAMEBUFFER, Some(content.fbo));
+        gl.disable(glow::SCISSOR_TEST);
+        gl.clear_color(0.0, 0.0, 0.0, 0.0);
+        gl.clear(glow::COLOR_BUFFER_BIT);
+        content.fbo
+    }
+
+    /// Composite the content of the group at this nesting depth into `target`,
+    /// clipped to `scissor` (`[x, y, width, height]` in pixels, as given to `glScissor`).
+    ///
+    /// Changes the bound program, vertex array, texture and framebuffer.
+    pub(crate) unsafe fn end(
+        &self,
+        gl: &glow::Context,
+        pixels_per_point: f32,
+        depth: usize,
+        effects: &GroupEffects,
+        target: Option<glow::Framebuffer>,
+        scissor: [i32; 4],
+    ) {
+        let opacity = effects.opacity.clamp(0.0, 1.0);
+
+        gl.use_program(Some(self.program));
+        gl.uniform_1_i32(Some(&self.u_sampler), 0);
+        gl.active_texture(glow::TEXTURE0);
+        self.vertex_array.bind_vertex_array(gl);
+        gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(self.index_buffer));
+
+        if let Some(shadow) = effects.shadow {
+            let tint = if self.linear_targets {
+                Rgba::from(shadow.color)
+            } else {
+                let [r, g, b, a] = shadow.color.to_array();
+                Rgba::from_rgba_premultiplied(
+                    r as f32 / 255.0,
+                    g as f32 / 255.0,
+                    b as f32 / 255.0,
+                    a as f32 / 255.0,
+                )
+            };
+            let offset = shadow.offset * pixels_per_point;
+            self.blur_into(
+                gl,
+                depth,
+                shadow.blur * pixels_per_point,
+                target,
+                scissor,
+                [offset.x, -offset.y],
+                Some(tint),
+                opacity,
+            );
+        }
+
+        self.blur_into(
+            gl,
+            depth,
+            effects.blur * pixels_per_point,
+            target,
+            scissor,
+            [0.0, 0.0],
+            None,
+            opacity,
+        );
+
+        self.vertex_array.unbind_vertex_array(gl);
+        gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, None);
+        gl.bind_texture(glow::TEXTURE_2D, None);
+    }
+
+    /// Blur the content (with `sigma` in pixels) in two passes: horizontally into the scratch target,
+    /// and then vertically into `target`, where the offset, tint and opacity are also applied.
+    #[allow(clippy::too_many_arguments)]
+    unsafe fn blur_into(
+        &self,
+        gl: &glow::Context,
+        depth: usize,
+        sigma: f32,
+        target: Option<glow::Framebuffer>,
+        scissor: [i32; 4],
+        offset: [f32; 2],
+        tint: Option<Rgba>,
+        opacity: f32,
+    ) {
+        let content = &self.targets[2 * depth];
+        let scratch = &self.targets[2 * depth + 1];
+
+        // Spread out the samples if the blur is wider than the shader can handle:
+        let step = (3.0 * sigma / MAX_BLUR_RADIUS).max(1.0);
+        let sigma = sigma.max(0.0) / step;
+
+        let source = if sigma > 0.0 {
+            gl.bind_framebuffer(glow::FRAMEBUFFER, Some(scratch.fbo));
+            gl.disable(glow::SCISSOR_TEST);
+            gl.clear_color(0.0, 0.0, 0.0, 0.0);
+            gl.clear(glow::COLOR_BUFFER_BIT);
+            self.draw(
+                gl,
+                content,
+                &Pass {
+                    step: [step, 0.0],
+                    sigma,
+                    offset: [0.0, 0.0],
+                    tint: None,
+                    opacity: 1.0,
+                },
+            );
+            scratch
+        } else {
+            content
+        };
+
+        gl.bind_framebuffer(glow::FRAMEBUFFER, target);
+        gl.enable(glow::SCISSOR_TEST);
+        let [x, y, width, height] = scissor;
+        gl.scissor(x, y, width, height);
+        self.draw(
+            gl,
+            source,
+            &Pass {
+                step: [0.0, step],
+                sigma,
+                offset,
+                tint,
+                opacity,
+            },
+        );
+    }
+
+    /// Draw a full-screen quad sampling from `source`.
+    unsafe fn draw(&self, gl: &glow::Context, source: &RenderTarget, pass: &Pass) {
+        let (width, height) = (source.size.0 as f32, source.size.1 as f32);
+        gl.bind_texture(glow::TEXTURE_2D, Some(source.texture));
+        gl.uniform_2_f32(
+            Some(&self.u_step),
+            pass.step[0] / width,
+            pass.step[1] / height,
+        );
+        gl.uniform_1_f32(Some(&self.u_sigma), pass.sigma);
+        gl.uniform_2_f32(
+            Some(&self.u_offset),
+            pass.offset[0] / width,
+            pass.offset[1] / height,
+        );
+        let use_tint = if pass.tint.is_some() { 1.0 } else { 0.0 };
+        let tint = pass.tint.unwrap_or(Rgba::WHITE);
+        gl.uniform_1_f32(Some(&self.u_use_tint), use_tint);
+        gl.uniform_4_f32(Some(&self.u_tint), tint.r(), tint.g(), tint.b(), tint.a());
+        gl.uniform_1_f32(Some(&self.u_opacity), pass.opacity);
+        gl.draw_elements(glow::TRIANGLES, 6, glow::UNSIGNED_BYTE, 0);
+    }
+
+    pub(crate) unsafe fn destroy(&self, gl: &glow::Context) {
+        gl.delete_buffer(self.pos_buffer);
+        gl.delete_buffer(self.index_buffer);
+        gl.delete_program(self.program);
+        for target in &self.targets {
+            target.destroy(gl);
+        }
+    }
+}
@@ -92,6 +92,7 @@ pub use glow;
 pub use painter::Painter;
 #[cfg(feature = "winit")]
 mod epi_backend;
+mod group_compositor;
 mod misc_util;
 mod post_process;
 mod shader_version;
@@ -161,15 +162,15 @@ impl EguiGlow {
         gl: &glow::Context,
         shapes: Vec<egui::epaint::ClippedShape>,
     ) {
-        let clipped_meshes = self.egui_ctx.tessellate(shapes);
+        let clipped_primitives = self.egui_ctx.tessellate_primitives(shapes);
         let dimensions: [u32; 2] = gl_window.window().inner_size().into();
         self.painter
             .upload_egui_texture(gl, &self.egui_ctx.font_image());
-        self.painter.paint_meshes(
+        self.painter.paint_primitives(
             gl,
             dimensions,
             self.egui_ctx.pixels_per_point(),
-            clipped_meshes,
+            &clipped_primitives,
         );
     }
 
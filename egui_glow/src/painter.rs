@@ -4,11 +4,12 @@ use std::collections::HashMap;
 
 use egui::{
     emath::Rect,
-    epaint::{Color32, Mesh, Vertex},
+    epaint::{ClippedPrimitive, Color32, Mesh, Primitive, Vertex},
 };
 use glow::HasContext;
 use memoffset::offset_of;
 
+use crate::group_compositor::GroupCompositor;
 use crate::misc_util::{
     as_u8_slice, compile_shader, glow_debug_print, link_program, srgbtexture2d,
 };
@@ -38,6 +39,8 @@ pub struct Painter {
     /// The filter used for subsequent textures.
     texture_filter: TextureFilter,
     post_process: Option<PostProcess>,
+    /// `None` if render targets are not supported, in which case groups are painted without effects.
+    group_compositor: Option<GroupCompositor>,
     vertex_buffer: glow::Buffer,
     element_array_buffer: glow::Buffer,
 
@@ -203,6 +206,22 @@ impl Painter {
             vertex_array.add_new_attribute(gl, color_buffer_info);
             assert_eq!(gl.get_error(), glow::NO_ERROR, "OpenGL error occurred!");
 
+            let group_compositor = GroupCompositor::new(
+                gl,
+                shader_version,
+                shader_prefix,
+                !support_vao,
+                !srgb_support_define.is_empty(),
+                is_webgl_1,
+            )
+            .map_err(|err| {
+                glow_debug_print(format!(
+                    "Failed to create group compositor, so groups will be painted without effects: {}",
+                    err
+                ));
+            })
+            .ok();
+
             Ok(Painter {
                 program,
                 u_screen_size,
@@ -215,6 +234,7 @@ impl Painter {
                 srgb_support,
                 texture_filter: Default::default(),
                 post_process,
+                group_compositor,
                 vertex_buffer,
                 element_array_buffer,
                 user_textures: Default::default(),
@@ -350,6 +370,110 @@ impl Painter {
         }
     }
 
+    /// Like [`Self::paint_meshes`], but also paints [`egui::epaint::GroupPrimitive`]s
+    /// (see [`egui::Context::tessellate_primitives`]), by rendering them into offscreen render targets.
+    ///
+    /// This changes the same OpenGL state as [`Self::paint_meshes`], and also the bound framebuffer.
+    pub fn paint_primitives(
+        &mut self,
+        gl: &glow::Context,
+        inner_size: [u32; 2],
+        pixels_per_point: f32,
+        clipped_primitives: &[ClippedPrimitive],
+    ) {
+        self.assert_not_destroyed();
+
+        if let Some(ref mut post_process) = self.post_process {
+            unsafe {
+                post_process.begin(gl, inner_size[0] as i32, inner_size[1] as i32);
+            }
+        }
+        let screen = self
+            .post_process
+            .as_ref()
+            .map(|post_process| post_process.framebuffer());
+        unsafe { self.prepare_painting(inner_size, gl, pixels_per_point) };
+        self.paint_clipped_primitives(
+            gl,
+            inner_size,
+            pixels_per_point,
+            clipped_primitives,
+            screen,
+            0,
+        );
+        unsafe {
+            self.vertex_array.unbind_vertex_array(gl);
+            gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, None);
+
+            if let Some(ref post_process) = self.post_process {
+                post_process.end(gl);
+            }
+
+            gl.disable(glow::SCISSOR_TEST);
+
+            assert_eq!(glow::NO_ERROR, gl.get_error(), "GL error occurred!");
+        }
+    }
+
+    /// `target` is the framebuffer we are painting into, and `depth` how many groups we are inside.
+    fn paint_clipped_primitives(
+        &mut self,
+        gl: &glow::Context,
+        inner_size: [u32; 2],
+        pixels_per_point: f32,
+        clipped_primitives: &[ClippedPrimitive],
+        target: Option<glow::Framebuffer>,
+        depth: usize,
+    ) {
+        let size_in_pixels = (inner_size[0], inner_size[1]);
+        for ClippedPrimitive(clip_rect, primitive) in clipped_primitives {
+            match primitive {
+                Primitive::Mesh(mesh) => {
+                    self.paint_mesh(gl, size_in_pixels, pixels_per_point, *clip_rect, mesh);
+                }
+                Primitive::Group(group) if self.group_compositor.is_none() => {
+                    self.paint_clipped_primitives(
+                        gl,
+                        inner_size,
+                        pixels_per_point,
+                        &group.primitives,
+                        target,
+                        depth,
+                    );
+                }
+                Primitive::Group(group) => {
+                    unsafe {
+                        let compositor = self.group_compositor.as_mut().unwrap();
+                        let content = compositor.begin(gl, inner_size, depth);
+                        self.prepare_painting(inner_size, gl, pixels_per_point);
+                        self.paint_clipped_primitives(
+                            gl,
+                            inner_size,
+                            pixels_per_point,
+                            &group.primitives,
+                            Some(content),
+                            depth + 1,
+                        );
+
+                        let scissor =
+                            clip_rect_in_pixels(size_in_pixels, pixels_per_point, *clip_rect);
+                        self.group_compositor.as_ref().unwrap().end(
+                            gl,
+                            pixels_per_point,
+                            depth,
+                            &group.effects,
+                            target,
+                            scissor,
+                        );
+
+                        // Back to painting meshes into the target:
+                        self.prepare_painting(inner_size, gl, pixels_per_point);
+                    }
+                }
+            }
+        }
+    }
+
     #[inline(never)] // Easier profiling
     fn paint_mesh(
         &mut self,
@@ -378,30 +502,11 @@ impl Painter {
 
                 gl.bind_texture(glow::TEXTURE_2D, Some(texture));
             }
-            // Transform clip rect to physical pixels:
-            let clip_min_x = pixels_per_point * clip_rect.min.x;
-            let clip_min_y = pixels_per_point * clip_rect.min.y;
-            let clip_max_x = pixels_per_point * clip_rect.max.x;
-            let clip_max_y = pixels_per_point * clip_rect.max.y;
-
-            // Make sure clip rect can fit within a `u32`:
-            let clip_min_x = clip_min_x.clamp(0.0, size_in_pixels.0 as f32);
-            let clip_min_y = clip_min_y.clamp(0.0, size_in_pixels.1 as f32);
-            let clip_max_x = clip_max_x.clamp(clip_min_x, size_in_pixels.0 as f32);
-            let clip_max_y = clip_max_y.clamp(clip_min_y, size_in_pixels.1 as f32);
-
-            let clip_min_x = clip_min_x.round() as i32;
-            let clip_min_y = clip_min_y.round() as i32;
-            let clip_max_x = clip_max_x.round() as i32;
-            let clip_max_y = clip_max_y.round() as i32;
+            let [x, y, width, height] =
+                clip_rect_in_pixels(size_in_pixels, pixels_per_point, clip_rect);
 
             unsafe {
-                gl.scissor(
-                    clip_min_x,
-                    size_in_pixels.1 as i32 - clip_max_y,
-                    clip_max_x - clip_min_x,
-                    clip_max_y - clip_min_y,
-                );
+                gl.scissor(x, y, width, height);
                 gl.draw_elements(
                     glow::TRIANGLES,
                     mesh.indices.len() as i32,
@@ -490,6 +595,9 @@ impl Painter {
                 if let Some(ref post_process) = self.post_process {
                     post_process.destroy(gl);
                 }
+                if let Some(ref group_compositor) = self.group_compositor {
+                    group_compositor.destroy(gl);
+                }
             }
             self.destroyed = true;
         }
@@ -500,6 +608,37 @@ impl Painter {
     }
 }
 
+/// The clip rectangle in physical pixels, as `[x, y, width, height]` for `glScissor`.
+fn clip_rect_in_pixels(
+    size_in_pixels: (u32, u32),
+    pixels_per_point: f32,
+    clip_rect: Rect,
+) -> [i32; 4] {
+    // Transform clip rect to physical pixels:
+    let clip_min_x = pixels_per_point * clip_rect.min.x;
+    let clip_min_y = pixels_per_point * clip_rect.min.y;
+    let clip_max_x = pixels_per_point * clip_rect.max.x;
+    let clip_max_y = pixels_per_point * clip_rect.max.y;
+
+    // Make sure clip rect can fit within a `u32`:
+    let clip_min_x = clip_min_x.clamp(0.0, size_in_pixels.0 as f32);
+    let clip_min_y = clip_min_y.clamp(0.0, size_in_pixels.1 as f32);
+    let clip_max_x = clip_max_x.clamp(clip_min_x, size_in_pixels.0 as f32);
+    let clip_max_y = clip_max_y.clamp(clip_min_y, size_in_pixels.1 as f32);
+
+    let clip_min_x = clip_min_x.round() as i32;
+    let clip_min_y = clip_min_y.round() as i32;
+    let clip_max_x = clip_max_x.round() as i32;
+    let clip_max_y = clip_max_y.round() as i32;
+
+    [
+        clip_min_x,
+        size_in_pixels.1 as i32 - clip_max_y,
+        clip_max_x - clip_min_x,
+        clip_max_y - clip_min_y,
+    ]
+}
+
 pub fn clear(gl: &glow::Context, dimension: [u32; 2], clear_color: egui::Rgba) {
     unsafe {
         gl.disable(glow::SCISSOR_TEST);
@@ -202,6 +202,11 @@ impl PostProcess {
         gl.clear(glow::COLOR_BUFFER_BIT);
     }
 
+    /// Where everything is painted before the post processing.
+    pub(crate) fn framebuffer(&self) -> glow::Framebuffer {
+        self.fbo
+    }
+
     pub(crate) unsafe fn end(&self, gl: &glow::Context) {
         gl.bind_framebuffer(glow::FRAMEBUFFER, None);
         gl.disable(glow::SCISSOR_TEST);
@@ -0,0 +1,58 @@
+#ifdef GL_ES
+    precision mediump float;
+#endif
+
+uniform sampler2D u_sampler;
+uniform vec2 u_step;        // Distance between blur samples, in texture coordinates
+uniform float u_sigma;      // Standard deviation of the blur, in samples. 0 = no blur
+uniform vec2 u_offset;      // Move the image by this much, in texture coordinates
+uniform float u_use_tint;   // If 1, replace the color with the tint times the alpha
+uniform vec4 u_tint;
+uniform float u_opacity;
+
+#ifdef NEW_SHADER_INTERFACE
+    in vec2 v_tc;
+    out vec4 f_color;
+    // a dirty hack applied to support webGL2
+    #define gl_FragColor f_color
+    #define texture2D texture
+#else
+    varying vec2 v_tc;
+#endif
+
+// Must match `MAX_BLUR_RADIUS` in group_compositor.rs
+const int MAX_RADIUS = 32;
+
+// Transparent outside of the texture (instead of clamping to the edge).
+vec4 sample_texture(vec2 tc) {
+    vec2 inside = step(vec2(0.0), tc) * step(tc, vec2(1.0));
+    return inside.x * inside.y * texture2D(u_sampler, tc);
+}
+
+void main() {
+    vec2 tc = v_tc - u_offset;
+
+    vec4 color;
+    if (u_sigma > 0.0) {
+        color = vec4(0.0);
+        float weight_sum = 0.0;
+        for (int i = -MAX_RADIUS; i <= MAX_RADIUS; i++) {
+            float x = float(i);
+            if (abs(x) <= 3.0 * u_sigma) {
+                float weight = exp(-0.5 * x * x / (u_sigma * u_sigma));
+                color += weight * sample_texture(tc + x * u_step);
+                weight_sum += weight;
+            }
+        }
+        color /= weight_sum;
+    } else {
+        color = sample_texture(tc);
+    }
+
+    if (u_use_tint > 0.5) {
+        color = u_tint * color.a;
+    }
+
+    // Premultiplied alpha, so the opacity applies to all channels:
+    gl_FragColor = u_opacity * color;
+}
@@ -112,9 +112,14 @@ impl AppRunner {
                 prefer_dark_mode,
                 cpu_usage: None,
                 native_pixels_per_point: Some(native_pixels_per_point()),
+                window_info: None,
+                monitors: vec![],
             },
             output: Default::default(),
             repaint_signal: needs_repaint.clone(),
+            windows: None,
+            desktop: None,
+            desktop_responses: Default::default(),
         });
 
         let egui_ctx = egui::CtxRef::default();
@@ -183,7 +188,7 @@ impl AppRunner {
         Ok(())
     }
 
-    pub fn logic(&mut self) -> Result<(egui::Output, Vec<egui::ClippedMesh>), JsValue> {
+    pub fn logic(&mut self) -> Result<(egui::Output, Vec<egui::ClippedPrimitive>), JsValue> {
         let frame_start = now_sec();
 
         resize_canvas_to_screen_size(self.canvas_id(), self.app.max_size_points());
@@ -193,18 +198,24 @@ impl AppRunner {
         let (egui_output, shapes) = self.egui_ctx.run(raw_input, |egui_ctx| {
             self.app.update(egui_ctx, &self.frame);
         });
-        let clipped_meshes = self.egui_ctx.tessellate(shapes);
+        let clipped_primitives = self.egui_ctx.tessellate_primitives(shapes);
 
         self.handle_egui_output(&egui_output);
 
         {
             let app_output = self.frame.take_app_output();
             let epi::backend::AppOutput {
-                quit: _,         // Can't quit a web page
-                window_size: _,  // Can't resize a web page
-                window_title: _, // TODO: change title of window
-                decorated: _,    // Can't toggle decorations
-                drag_window: _,  // Can't be dragged
+                quit: _,            // Can't quit a web page
+                window_size: _,     // Can't resize a web page
+                window_title: _,    // TODO: change title of window
+                decorated: _,       // Can't toggle decorations
+                fullscreen: _,      // TODO: use the Fullscreen API of the browser
+                maximized: _,       // Can't maximize a web page
+                minimized: _,       // Can't minimize a web page
+                window_pos: _,      // Can't move a web page
+                min_window_size: _, // Can't limit the size of a web page
+                max_window_size: _, // Can't limit the size of a web page
+                drag_window: _,     // Can't be dragged
                 tex_allocation_data,
             } = app_output;
 
@@ -215,15 +226,18 @@ impl AppRunner {
         }
 
         self.frame.lock().info.cpu_usage = Some((now_sec() - frame_start) as f32);
-        Ok((egui_output, clipped_meshes))
+        Ok((egui_output, clipped_primitives))
     }
 
-    pub fn paint(&mut self, clipped_meshes: Vec<egui::ClippedMesh>) -> Result<(), JsValue> {
+    pub fn paint(
+        &mut self,
+        clipped_primitives: Vec<egui::ClippedPrimitive>,
+    ) -> Result<(), JsValue> {
         self.painter
             .upload_egui_texture(&self.egui_ctx.font_image());
         self.painter.clear(self.app.clear_color());
         self.painter
-            .paint_meshes(clipped_meshes, self.egui_ctx.pixels_per_point())?;
+            .paint_primitives(clipped_primitives, self.egui_ctx.pixels_per_point())?;
         for id in self.pending_texture_destructions.drain(..) {
             self.painter.free_texture(id);
         }
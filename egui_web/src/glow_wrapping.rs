@@ -1,5 +1,5 @@
 use crate::{canvas_element_or_die, console_error};
-use egui::{ClippedMesh, FontImage, Rgba};
+use egui::{ClippedPrimitive, FontImage, Rgba};
 use egui_glow::glow;
 use wasm_bindgen::JsCast;
 use wasm_bindgen::JsValue;
@@ -95,17 +95,17 @@ impl crate::Painter for WrappedGlowPainter {
         egui_glow::painter::clear(&self.gl_ctx, canvas_dimension, clear_color)
     }
 
-    fn paint_meshes(
+    fn paint_primitives(
         &mut self,
-        clipped_meshes: Vec<ClippedMesh>,
+        clipped_primitives: Vec<ClippedPrimitive>,
         pixels_per_point: f32,
     ) -> Result<(), JsValue> {
         let canvas_dimension = [self.canvas.width(), self.canvas.height()];
-        self.painter.paint_meshes(
+        self.painter.paint_primitives(
             &self.gl_ctx,
             canvas_dimension,
             pixels_per_point,
-            clipped_meshes,
+            &clipped_primitives,
         );
         Ok(())
     }
@@ -482,8 +482,8 @@ fn paint_and_schedule(runner_ref: AppRunnerRef) -> Result<(), JsValue> {
     fn paint_if_needed(runner_ref: &AppRunnerRef) -> Result<(), JsValue> {
         let mut runner_lock = runner_ref.0.lock();
         if runner_lock.needs_repaint.fetch_and_clear() {
-            let (output, clipped_meshes) = runner_lock.logic()?;
-            runner_lock.paint(clipped_meshes)?;
+            let (output, clipped_primitives) = runner_lock.logic()?;
+            runner_lock.paint(clipped_primitives)?;
             if output.needs_repaint {
                 runner_lock.needs_repaint.set_true();
             }
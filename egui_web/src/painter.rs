@@ -14,9 +14,13 @@ pub trait Painter {
 
     fn clear(&mut self, clear_color: egui::Rgba);
 
-    fn paint_meshes(
+    /// Paint the output of [`egui::Context::tessellate_primitives`].
+    ///
+    /// The `glow` backend composites shape groups ([`egui::epaint::GroupShape`]) properly.
+    /// The `webgl` backends only approximate them with [`egui::epaint::approximate_primitives`].
+    fn paint_primitives(
         &mut self,
-        clipped_meshes: Vec<egui::ClippedMesh>,
+        clipped_primitives: Vec<egui::ClippedPrimitive>,
         pixels_per_point: f32,
     ) -> Result<(), JsValue>;
 
@@ -364,11 +364,13 @@ impl crate::Painter for WebGl2Painter {
         gl.clear(Gl::COLOR_BUFFER_BIT);
     }
 
-    fn paint_meshes(
+    fn paint_primitives(
         &mut self,
-        clipped_meshes: Vec<egui::ClippedMesh>,
+        clipped_primitives: Vec<egui::ClippedPrimitive>,
         pixels_per_point: f32,
     ) -> Result<(), JsValue> {
+        // This backend can't composite groups:
+        let clipped_meshes = egui::epaint::approximate_primitives(clipped_primitives);
         let gl = &self.gl;
 
         self.post_process
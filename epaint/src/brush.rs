@@ -0,0 +1,283 @@
+#![allow(clippy::derive_hash_xor_eq)] // We need to impl Hash for f32, but we don't implement Eq, which is fine
+
+use crate::*;
+use emath::*;
+
+/// How to fill the inside of a [`RectShape`], [`CircleShape`] or [`PathShape`].
+///
+/// All positions are in screen space (the same space as the shape),
+/// so a gradient does not follow the shape around unless you also move the brush
+/// (as [`Shape::translate`] does).
+///
+/// Any [`Color32`] can be turned into a solid brush with `.into()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Brush {
+    /// One color everywhere.
+    Solid(Color32),
+
+    /// Goes from `start_color` at `start` to `end_color` at `end`.
+    ///
+    /// The colors are extended beyond the end points.
+    LinearGradient {
+        start: Pos2,
+        end: Pos2,
+        start_color: Color32,
+        end_color: Color32,
+    },
+
+    /// Goes from `inner_color` at `center` to `outer_color` at `radius` from it.
+    ///
+    /// The outer color is extended beyond the radius.
+    RadialGradient {
+        center: Pos2,
+        radius: f32,
+        inner_color: Color32,
+        outer_color: Color32,
+    },
+
+    /// Paint a texture, with the region `uv` (in normalized 0-1 texture coordinates)
+    /// stretched over `rect` (in screen space).
+    ///
+    /// The texture is multiplied by `tint`. Use [`Color32::WHITE`] to leave it as-is.
+    ///
+    /// The stroke of a shape with a textured fill is tessellated into a separate mesh
+    /// by [`tessellate_shapes`].
+    Texture {
+        texture_id: TextureId,
+        rect: Rect,
+        uv: Rect,
+        tint: Color32,
+    },
+}
+
+impl std::hash::Hash for Brush {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match *self {
+            Brush::Solid(color) => color.hash(state),
+            Brush::LinearGradient {
+                start,
+                end,
+                start_color,
+                end_color,
+            } => {
+                crate::pos2_hash(state, start);
+                crate::pos2_hash(state, end);
+                start_color.hash(state);
+                end_color.hash(state);
+            }
+            Brush::RadialGradient {
+                center,
+                radius,
+                inner_color,
+                outer_color,
+            } => {
+                crate::pos2_hash(state, center);
+                crate::f32_hash(state, radius);
+                inner_color.hash(state);
+                outer_color.hash(state);
+            }
+            Brush::Texture {
+                texture_id,
+                rect,
+                uv,
+                tint,
+            } => {
+                texture_id.hash(state);
+                crate::rect_hash(state, rect);
+                crate::rect_hash(state, uv);
+                tint.hash(state);
+            }
+        }
+    }
+}
+
+impl Default for Brush {
+    fn default() -> Self {
+        Self::Solid(Color32::TRANSPARENT)
+    }
+}
+
+impl From<Color32> for Brush {
+    #[inline(always)]
+    fn from(color: Color32) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl Brush {
+    pub fn linear_gradient(
+        [start, end]: [Pos2; 2],
+        start_color: impl Into<Color32>,
+        end_color: impl Into<Color32>,
+    ) -> Self {
+        Self::LinearGradient {
+            start,
+            end,
+            start_color: start_color.into(),
+            end_color: end_color.into(),
+        }
+    }
+
+    pub fn radial_gradient(
+        center: Pos2,
+        radius: f32,
+        inner_color: impl Into<Color32>,
+        outer_color: impl Into<Color32>,
+    ) -> Self {
+        Self::RadialGradient {
+            center,
+            radius,
+            inner_color: inner_color.into(),
+            outer_color: outer_color.into(),
+        }
+    }
+
+    /// Stretch the whole texture over `rect`.
+    pub fn texture(texture_id: TextureId, rect: Rect) -> Self {
+        Self::Texture {
+            texture_id,
+            rect,
+            uv: Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+            tint: Color32::WHITE,
+        }
+    }
+
+    /// Does this brush paint nothing?
+    pub fn is_transparent(&self) -> bool {
+        match *self {
+            Self::Solid(color) | Self::Texture { tint: color, .. } => color == Color32::TRANSPARENT,
+            Self::LinearGradient {
+                start_color,
+                end_color,
+                ..
+            } => start_color == Color32::TRANSPARENT && end_color == Color32::TRANSPARENT,
+            Self::RadialGradient {
+                inner_color,
+                outer_color,
+                ..
+            } => inner_color == Color32::TRANSPARENT && outer_color == Color32::TRANSPARENT,
+        }
+    }
+
+    /// The texture this brush samples from.
+    pub fn texture_id(&self) -> TextureId {
+        match self {
+            Self::Texture { texture_id, .. } => *texture_id,
+            _ => TextureId::Egui,
+        }
+    }
+
+    /// The color of the brush at this position.
+    ///
+    /// For [`Self::Texture`] this is the tint.
+    pub fn color_at(&self, pos: Pos2) -> Color32 {
+        match *self {
+            Self::Solid(color) | Self::Texture { tint: color, .. } => color,
+            Self::LinearGradient {
+                start,
+                end,
+                start_color,
+                end_color,
+            } => {
+                let dir = end - start;
+                let t = if dir.length_sq() > 0.0 {
+                    let offset = pos - start;
+                    (offset.x * dir.x + offset.y * dir.y) / dir.length_sq()
+                } else {
+                    1.0
+                };
+                lerp_color(start_color, end_color, t)
+            }
+            Self::RadialGradient {
+                center,
+                radius,
+                inner_color,
+                outer_color,
+            } => {
+                let t = if radius > 0.0 {
+                    pos.distance(center) / radius
+                } else {
+                    1.0
+                };
+                lerp_color(inner_color, outer_color, t)
+            }
+        }
+    }
+
+    /// The texture coordinate at this position.
+    pub fn uv_at(&self, pos: Pos2) -> Pos2 {
+        match *self {
+            Self::Texture { rect, uv, .. } => pos2(
+                remap(pos.x, rect.x_range(), uv.x_range()),
+                remap(pos.y, rect.y_range(), uv.y_range()),
+            ),
+            _ => WHITE_UV,
+        }
+    }
+
+    /// A vertex at this position, colored by the brush.
+    #[inline]
+    pub(crate) fn vertex(&self, pos: Pos2) -> Vertex {
+        Vertex {
+            pos,
+            uv: self.uv_at(pos),
+            color: self.color_at(pos),
+        }
+    }
+
+    /// The color is not linear across the shape, so large triangles must be split up
+    /// into triangles no larger than this.
+    pub(crate) fn max_triangle_size(&self) -> Option<f32> {
+        match self {
+            Self::Solid(_) | Self::Texture { .. } => None,
+            Self::LinearGradient { start, end, .. } => Some(start.distance(*end)),
+            Self::RadialGradient { radius, .. } => Some(*radius),
+        }
+        .map(|size| (size / 8.0).at_least(2.0))
+    }
+
+    /// Move the brush by this many points.
+    pub fn translate(&mut self, delta: Vec2) {
+        match self {
+            Self::Solid(_) => {}
+            Self::LinearGradient { start, end, .. } => {
+                *start += delta;
+                *end += delta;
+            }
+            Self::RadialGradient { center, .. } => {
+                *center += delta;
+            }
+            Self::Texture { rect, .. } => {
+                *rect = rect.translate(delta);
+            }
+        }
+    }
+
+    /// Call the given function on every color of the brush.
+    pub fn adjust_colors(&mut self, adjust_color: &impl Fn(&mut Color32)) {
+        match self {
+            Self::Solid(color) | Self::Texture { tint: color, .. } => adjust_color(color),
+            Self::LinearGradient {
+                start_color: a,
+                end_color: b,
+                ..
+            }
+            | Self::RadialGradient {
+                inner_color: a,
+                outer_color: b,
+                ..
+            } => {
+                adjust_color(a);
+                adjust_color(b);
+            }
+        }
+    }
+}
+
+/// Blend in linear space.
+fn lerp_color(a: Color32, b: Color32, t: f32) -> Color32 {
+    let t = t.clamp(0.0, 1.0);
+    (Rgba::from(a) * (1.0 - t) + Rgba::from(b) * t).into()
+}
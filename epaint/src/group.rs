@@ -0,0 +1,222 @@
+#![allow(clippy::derive_hash_xor_eq)] // We need to impl Hash for f32, but we don't implement Eq, which is fine
+
+use crate::*;
+use emath::*;
+
+/// Effects applied to a [`GroupShape`] as a whole.
+///
+/// The shapes of the group are first painted on top of each other into an offscreen image,
+/// which is then blurred, given a shadow and faded before it is painted on screen.
+/// This means overlapping shapes in a faded group don't show through each other.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct GroupEffects {
+    /// Multiplied with the alpha of the whole group. Default: `1.0`.
+    pub opacity: f32,
+
+    /// Standard deviation of a gaussian blur of the group, in points. Default: `0.0` (no blur).
+    pub blur: f32,
+
+    /// Painted behind the group.
+    pub shadow: Option<DropShadow>,
+}
+
+impl Default for GroupEffects {
+    fn default() -> Self {
+        Self {
+            opacity: 1.0,
+            blur: 0.0,
+            shadow: None,
+        }
+    }
+}
+
+impl std::hash::Hash for GroupEffects {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            opacity,
+            blur,
+            shadow,
+        } = *self;
+        crate::f32_hash(state, opacity);
+        crate::f32_hash(state, blur);
+        shadow.hash(state);
+    }
+}
+
+impl GroupEffects {
+    /// Does this leave the group as if it was painted shape by shape?
+    pub fn is_noop(&self) -> bool {
+        self.opacity >= 1.0 && self.blur <= 0.0 && self.shadow.is_none()
+    }
+
+    /// How far outside the shapes of the group the effects can reach, in points.
+    pub fn margin(&self) -> f32 {
+        let shadow_margin = self.shadow.map_or(0.0, |shadow| {
+            shadow.offset.x.abs().max(shadow.offset.y.abs()) + 3.0 * shadow.blur
+        });
+        (3.0 * self.blur).max(shadow_margin)
+    }
+}
+
+/// A blurred silhouette of a [`GroupShape`], painted behind it.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DropShadow {
+    /// How far the shadow is moved from the group, in points.
+    pub offset: Vec2,
+
+    /// Standard deviation of the gaussian blur of the shadow, in points.
+    pub blur: f32,
+
+    /// The color of the shadow where the group is opaque.
+    pub color: Color32,
+}
+
+impl DropShadow {
+    pub fn new(offset: Vec2, blur: f32, color: impl Into<Color32>) -> Self {
+        Self {
+            offset,
+            blur,
+            color: color.into(),
+        }
+    }
+}
+
+impl std::hash::Hash for DropShadow {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            offset,
+            blur,
+            color,
+        } = *self;
+        crate::f32_hash(state, offset.x);
+        crate::f32_hash(state, offset.y);
+        crate::f32_hash(state, blur);
+        color.hash(state);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A group of [`Mesh`]es with [`GroupEffects`].
+///
+/// A backend paints the primitives of the group into an offscreen render target
+/// (with the same size as the screen), and then composites it using the effects:
+///
+/// 1. The shadow: the alpha of the render target, blurred by [`DropShadow::blur`],
+///    multiplied by [`DropShadow::color`] and moved by [`DropShadow::offset`].
+/// 2. The render target itself, blurred by [`GroupEffects::blur`].
+///
+/// Both are multiplied by [`GroupEffects::opacity`] and clipped to the clip rectangle of the group.
+///
+/// See [`crate::raster`] for a reference implementation on the CPU.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct GroupPrimitive {
+    pub primitives: Vec<ClippedPrimitive>,
+    pub effects: GroupEffects,
+}
+
+/// Something a backend can paint: either a [`Mesh`], or a [`GroupPrimitive`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Primitive {
+    Mesh(Mesh),
+    Group(GroupPrimitive),
+}
+
+/// A [`Primitive`] within a clip rectangle.
+///
+/// Everything is using logical points.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ClippedPrimitive(
+    /// Clip / scissor rectangle.
+    /// Only show the part of the [`Primitive`] that falls within this.
+    pub Rect,
+    pub Primitive,
+);
+
+// ----------------------------------------------------------------------------
+
+/// Approximate the effects of a group without an offscreen render target.
+///
+/// This is what [`tessellate_shapes`] does for backends that only paint [`Mesh`]es:
+/// the shadow is a sharp copy of the shapes in the shadow color,
+/// the opacity is applied to each shape separately (so overlapping shapes show through each other)
+/// and the blur is ignored.
+pub(crate) fn approximate_group(clip_rect: Rect, group: GroupShape) -> Vec<ClippedShape> {
+    let GroupShape { shapes, effects } = group;
+    let mut clipped_shapes = Vec::with_capacity(2 * shapes.len());
+
+    if let Some(shadow) = effects.shadow {
+        let shadow_color = shadow.color.linear_multiply(effects.opacity);
+        for shape in &shapes {
+            let mut shape = shape.clone();
+            shape.translate(shadow.offset);
+            shape_transform::adjust_colors(&mut shape, &|color| {
+                *color = shadow_color.linear_multiply(color.a() as f32 / 255.0);
+            });
+            clipped_shapes.push(ClippedShape(clip_rect, shape));
+        }
+    }
+
+    for mut shape in shapes {
+        if effects.opacity < 1.0 {
+            shape_transform::adjust_colors(&mut shape, &|color| {
+                *color = color.linear_multiply(effects.opacity);
+            });
+        }
+        clipped_shapes.push(ClippedShape(clip_rect, shape));
+    }
+
+    clipped_shapes
+}
+
+/// Turn [`ClippedPrimitive`]s into [`ClippedMesh`]es, for backends that can't composite groups.
+///
+/// The effects of each [`GroupPrimitive`] are approximated the same way [`tessellate_shapes`] does it:
+/// the shadow is a sharp copy of the meshes in the shadow color,
+/// the opacity is applied to each vertex (so overlapping shapes show through each other)
+/// and the blur is ignored.
+pub fn approximate_primitives(clipped_primitives: Vec<ClippedPrimitive>) -> Vec<ClippedMesh> {
+    let mut clipped_meshes = Vec::with_capacity(clipped_primitives.len());
+    for ClippedPrimitive(clip_rect, primitive) in clipped_primitives {
+        match primitive {
+            Primitive::Mesh(mesh) => clipped_meshes.push(ClippedMesh(clip_rect, mesh)),
+            Primitive::Group(GroupPrimitive {
+                primitives,
+                effects,
+            }) => {
+                let content = approximate_primitives(primitives);
+
+                if let Some(shadow) = effects.shadow {
+                    let shadow_color = shadow.color.linear_multiply(effects.opacity);
+                    for ClippedMesh(inner_clip_rect, mesh) in &content {
+                        let mut mesh = mesh.clone();
+                        mesh.translate(shadow.offset);
+                        for vertex in &mut mesh.vertices {
+                            vertex.color =
+                                shadow_color.linear_multiply(vertex.color.a() as f32 / 255.0);
+                        }
+                        let shadow_clip_rect = inner_clip_rect.translate(shadow.offset);
+                        clipped_meshes
+                            .push(ClippedMesh(clip_rect.intersect(shadow_clip_rect), mesh));
+                    }
+                }
+
+                for ClippedMesh(inner_clip_rect, mut mesh) in content {
+                    if effects.opacity < 1.0 {
+                        for vertex in &mut mesh.vertices {
+                            vertex.color = vertex.color.linear_multiply(effects.opacity);
+                        }
+                    }
+                    clipped_meshes.push(ClippedMesh(clip_rect.intersect(inner_clip_rect), mesh));
+                }
+            }
+        }
+    }
+    clipped_meshes
+}
@@ -87,9 +87,12 @@
 #![allow(clippy::float_cmp)]
 #![allow(clippy::manual_range_contains)]
 
+mod brush;
 pub mod color;
+mod group;
 mod mesh;
 pub mod mutex;
+pub mod raster;
 mod shadow;
 mod shape;
 pub mod shape_transform;
@@ -101,13 +104,21 @@ mod texture_atlas;
 pub mod util;
 
 pub use {
+    brush::Brush,
     color::{Color32, Rgba},
+    group::{
+        approximate_primitives, ClippedPrimitive, DropShadow, GroupEffects, GroupPrimitive,
+        Primitive,
+    },
     mesh::{Mesh, Mesh16, Vertex},
     shadow::Shadow,
-    shape::{CircleShape, PathShape, RectShape, Shape, TextShape},
+    shape::{
+        CircleShape, ComplexPathShape, FillRule, GroupShape, PathCommand, PathShape, RectShape,
+        Shape, TextShape,
+    },
     stats::PaintStats,
-    stroke::Stroke,
-    tessellator::{tessellate_shapes, TessellationOptions, Tessellator},
+    stroke::{DashPattern, LineCap, LineJoin, Stroke},
+    tessellator::{tessellate_primitives, tessellate_shapes, TessellationOptions, Tessellator},
     text::{Fonts, Galley, TextStyle},
     texture_atlas::{FontImage, TextureAtlas},
 };
@@ -154,6 +165,17 @@ pub struct ClippedShape(
     pub Shape,
 );
 
+// `f32` isn't `Hash`, so we use `f32_hash`, which hashes `0.0` and `-0.0` the same.
+// Shapes that compare equal therefore hash equal, as the derived `PartialEq` requires.
+#[allow(clippy::derive_hash_xor_eq)]
+impl std::hash::Hash for ClippedShape {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        rect_hash(state, self.0);
+        self.1.hash(state);
+    }
+}
+
 /// A [`Mesh`] within a clip rectangle.
 ///
 /// Everything is using logical points.
@@ -197,6 +219,18 @@ pub(crate) fn f32_hash<H: std::hash::Hasher>(state: &mut H, f: f32) {
     }
 }
 
+#[inline(always)]
+pub(crate) fn pos2_hash<H: std::hash::Hasher>(state: &mut H, pos: emath::Pos2) {
+    f32_hash(state, pos.x);
+    f32_hash(state, pos.y);
+}
+
+#[inline(always)]
+pub(crate) fn rect_hash<H: std::hash::Hasher>(state: &mut H, rect: emath::Rect) {
+    pos2_hash(state, rect.min);
+    pos2_hash(state, rect.max);
+}
+
 #[inline(always)]
 pub(crate) fn f64_hash<H: std::hash::Hasher>(state: &mut H, f: f64) {
     if f == 0.0 {
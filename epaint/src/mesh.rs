@@ -1,3 +1,5 @@
+#![allow(clippy::derive_hash_xor_eq)] // We need to impl Hash for f32, but we don't implement Eq, which is fine
+
 use crate::*;
 use emath::*;
 
@@ -41,6 +43,29 @@ pub struct Mesh {
     // TODO: bounding rectangle
 }
 
+impl std::hash::Hash for Vertex {
+    #[inline(always)]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self { pos, uv, color } = *self;
+        crate::pos2_hash(state, pos);
+        crate::pos2_hash(state, uv);
+        color.hash(state);
+    }
+}
+
+impl std::hash::Hash for Mesh {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            indices,
+            vertices,
+            texture_id,
+        } = self;
+        indices.hash(state);
+        vertices.hash(state);
+        texture_id.hash(state);
+    }
+}
+
 impl Mesh {
     pub fn with_texture(texture_id: TextureId) -> Self {
         Self {
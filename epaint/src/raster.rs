@@ -0,0 +1,344 @@
+//! Paint [`ClippedPrimitive`]s into an image on the CPU.
+//!
+//! This is slow, and is mostly meant for testing and as a reference for how a backend
+//! should composite a [`Primitive::Group`].
+
+use crate::*;
+use emath::*;
+
+/// An image of premultiplied linear colors that primitives can be painted into.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Canvas {
+    /// Width and height in physical pixels.
+    pub size: [usize; 2],
+
+    pub pixels_per_point: f32,
+
+    /// Row by row, top to bottom.
+    pub pixels: Vec<Rgba>,
+}
+
+/// A clip rectangle in physical pixels: `[min_x, min_y, max_x, max_y]`.
+type PixelRect = [usize; 4];
+
+impl Canvas {
+    /// A transparent canvas of the given size in physical pixels.
+    pub fn new(size: [usize; 2], pixels_per_point: f32) -> Self {
+        Self {
+            size,
+            pixels_per_point,
+            pixels: vec![Rgba::TRANSPARENT; size[0] * size[1]],
+        }
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Rgba {
+        self.pixels[y * self.size[0] + x]
+    }
+
+    /// Paint on top of what is already in the canvas.
+    ///
+    /// `font_image` is used for [`TextureId::Egui`]. Other textures are painted as if they were white.
+    pub fn paint(&mut self, primitives: &[ClippedPrimitive], font_image: Option<&FontImage>) {
+        for ClippedPrimitive(clip_rect, primitive) in primitives {
+            let clip_rect = self.pixel_rect(*clip_rect);
+            match primitive {
+                Primitive::Mesh(mesh) => self.paint_mesh(clip_rect, mesh, font_image),
+                Primitive::Group(group) => self.paint_group(clip_rect, group, font_image),
+            }
+        }
+    }
+
+    fn pixel_rect(&self, rect: Rect) -> PixelRect {
+        let to_pixel = |points: f32, size: usize| {
+            (points * self.pixels_per_point)
+                .round()
+                .clamp(0.0, size as f32) as usize
+        };
+        [
+            to_pixel(rect.min.x, self.size[0]),
+            to_pixel(rect.min.y, self.size[1]),
+            to_pixel(rect.max.x, self.size[0]),
+            to_pixel(rect.max.y, self.size[1]),
+        ]
+    }
+
+    fn paint_mesh(&mut self, clip_rect: PixelRect, mesh: &Mesh, font_image: Option<&FontImage>) {
+        let [clip_min_x, clip_min_y, clip_max_x, clip_max_y] = clip_rect;
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = [
+                mesh.vertices[triangle[0] as usize],
+                mesh.vertices[triangle[1] as usize],
+                mesh.vertices[triangle[2] as usize],
+            ];
+            let [first, second, third] = vertices.map(|v| v.pos.to_vec2() * self.pixels_per_point);
+            let area = edge(first, second, third);
+            if area == 0.0 {
+                continue;
+            }
+
+            let min = first.min(second).min(third);
+            let max = first.max(second).max(third);
+            let min_x = (min.x.floor().max(0.0) as usize).max(clip_min_x);
+            let min_y = (min.y.floor().max(0.0) as usize).max(clip_min_y);
+            let max_x = (max.x.ceil().max(0.0) as usize).min(clip_max_x);
+            let max_y = (max.y.ceil().max(0.0) as usize).min(clip_max_y);
+
+            let colors = vertices.map(|v| Rgba::from(v.color));
+
+            for y in min_y..max_y {
+                for x in min_x..max_x {
+                    let center = vec2(x as f32 + 0.5, y as f32 + 0.5);
+                    let weights = [
+                        edge(second, third, center) / area,
+                        edge(third, first, center) / area,
+                        edge(first, second, center) / area,
+                    ];
+                    if weights.iter().any(|&weight| weight < 0.0) {
+                        continue;
+                    }
+
+                    let color =
+                        weights[0] * colors[0] + weights[1] * colors[1] + weights[2] * colors[2];
+                    let uv = weights[0] * vertices[0].uv.to_vec2()
+                        + weights[1] * vertices[1].uv.to_vec2()
+                        + weights[2] * vertices[2].uv.to_vec2();
+                    let color = color * sample(mesh.texture_id, uv, font_image);
+
+                    let pixel = &mut self.pixels[y * self.size[0] + x];
+                    *pixel = color + *pixel * (1.0 - color.a());
+                }
+            }
+        }
+    }
+
+    fn paint_group(
+        &mut self,
+        clip_rect: PixelRect,
+        group: &GroupPrimitive,
+        font_image: Option<&FontImage>,
+    ) {
+        let mut content = Self::new(self.size, self.pixels_per_point);
+        content.paint(&group.primitives, font_image);
+
+        let effects = &group.effects;
+        let opacity = effects.opacity.clamp(0.0, 1.0);
+
+        if let Some(shadow) = effects.shadow {
+            let mut silhouette = content.clone();
+            let shadow_color = Rgba::from(shadow.color);
+            for pixel in &mut silhouette.pixels {
+                *pixel = shadow_color * pixel.a();
+            }
+            silhouette.blur(shadow.blur * self.pixels_per_point);
+            let offset = shadow.offset * self.pixels_per_point;
+            self.composite(
+                &silhouette,
+                clip_rect,
+                [offset.x.round() as isize, offset.y.round() as isize],
+                opacity,
+            );
+        }
+
+        content.blur(effects.blur * self.pixels_per_point);
+        self.composite(&content, clip_rect, [0, 0], opacity);
+    }
+
+    /// Paint `other` (moved by `offset` pixels) on top of `self`.
+    fn composite(&mut self, other: &Self, clip_rect: PixelRect, offset: [isize; 2], opacity: f32) {
+        let [min_x, min_y, max_x, max_y] = clip_rect;
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let source_x = x as isize - offset[0];
+                let source_y = y as isize - offset[1];
+                if source_x < 0
+                    || source_y < 0
+                    || source_x as usize >= other.size[0]
+                    || source_y as usize >= other.size[1]
+                {
+                    continue;
+                }
+                let color = other.pixel(source_x as usize, source_y as usize) * opacity;
+                let pixel = &mut self.pixels[y * self.size[0] + x];
+                *pixel = color + *pixel * (1.0 - color.a());
+            }
+        }
+    }
+
+    /// Gaussian blur with the given standard deviation (in pixels).
+    fn blur(&mut self, sigma: f32) {
+        if sigma <= 0.0 {
+            return;
+        }
+
+        let radius = (3.0 * sigma).ceil() as isize;
+        let kernel: Vec<f32> = (-radius..=radius)
+            .map(|i| (-0.5 * (i as f32 / sigma).powi(2)).exp())
+            .collect();
+        let kernel_sum: f32 = kernel.iter().sum();
+        let kernel: Vec<f32> = kernel.iter().map(|k| k / kernel_sum).collect();
+
+        let [width, height] = self.size;
+        let blur_pass = |pixels: &[Rgba], horizontal: bool| -> Vec<Rgba> {
+            let mut out = vec![Rgba::TRANSPARENT; pixels.len()];
+            for y in 0..height {
+                for x in 0..width {
+                    let mut sum = Rgba::TRANSPARENT;
+                    for (i, k) in (-radius..=radius).zip(&kernel) {
+                        let (sx, sy) = if horizontal {
+                            (x as isize + i, y as isize)
+                        } else {
+                            (x as isize, y as isize + i)
+                        };
+                        if 0 <= sx && sx < width as isize && 0 <= sy && sy < height as isize {
+                            sum = sum + pixels[sy as usize * width + sx as usize] * *k;
+                        }
+                    }
+                    out[y * width + x] = sum;
+                }
+            }
+            out
+        };
+
+        let horizontal = blur_pass(&self.pixels, true);
+        self.pixels = blur_pass(&horizontal, false);
+    }
+}
+
+/// Twice the signed area of the triangle `a, b, p`.
+fn edge(a: Vec2, b: Vec2, p: Vec2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+/// Nearest-neighbor sampling.
+fn sample(texture_id: TextureId, uv: Vec2, font_image: Option<&FontImage>) -> Rgba {
+    match (texture_id, font_image) {
+        (TextureId::Egui, Some(font_image)) => {
+            let x = ((uv.x * font_image.width as f32) as usize).min(font_image.width - 1);
+            let y = ((uv.y * font_image.height as f32) as usize).min(font_image.height - 1);
+            Rgba::from_white_alpha(color::linear_f32_from_linear_u8(font_image[(x, y)]))
+        }
+        _ => Rgba::WHITE,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clipped_shapes(shapes: Vec<Shape>) -> Vec<ClippedShape> {
+        let clip_rect = Rect::from_min_size(Pos2::ZERO, vec2(20.0, 20.0));
+        shapes
+            .into_iter()
+            .map(|shape| ClippedShape(clip_rect, shape))
+            .collect()
+    }
+
+    fn options() -> TessellationOptions {
+        TessellationOptions {
+            anti_alias: false,
+            ..Default::default()
+        }
+    }
+
+    fn paint_primitives(primitives: &[ClippedPrimitive]) -> Canvas {
+        let mut canvas = Canvas::new([20, 20], 1.0);
+        canvas.paint(primitives, None);
+        canvas
+    }
+
+    fn paint(shapes: Vec<Shape>) -> Canvas {
+        paint_primitives(&tessellate_primitives(
+            clipped_shapes(shapes),
+            options(),
+            [1, 1],
+        ))
+    }
+
+    fn mesh_primitives(clipped_meshes: Vec<ClippedMesh>) -> Vec<ClippedPrimitive> {
+        clipped_meshes
+            .into_iter()
+            .map(|ClippedMesh(clip_rect, mesh)| ClippedPrimitive(clip_rect, Primitive::Mesh(mesh)))
+            .collect()
+    }
+
+    fn overlapping_squares() -> Vec<Shape> {
+        vec![
+            Shape::rect_filled(
+                Rect::from_min_max(pos2(5.0, 5.0), pos2(11.0, 11.0)),
+                0.0,
+                Color32::WHITE,
+            ),
+            Shape::rect_filled(
+                Rect::from_min_max(pos2(8.0, 8.0), pos2(14.0, 14.0)),
+                0.0,
+                Color32::WHITE,
+            ),
+        ]
+    }
+
+    fn assert_close(a: Rgba, b: Rgba) {
+        let close = (0..4).all(|i| (a[i] - b[i]).abs() < 0.01);
+        assert!(close, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn group_opacity_is_applied_once() {
+        let canvas = paint(vec![GroupShape::new(overlapping_squares())
+            .opacity(0.5)
+            .into()]);
+        assert_close(canvas.pixel(6, 6), Rgba::WHITE * 0.5);
+        assert_close(canvas.pixel(9, 9), Rgba::WHITE * 0.5);
+    }
+
+    #[test]
+    fn drop_shadow_is_behind_the_group() {
+        let shadow = DropShadow::new(vec2(3.0, 3.0), 0.0, Color32::BLACK);
+        let square = Shape::rect_filled(
+            Rect::from_min_max(pos2(2.0, 2.0), pos2(8.0, 8.0)),
+            0.0,
+            Color32::WHITE,
+        );
+        let canvas = paint(vec![GroupShape::new(vec![square]).shadow(shadow).into()]);
+        assert_close(canvas.pixel(4, 4), Rgba::WHITE);
+        assert_close(canvas.pixel(9, 9), Rgba::BLACK);
+        assert_close(canvas.pixel(1, 9), Rgba::TRANSPARENT);
+    }
+
+    #[test]
+    fn blur_preserves_coverage() {
+        let sharp = paint(overlapping_squares());
+        let blurred = paint(vec![GroupShape::new(overlapping_squares())
+            .blur(1.5)
+            .into()]);
+        let coverage = |canvas: &Canvas| canvas.pixels.iter().map(|p| p.a()).sum::<f32>();
+        assert!((coverage(&sharp) - coverage(&blurred)).abs() < 0.1);
+        assert!(blurred.pixel(6, 6).a() < 0.99);
+        assert!(blurred.pixel(3, 8).a() > 0.01);
+    }
+
+    #[test]
+    fn approximate_primitives_matches_tessellate_shapes() {
+        let shadow = DropShadow::new(vec2(2.0, 3.0), 0.0, Color32::BLACK);
+        let group = || {
+            vec![GroupShape::new(overlapping_squares())
+                .opacity(0.5)
+                .shadow(shadow)
+                .into()]
+        };
+
+        let tessellated = tessellate_shapes(clipped_shapes(group()), options(), [1, 1]);
+        let approximated = approximate_primitives(tessellate_primitives(
+            clipped_shapes(group()),
+            options(),
+            [1, 1],
+        ));
+
+        let expected = paint_primitives(&mesh_primitives(tessellated));
+        let actual = paint_primitives(&mesh_primitives(approximated));
+        for (a, b) in expected.pixels.iter().zip(&actual.pixels) {
+            assert_close(*a, *b);
+        }
+        assert_close(actual.pixel(15, 12), Rgba::BLACK * 0.5);
+    }
+}
@@ -1,6 +1,8 @@
+#![allow(clippy::derive_hash_xor_eq)] // We need to impl Hash for f32, but we don't implement Eq, which is fine
+
 use crate::{
     text::{Fonts, Galley, TextStyle},
-    Color32, Mesh, Stroke,
+    Brush, Color32, DashPattern, DropShadow, GroupEffects, Mesh, Stroke,
 };
 use emath::*;
 
@@ -20,9 +22,13 @@ pub enum Shape {
         stroke: Stroke,
     },
     Path(PathShape),
+    /// Concave polygons, polygons with holes, and bezier curves.
+    ComplexPath(ComplexPathShape),
     Rect(RectShape),
     Text(TextShape),
     Mesh(Mesh),
+    /// Shapes painted as a unit, with opacity, blur and a drop shadow.
+    Group(GroupShape),
 }
 
 /// ## Constructors
@@ -87,7 +93,40 @@ impl Shape {
         dashes_from_line(points, stroke.into(), dash_length, gap_length, shapes);
     }
 
+    /// A wavy ("squiggly") line between two points, e.g. for underlining spelling errors.
+    ///
+    /// `amplitude` is how far the wave goes to each side of the straight line,
+    /// and `wavelength` is the length of one full period.
+    pub fn wavy_line(
+        points: [Pos2; 2],
+        stroke: impl Into<Stroke>,
+        amplitude: f32,
+        wavelength: f32,
+    ) -> Self {
+        let [start, end] = points;
+        let vector = end - start;
+        let length = vector.length();
+        if length <= 0.0 || wavelength <= 0.0 {
+            return Self::line_segment(points, stroke);
+        }
+        let dir = vector / length;
+        let normal = dir.rot90();
+
+        // A zig-zag with two points per wavelength:
+        let half_wavelength = 0.5 * wavelength;
+        let num_points = (length / half_wavelength).ceil() as usize + 1;
+        let mut wave = Vec::with_capacity(num_points);
+        for i in 0..num_points {
+            let t = (i as f32 * half_wavelength).min(length);
+            let side = if i % 2 == 0 { amplitude } else { -amplitude };
+            wave.push(start + t * dir + side * normal);
+        }
+        Self::line(wave, stroke)
+    }
+
     /// A convex polygon with a fill and optional stroke.
+    ///
+    /// Use [`ComplexPathShape`] for concave polygons.
     #[inline]
     pub fn convex_polygon(
         points: Vec<Pos2>,
@@ -146,10 +185,22 @@ impl Shape {
 impl Shape {
     #[inline(always)]
     pub fn texture_id(&self) -> super::TextureId {
-        if let Shape::Mesh(mesh) = self {
-            mesh.texture_id
-        } else {
-            super::TextureId::Egui
+        match self {
+            Shape::Mesh(mesh) => mesh.texture_id,
+            Shape::Circle(circle_shape) => circle_shape.fill.texture_id(),
+            Shape::Path(path_shape) => path_shape.fill.texture_id(),
+            Shape::ComplexPath(path_shape) => path_shape.fill.texture_id(),
+            Shape::Rect(rect_shape) => rect_shape.fill.texture_id(),
+            _ => super::TextureId::Egui,
+        }
+    }
+
+    /// Is this a [`Self::Group`], or a [`Self::Vec`] with one in it?
+    pub fn contains_group(&self) -> bool {
+        match self {
+            Shape::Group(_) => true,
+            Shape::Vec(shapes) => shapes.iter().any(Shape::contains_group),
+            _ => false,
         }
     }
 
@@ -164,6 +215,7 @@ impl Shape {
             }
             Shape::Circle(circle_shape) => {
                 circle_shape.center += delta;
+                circle_shape.fill.translate(delta);
             }
             Shape::LineSegment { points, .. } => {
                 for p in points {
@@ -174,9 +226,14 @@ impl Shape {
                 for p in &mut path_shape.points {
                     *p += delta;
                 }
+                path_shape.fill.translate(delta);
+            }
+            Shape::ComplexPath(path_shape) => {
+                path_shape.translate(delta);
             }
             Shape::Rect(rect_shape) => {
                 rect_shape.rect = rect_shape.rect.translate(delta);
+                rect_shape.fill.translate(delta);
             }
             Shape::Text(text_shape) => {
                 text_shape.pos += delta;
@@ -184,6 +241,11 @@ impl Shape {
             Shape::Mesh(mesh) => {
                 mesh.translate(delta);
             }
+            Shape::Group(group) => {
+                for shape in &mut group.shapes {
+                    shape.translate(delta);
+                }
+            }
         }
     }
 }
@@ -196,8 +258,10 @@ impl Shape {
 pub struct CircleShape {
     pub center: Pos2,
     pub radius: f32,
-    pub fill: Color32,
+    pub fill: Brush,
     pub stroke: Stroke,
+    /// If set, the stroke is dashed.
+    pub dash: Option<DashPattern>,
 }
 
 impl CircleShape {
@@ -206,8 +270,9 @@ impl CircleShape {
         Self {
             center,
             radius,
-            fill: fill_color.into(),
+            fill: Brush::Solid(fill_color.into()),
             stroke: Default::default(),
+            dash: None,
         }
     }
 
@@ -218,8 +283,16 @@ impl CircleShape {
             radius,
             fill: Default::default(),
             stroke: stroke.into(),
+            dash: None,
         }
     }
+
+    /// Dash the stroke with this pattern.
+    #[inline]
+    pub fn dashed(mut self, dash: DashPattern) -> Self {
+        self.dash = Some(dash);
+        self
+    }
 }
 
 impl From<CircleShape> for Shape {
@@ -237,11 +310,13 @@ impl From<CircleShape> for Shape {
 pub struct PathShape {
     pub points: Vec<Pos2>,
     /// If true, connect the first and last of the points together.
-    /// This is required if `fill != TRANSPARENT`.
+    /// This is required if the `fill` is not transparent.
     pub closed: bool,
     /// Fill is only supported for convex polygons.
-    pub fill: Color32,
+    pub fill: Brush,
     pub stroke: Stroke,
+    /// If set, the stroke is dashed.
+    pub dash: Option<DashPattern>,
 }
 
 impl PathShape {
@@ -255,6 +330,7 @@ impl PathShape {
             closed: false,
             fill: Default::default(),
             stroke: stroke.into(),
+            dash: None,
         }
     }
 
@@ -266,6 +342,7 @@ impl PathShape {
             closed: true,
             fill: Default::default(),
             stroke: stroke.into(),
+            dash: None,
         }
     }
 
@@ -279,11 +356,19 @@ impl PathShape {
         PathShape {
             points,
             closed: true,
-            fill: fill.into(),
+            fill: Brush::Solid(fill.into()),
             stroke: stroke.into(),
+            dash: None,
         }
     }
 
+    /// Dash the stroke with this pattern.
+    #[inline]
+    pub fn dashed(mut self, dash: DashPattern) -> Self {
+        self.dash = Some(dash);
+        self
+    }
+
     /// Screen-space bounding rectangle.
     #[inline]
     pub fn bounding_rect(&self) -> Rect {
@@ -300,6 +385,283 @@ impl From<PathShape> for Shape {
 
 // ----------------------------------------------------------------------------
 
+/// One step of a [`ComplexPathShape`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum PathCommand {
+    /// Start a new sub-path at this point.
+    MoveTo(Pos2),
+
+    /// A straight line to this point.
+    LineTo(Pos2),
+
+    /// A quadratic bezier curve to `to`.
+    QuadraticTo { control: Pos2, to: Pos2 },
+
+    /// A cubic bezier curve to `to`.
+    CubicTo {
+        control1: Pos2,
+        control2: Pos2,
+        to: Pos2,
+    },
+
+    /// Connect the end of the current sub-path back to its start.
+    Close,
+}
+
+/// How to decide what is inside a [`ComplexPathShape`] when its sub-paths overlap or intersect themselves.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FillRule {
+    /// Inside is where the outline winds around a non-zero number of times.
+    ///
+    /// Holes need to wind the other way from the outline around them.
+    NonZero,
+
+    /// Inside is where you cross an odd number of edges to get to the outside.
+    ///
+    /// Any sub-path inside another one becomes a hole.
+    EvenOdd,
+}
+
+impl Default for FillRule {
+    fn default() -> Self {
+        Self::NonZero
+    }
+}
+
+/// A path made up of lines and bezier curves, which can be concave,
+/// intersect itself and consist of several sub-paths (e.g. an outline with holes).
+///
+/// Every sub-path is implicitly closed when filling.
+///
+/// ```
+/// # use epaint::*;
+/// let triangle_with_hole = ComplexPathShape::default()
+///     .move_to(pos2(0.0, 0.0))
+///     .line_to(pos2(100.0, 0.0))
+///     .quadratic_to(pos2(100.0, 100.0), pos2(0.0, 100.0))
+///     .close()
+///     .move_to(pos2(20.0, 20.0))
+///     .line_to(pos2(40.0, 20.0))
+///     .line_to(pos2(20.0, 40.0))
+///     .close()
+///     .fill_rule(FillRule::EvenOdd)
+///     .fill(Color32::RED);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct ComplexPathShape {
+    pub commands: Vec<PathCommand>,
+    pub fill_rule: FillRule,
+    pub fill: Brush,
+    pub stroke: Stroke,
+    /// If set, the stroke is dashed.
+    pub dash: Option<DashPattern>,
+}
+
+impl ComplexPathShape {
+    #[inline]
+    pub fn move_to(mut self, pos: Pos2) -> Self {
+        self.commands.push(PathCommand::MoveTo(pos));
+        self
+    }
+
+    #[inline]
+    pub fn line_to(mut self, pos: Pos2) -> Self {
+        self.commands.push(PathCommand::LineTo(pos));
+        self
+    }
+
+    #[inline]
+    pub fn quadratic_to(mut self, control: Pos2, to: Pos2) -> Self {
+        self.commands.push(PathCommand::QuadraticTo { control, to });
+        self
+    }
+
+    #[inline]
+    pub fn cubic_to(mut self, control1: Pos2, control2: Pos2, to: Pos2) -> Self {
+        self.commands.push(PathCommand::CubicTo {
+            control1,
+            control2,
+            to,
+        });
+        self
+    }
+
+    #[inline]
+    pub fn close(mut self) -> Self {
+        self.commands.push(PathCommand::Close);
+        self
+    }
+
+    #[inline]
+    pub fn fill_rule(mut self, fill_rule: FillRule) -> Self {
+        self.fill_rule = fill_rule;
+        self
+    }
+
+    #[inline]
+    pub fn fill(mut self, fill: impl Into<Brush>) -> Self {
+        self.fill = fill.into();
+        self
+    }
+
+    #[inline]
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Dash the stroke with this pattern.
+    #[inline]
+    pub fn dashed(mut self, dash: DashPattern) -> Self {
+        self.dash = Some(dash);
+        self
+    }
+
+    /// Every point of the path, including the bezier control points.
+    fn points_mut(&mut self) -> impl Iterator<Item = &mut Pos2> {
+        self.commands.iter_mut().flat_map(|command| {
+            let points: Vec<&mut Pos2> = match command {
+                PathCommand::MoveTo(pos) | PathCommand::LineTo(pos) => vec![pos],
+                PathCommand::QuadraticTo { control, to } => vec![control, to],
+                PathCommand::CubicTo {
+                    control1,
+                    control2,
+                    to,
+                } => vec![control1, control2, to],
+                PathCommand::Close => vec![],
+            };
+            points
+        })
+    }
+
+    /// Move the shape by this many points, in-place.
+    pub fn translate(&mut self, delta: Vec2) {
+        for pos in self.points_mut() {
+            *pos += delta;
+        }
+        self.fill.translate(delta);
+    }
+
+    /// Screen-space bounding rectangle.
+    ///
+    /// The curves always stay within their control points, so this is conservative.
+    pub fn bounding_rect(&self) -> Rect {
+        let mut rect = Rect::NOTHING;
+        for command in &self.commands {
+            match *command {
+                PathCommand::MoveTo(pos) | PathCommand::LineTo(pos) => rect.extend_with(pos),
+                PathCommand::QuadraticTo { control, to } => {
+                    rect.extend_with(control);
+                    rect.extend_with(to);
+                }
+                PathCommand::CubicTo {
+                    control1,
+                    control2,
+                    to,
+                } => {
+                    rect.extend_with(control1);
+                    rect.extend_with(control2);
+                    rect.extend_with(to);
+                }
+                PathCommand::Close => {}
+            }
+        }
+        rect.expand(self.stroke.width)
+    }
+
+    /// Turn the curves into line segments.
+    ///
+    /// `tolerance` is the maximum distance (in points) between the lines and the true curves.
+    ///
+    /// Returns each sub-path, and whether or not it was explicitly closed.
+    pub fn flatten(&self, tolerance: f32) -> Vec<(Vec<Pos2>, bool)> {
+        let mut sub_paths = vec![];
+        let mut points: Vec<Pos2> = vec![];
+        for command in &self.commands {
+            let last = points.last().copied();
+            match *command {
+                PathCommand::MoveTo(pos) => {
+                    if points.len() > 1 {
+                        sub_paths.push((std::mem::take(&mut points), false));
+                    }
+                    points.clear();
+                    points.push(pos);
+                }
+                PathCommand::LineTo(pos) => {
+                    points.push(pos);
+                }
+                PathCommand::QuadraticTo { control, to } => {
+                    let from = last.unwrap_or(control);
+                    let n = num_bezier_segments(2, &[from, control, to], tolerance);
+                    points.extend((1..=n).map(|i| {
+                        let t = i as f32 / n as f32;
+                        let s = 1.0 - t;
+                        (s * s * from.to_vec2()
+                            + 2.0 * s * t * control.to_vec2()
+                            + t * t * to.to_vec2())
+                        .to_pos2()
+                    }));
+                }
+                PathCommand::CubicTo {
+                    control1,
+                    control2,
+                    to,
+                } => {
+                    let from = last.unwrap_or(control1);
+                    let n = num_bezier_segments(3, &[from, control1, control2, to], tolerance);
+                    points.extend((1..=n).map(|i| {
+                        let t = i as f32 / n as f32;
+                        let s = 1.0 - t;
+                        (s * s * s * from.to_vec2()
+                            + 3.0 * s * s * t * control1.to_vec2()
+                            + 3.0 * s * t * t * control2.to_vec2()
+                            + t * t * t * to.to_vec2())
+                        .to_pos2()
+                    }));
+                }
+                PathCommand::Close => {
+                    if points.len() > 1 {
+                        let start = points[0];
+                        sub_paths.push((std::mem::take(&mut points), true));
+                        // Continuing after a close starts from the same point:
+                        points.push(start);
+                    }
+                }
+            }
+        }
+        if points.len() > 1 {
+            sub_paths.push((points, false));
+        }
+        sub_paths
+    }
+}
+
+/// How many line segments we need for a bezier curve of the given degree,
+/// using Wang's formula.
+fn num_bezier_segments(degree: usize, points: &[Pos2], tolerance: f32) -> usize {
+    let max_second_difference = points
+        .windows(3)
+        .map(|p| (p[0].to_vec2() - 2.0 * p[1].to_vec2() + p[2].to_vec2()).length())
+        .fold(0.0, f32::max);
+    let factor = (degree * (degree - 1)) as f32 / 8.0;
+    let n = (factor * max_second_difference / tolerance.at_least(1e-3))
+        .sqrt()
+        .ceil();
+    (n as usize).clamp(1, 1000)
+}
+
+impl From<ComplexPathShape> for Shape {
+    #[inline(always)]
+    fn from(shape: ComplexPathShape) -> Self {
+        Self::ComplexPath(shape)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// How to paint a rectangle.
 #[derive(Copy, Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -307,8 +669,10 @@ pub struct RectShape {
     pub rect: Rect,
     /// How rounded the corners are. Use `0.0` for no rounding.
     pub corner_radius: f32,
-    pub fill: Color32,
+    pub fill: Brush,
     pub stroke: Stroke,
+    /// If set, the stroke is dashed.
+    pub dash: Option<DashPattern>,
 }
 
 impl RectShape {
@@ -317,8 +681,9 @@ impl RectShape {
         Self {
             rect,
             corner_radius,
-            fill: fill_color.into(),
+            fill: Brush::Solid(fill_color.into()),
             stroke: Default::default(),
+            dash: None,
         }
     }
 
@@ -329,9 +694,17 @@ impl RectShape {
             corner_radius,
             fill: Default::default(),
             stroke: stroke.into(),
+            dash: None,
         }
     }
 
+    /// Dash the stroke with this pattern.
+    #[inline]
+    pub fn dashed(mut self, dash: DashPattern) -> Self {
+        self.dash = Some(dash);
+        self
+    }
+
     /// Screen-space bounding rectangle.
     #[inline]
     pub fn bounding_rect(&self) -> Rect {
@@ -399,6 +772,208 @@ impl From<TextShape> for Shape {
 
 // ----------------------------------------------------------------------------
 
+/// Shapes that are composited together, with [`GroupEffects`] such as opacity, blur and a drop shadow.
+///
+/// To get the effects right the backend must support [`crate::Primitive::Group`]
+/// (see [`crate::tessellator::tessellate_primitives`]).
+/// Otherwise [`crate::tessellate_shapes`] approximates them.
+///
+/// `egui_glow`, `egui_glium` and the default (`glow`) painter of `egui_web` composite groups.
+/// The `webgl` painters of `egui_web` paint the approximation (see [`crate::approximate_primitives`]):
+/// the opacity is applied per shape, the blur is ignored and shadows are sharp.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GroupShape {
+    pub shapes: Vec<Shape>,
+    pub effects: GroupEffects,
+}
+
+impl GroupShape {
+    #[inline]
+    pub fn new(shapes: Vec<Shape>) -> Self {
+        Self {
+            shapes,
+            effects: Default::default(),
+        }
+    }
+
+    /// Fade the whole group, from `0.0` (invisible) to `1.0` (opaque).
+    #[inline]
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.effects.opacity = opacity;
+        self
+    }
+
+    /// Gaussian blur with this standard deviation (in points).
+    #[inline]
+    pub fn blur(mut self, blur: f32) -> Self {
+        self.effects.blur = blur;
+        self
+    }
+
+    #[inline]
+    pub fn shadow(mut self, shadow: DropShadow) -> Self {
+        self.effects.shadow = Some(shadow);
+        self
+    }
+}
+
+impl From<GroupShape> for Shape {
+    #[inline(always)]
+    fn from(shape: GroupShape) -> Self {
+        Self::Group(shape)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Hashing, so that unchanged shapes can be recognized from one frame to the next.
+
+impl std::hash::Hash for Shape {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Shape::Noop => {}
+            Shape::Vec(shapes) => shapes.hash(state),
+            Shape::Circle(circle_shape) => circle_shape.hash(state),
+            Shape::LineSegment { points, stroke } => {
+                crate::pos2_hash(state, points[0]);
+                crate::pos2_hash(state, points[1]);
+                stroke.hash(state);
+            }
+            Shape::Path(path_shape) => path_shape.hash(state),
+            Shape::ComplexPath(path_shape) => path_shape.hash(state),
+            Shape::Rect(rect_shape) => rect_shape.hash(state),
+            Shape::Text(text_shape) => text_shape.hash(state),
+            Shape::Mesh(mesh) => mesh.hash(state),
+            Shape::Group(group) => group.hash(state),
+        }
+    }
+}
+
+impl std::hash::Hash for CircleShape {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            center,
+            radius,
+            fill,
+            stroke,
+            dash,
+        } = self;
+        crate::pos2_hash(state, *center);
+        crate::f32_hash(state, *radius);
+        fill.hash(state);
+        stroke.hash(state);
+        dash.hash(state);
+    }
+}
+
+impl std::hash::Hash for PathShape {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            points,
+            closed,
+            fill,
+            stroke,
+            dash,
+        } = self;
+        points.len().hash(state);
+        for &point in points {
+            crate::pos2_hash(state, point);
+        }
+        closed.hash(state);
+        fill.hash(state);
+        stroke.hash(state);
+        dash.hash(state);
+    }
+}
+
+impl std::hash::Hash for PathCommand {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match *self {
+            PathCommand::MoveTo(pos) | PathCommand::LineTo(pos) => crate::pos2_hash(state, pos),
+            PathCommand::QuadraticTo { control, to } => {
+                crate::pos2_hash(state, control);
+                crate::pos2_hash(state, to);
+            }
+            PathCommand::CubicTo {
+                control1,
+                control2,
+                to,
+            } => {
+                crate::pos2_hash(state, control1);
+                crate::pos2_hash(state, control2);
+                crate::pos2_hash(state, to);
+            }
+            PathCommand::Close => {}
+        }
+    }
+}
+
+impl std::hash::Hash for ComplexPathShape {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            commands,
+            fill_rule,
+            fill,
+            stroke,
+            dash,
+        } = self;
+        commands.hash(state);
+        fill_rule.hash(state);
+        fill.hash(state);
+        stroke.hash(state);
+        dash.hash(state);
+    }
+}
+
+impl std::hash::Hash for RectShape {
+    #[inline]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            rect,
+            corner_radius,
+            fill,
+            stroke,
+            dash,
+        } = self;
+        crate::rect_hash(state, *rect);
+        crate::f32_hash(state, *corner_radius);
+        fill.hash(state);
+        stroke.hash(state);
+        dash.hash(state);
+    }
+}
+
+impl std::hash::Hash for TextShape {
+    // The galley is hashed by the `LayoutJob` it was created from,
+    // so two galleys from the same job are assumed to be laid out with the same `Fonts`.
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            pos,
+            galley,
+            underline,
+            override_text_color,
+            angle,
+        } = self;
+        crate::pos2_hash(state, *pos);
+        galley.job.hash(state);
+        underline.hash(state);
+        override_text_color.hash(state);
+        crate::f32_hash(state, *angle);
+    }
+}
+
+impl std::hash::Hash for GroupShape {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self { shapes, effects } = self;
+        shapes.hash(state);
+        effects.hash(state);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Creates equally spaced filled circles from a line.
 fn points_from_line(
     line: &[Pos2],
@@ -461,3 +1036,66 @@ fn dashes_from_line(
         position_on_segment -= segment_length;
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wave_points(shape: &Shape) -> &[Pos2] {
+        match shape {
+            Shape::Path(path) => {
+                assert!(!path.closed);
+                &path.points
+            }
+            _ => panic!("Expected a path, got {:?}", shape),
+        }
+    }
+
+    #[test]
+    fn wavy_line_bounding_rect() {
+        let stroke = Stroke::new(1.0, Color32::RED);
+        let wave = Shape::wavy_line([pos2(10.0, 20.0), pos2(110.0, 20.0)], stroke, 2.0, 10.0);
+        let points = wave_points(&wave);
+
+        // Two points per wavelength, plus the end point:
+        assert_eq!(points.len(), 21);
+        assert_eq!(points.first().unwrap().x, 10.0);
+        assert_eq!(points.last().unwrap().x, 110.0);
+
+        let rect = Rect::from_points(points);
+        assert_eq!(
+            rect,
+            Rect::from_min_max(pos2(10.0, 18.0), pos2(110.0, 22.0))
+        );
+        if let Shape::Path(path) = &wave {
+            assert_eq!(path.bounding_rect(), rect.expand(stroke.width));
+        }
+    }
+
+    #[test]
+    fn wavy_line_does_not_overshoot() {
+        // The length is not a multiple of the half wavelength:
+        let wave = Shape::wavy_line(
+            [pos2(0.0, 0.0), pos2(0.0, 12.0)],
+            Stroke::new(1.0, Color32::RED),
+            1.0,
+            10.0,
+        );
+        let rect = Rect::from_points(wave_points(&wave));
+        assert_eq!(rect, Rect::from_min_max(pos2(-1.0, 0.0), pos2(1.0, 12.0)));
+    }
+
+    #[test]
+    fn degenerate_wavy_line_is_a_line_segment() {
+        let stroke = Stroke::new(1.0, Color32::RED);
+        let points = [pos2(1.0, 1.0), pos2(5.0, 1.0)];
+        assert!(matches!(
+            Shape::wavy_line(points, stroke, 2.0, 0.0),
+            Shape::LineSegment { .. }
+        ));
+        assert!(matches!(
+            Shape::wavy_line([points[0], points[0]], stroke, 2.0, 10.0),
+            Shape::LineSegment { .. }
+        ));
+    }
+}
@@ -10,18 +10,22 @@ pub fn adjust_colors(shape: &mut Shape, adjust_color: &impl Fn(&mut Color32)) {
             }
         }
         Shape::Circle(circle_shape) => {
-            adjust_color(&mut circle_shape.fill);
+            circle_shape.fill.adjust_colors(adjust_color);
             adjust_color(&mut circle_shape.stroke.color);
         }
         Shape::LineSegment { stroke, .. } => {
             adjust_color(&mut stroke.color);
         }
         Shape::Path(path_shape) => {
-            adjust_color(&mut path_shape.fill);
+            path_shape.fill.adjust_colors(adjust_color);
+            adjust_color(&mut path_shape.stroke.color);
+        }
+        Shape::ComplexPath(path_shape) => {
+            path_shape.fill.adjust_colors(adjust_color);
             adjust_color(&mut path_shape.stroke.color);
         }
         Shape::Rect(rect_shape) => {
-            adjust_color(&mut rect_shape.fill);
+            rect_shape.fill.adjust_colors(adjust_color);
             adjust_color(&mut rect_shape.stroke.color);
         }
         Shape::Text(text_shape) => {
@@ -43,5 +47,13 @@ pub fn adjust_colors(shape: &mut Shape, adjust_color: &impl Fn(&mut Color32)) {
                 adjust_color(&mut v.color);
             }
         }
+        Shape::Group(group) => {
+            for shape in &mut group.shapes {
+                adjust_colors(shape, adjust_color);
+            }
+            if let Some(shadow) = &mut group.effects.shadow {
+                adjust_color(&mut shadow.color);
+            }
+        }
     }
 }
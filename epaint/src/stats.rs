@@ -170,6 +170,12 @@ pub struct PaintStats {
     pub clipped_meshes: AllocInfo,
     pub vertices: AllocInfo,
     pub indices: AllocInfo,
+
+    /// Number of layers whose tessellation was reused from the previous frame.
+    pub cached_layers: usize,
+
+    /// Number of layers that had to be tessellated.
+    pub tessellated_layers: usize,
 }
 
 impl PaintStats {
@@ -187,7 +193,7 @@ impl PaintStats {
 
     fn add(&mut self, shape: &Shape) {
         match shape {
-            Shape::Vec(shapes) => {
+            Shape::Vec(shapes) | Shape::Group(GroupShape { shapes, .. }) => {
                 // self += PaintStats::from_shapes(&shapes); // TODO
                 self.shapes += AllocInfo::from_slice(shapes);
                 self.shape_vec += AllocInfo::from_slice(shapes);
@@ -200,6 +206,9 @@ impl PaintStats {
             Shape::Path(path_shape) => {
                 self.shape_path += AllocInfo::from_slice(&path_shape.points);
             }
+            Shape::ComplexPath(path_shape) => {
+                self.shape_path += AllocInfo::from_slice(&path_shape.commands);
+            }
             Shape::Text(text_shape) => {
                 self.shape_text += AllocInfo::from_galley(&text_shape.galley);
 
@@ -223,6 +232,35 @@ impl PaintStats {
         self
     }
 
+    pub fn with_clipped_primitives(
+        mut self,
+        clipped_primitives: &[crate::ClippedPrimitive],
+    ) -> Self {
+        self.clipped_meshes += AllocInfo::from_slice(clipped_primitives);
+        for ClippedPrimitive(_, primitive) in clipped_primitives {
+            match primitive {
+                Primitive::Mesh(mesh) => {
+                    self.vertices += AllocInfo::from_slice(&mesh.vertices);
+                    self.indices += AllocInfo::from_slice(&mesh.indices);
+                }
+                Primitive::Group(group) => {
+                    self = self.with_clipped_primitives(&group.primitives);
+                }
+            }
+        }
+        self
+    }
+
+    /// Fraction of layers whose tessellation was reused from the previous frame, if there were any layers.
+    pub fn cache_hit_rate(&self) -> Option<f32> {
+        let num_layers = self.cached_layers + self.tessellated_layers;
+        if num_layers == 0 {
+            None
+        } else {
+            Some(self.cached_layers as f32 / num_layers as f32)
+        }
+    }
+
     // pub fn total(&self) -> AllocInfo {
     //     self.shapes
     //         + self.shape_text
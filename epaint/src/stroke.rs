@@ -2,14 +2,24 @@
 
 use super::*;
 
-/// Describes the width and color of a line.
+/// Describes the width and color of a line,
+/// and optionally how its corners and ends look.
+///
+/// Dashes are set on the path instead, see [`PathShape::dash`], so that a [`Stroke`] stays small and `Copy`.
 ///
 /// The default stroke is the same as [`Stroke::none`].
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Stroke {
     pub width: f32,
     pub color: Color32,
+
+    /// How the corners of a path are painted.
+    pub join: LineJoin,
+
+    /// How the ends of an open path (and of each dash) are painted.
+    pub cap: LineCap,
 }
 
 impl Stroke {
@@ -24,6 +34,8 @@ impl Stroke {
         Self {
             width: width.into(),
             color: color.into(),
+            join: Default::default(),
+            cap: Default::default(),
         }
     }
 
@@ -32,6 +44,24 @@ impl Stroke {
     pub fn is_empty(&self) -> bool {
         self.width <= 0.0 || self.color == Color32::TRANSPARENT
     }
+
+    #[inline]
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    #[inline]
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Default joins and butt caps, which can be tessellated faster.
+    #[inline]
+    pub(crate) fn is_simple(&self) -> bool {
+        self.join == LineJoin::default() && self.cap == LineCap::Butt
+    }
 }
 
 impl<Color> From<(f32, Color)> for Stroke
@@ -47,8 +77,146 @@ where
 impl std::hash::Hash for Stroke {
     #[inline(always)]
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        let Self { width, color } = *self;
+        let Self {
+            width,
+            color,
+            join,
+            cap,
+        } = *self;
         crate::f32_hash(state, width);
         color.hash(state);
+        join.hash(state);
+        cap.hash(state);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How the corners of a stroked path are painted.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum LineJoin {
+    /// Extend the outer edges until they meet in a sharp corner.
+    ///
+    /// If the corner would stick out further than `limit` times half the stroke width,
+    /// it is cut off like [`Self::Bevel`].
+    Miter { limit: f32 },
+
+    /// A circular arc around the corner.
+    Round,
+
+    /// Cut off the corner.
+    Bevel,
+}
+
+impl Default for LineJoin {
+    /// Miter joins, with corners sharper than a right angle cut off.
+    fn default() -> Self {
+        Self::Miter {
+            limit: std::f32::consts::SQRT_2,
+        }
+    }
+}
+
+impl std::hash::Hash for LineJoin {
+    #[inline(always)]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        if let Self::Miter { limit } = *self {
+            crate::f32_hash(state, limit);
+        }
+    }
+}
+
+/// How the ends of a stroked path are painted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum LineCap {
+    /// The line ends exactly at the end point.
+    Butt,
+
+    /// A half circle around the end point.
+    Round,
+
+    /// The line extends half its width past the end point.
+    Square,
+}
+
+impl Default for LineCap {
+    fn default() -> Self {
+        Self::Butt
+    }
+}
+
+/// Alternating lengths of dashes and gaps (in points), like the SVG `stroke-dasharray`.
+///
+/// If an odd number of lengths is given, they are repeated
+/// (so `[5.0]` means five points on, then five points off).
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct DashPattern {
+    lengths: [f32; Self::MAX_LENGTHS],
+    num_lengths: usize,
+
+    /// How far into the pattern the line starts (in points).
+    pub offset: f32,
+}
+
+impl DashPattern {
+    /// How many lengths a pattern can have.
+    pub const MAX_LENGTHS: usize = 8;
+
+    /// Dashes and gaps of the given lengths, starting with a dash.
+    ///
+    /// Negative lengths are treated as zero.
+    /// Only the first [`Self::MAX_LENGTHS`] lengths are used.
+    pub fn new(lengths: &[f32]) -> Self {
+        let lengths = &lengths[..lengths.len().min(Self::MAX_LENGTHS)];
+        let mut pattern = Self {
+            lengths: [0.0; Self::MAX_LENGTHS],
+            num_lengths: lengths.len(),
+            offset: 0.0,
+        };
+        for (to, from) in pattern.lengths.iter_mut().zip(lengths) {
+            *to = from.max(0.0);
+        }
+        pattern
+    }
+
+    /// Start this many points into the pattern.
+    #[inline]
+    pub fn offset(mut self, offset: f32) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// The dash and gap lengths, as given to [`Self::new`].
+    pub fn lengths(&self) -> &[f32] {
+        &self.lengths[..self.num_lengths]
+    }
+
+    /// The lengths, repeated to get an even number of them (dash, gap, dash, gap, …).
+    pub(crate) fn dashes_and_gaps(&self) -> impl Iterator<Item = f32> + Clone + '_ {
+        let repeats = if self.num_lengths % 2 == 0 { 1 } else { 2 };
+        self.lengths()
+            .iter()
+            .copied()
+            .cycle()
+            .take(repeats * self.num_lengths)
+    }
+
+    /// Length of one full cycle of dashes and gaps.
+    pub(crate) fn period(&self) -> f32 {
+        self.dashes_and_gaps().sum()
+    }
+}
+
+impl std::hash::Hash for DashPattern {
+    #[inline(always)]
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        for &length in self.lengths() {
+            crate::f32_hash(state, length);
+        }
+        crate::f32_hash(state, self.offset);
     }
 }
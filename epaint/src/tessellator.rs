@@ -172,8 +172,8 @@ impl Path {
     }
 
     /// The path is taken to be closed (i.e. returning to the start again).
-    pub fn fill(&self, color: Color32, options: &TessellationOptions, out: &mut Mesh) {
-        fill_closed_path(&self.0, color, options, out);
+    pub fn fill(&self, brush: impl Into<Brush>, options: &TessellationOptions, out: &mut Mesh) {
+        fill_closed_path(&self.0, &brush.into(), options, out);
     }
 }
 
@@ -270,6 +270,10 @@ pub struct TessellationOptions {
     /// Default: `true`.
     pub anti_alias: bool,
 
+    /// Maximum distance (in physical pixels) between a bezier curve and the line segments it is turned into.
+    /// Default: `0.1`.
+    pub bezier_tolerance: f32,
+
     /// If `true` (default) cull certain primitives before tessellating them.
     /// This likely makes
     pub coarse_tessellation_culling: bool,
@@ -294,6 +298,7 @@ impl Default for TessellationOptions {
             pixels_per_point: 1.0,
             aa_size: 1.0,
             anti_alias: true,
+            bezier_tolerance: 0.1,
             coarse_tessellation_culling: true,
             round_text_to_pixels: true,
             debug_paint_text_rects: false,
@@ -327,14 +332,23 @@ impl TessellationOptions {
 /// Tessellate the given convex area into a polygon.
 fn fill_closed_path(
     path: &[PathPoint],
-    color: Color32,
+    brush: &Brush,
     options: &TessellationOptions,
     out: &mut Mesh,
 ) {
-    if color == Color32::TRANSPARENT {
+    if brush.is_transparent() {
         return;
     }
 
+    if out.is_empty() {
+        out.texture_id = brush.texture_id();
+    } else {
+        crate::epaint_assert!(
+            out.texture_id == brush.texture_id(),
+            "Can't fill using a different texture than the rest of the Mesh"
+        );
+    }
+
     let n = path.len() as u32;
     if options.anti_alias {
         out.reserve_triangles(3 * n as usize);
@@ -342,15 +356,25 @@ fn fill_closed_path(
         let color_outer = Color32::TRANSPARENT;
         let idx_inner = out.vertices.len() as u32;
         let idx_outer = idx_inner + 1;
+        for p in path {
+            let dm = 0.5 * options.aa_size * p.normal;
+            let inner = brush.vertex(p.pos - dm);
+            out.vertices.push(inner);
+            out.vertices.push(Vertex {
+                pos: p.pos + dm,
+                uv: inner.uv,
+                color: color_outer,
+            });
+        }
         for i in 2..n {
-            out.add_triangle(idx_inner + 2 * (i - 1), idx_inner, idx_inner + 2 * i);
+            add_fill_triangle(
+                out,
+                brush,
+                [idx_inner + 2 * (i - 1), idx_inner, idx_inner + 2 * i],
+            );
         }
         let mut i0 = n - 1;
         for i1 in 0..n {
-            let p1 = &path[i1 as usize];
-            let dm = 0.5 * options.aa_size * p1.normal;
-            out.colored_vertex(p1.pos - dm, color);
-            out.colored_vertex(p1.pos + dm, color_outer);
             out.add_triangle(idx_inner + i1 * 2, idx_inner + i0 * 2, idx_outer + 2 * i0);
             out.add_triangle(idx_outer + i0 * 2, idx_outer + i1 * 2, idx_inner + 2 * i1);
             i0 = i1;
@@ -358,15 +382,275 @@ fn fill_closed_path(
     } else {
         out.reserve_triangles(n as usize);
         let idx = out.vertices.len() as u32;
-        out.vertices.extend(path.iter().map(|p| Vertex {
-            pos: p.pos,
-            uv: WHITE_UV,
-            color,
-        }));
+        out.vertices
+            .extend(path.iter().map(|p| brush.vertex(p.pos)));
         for i in 2..n {
-            out.add_triangle(idx, idx + i - 1, idx + i);
+            add_fill_triangle(out, brush, [idx, idx + i - 1, idx + i]);
+        }
+    }
+}
+
+/// Add a triangle between three vertices of the fill.
+///
+/// Gradients are not linear across a triangle,
+/// so large triangles are split up into smaller ones, each with their own vertices.
+fn add_fill_triangle(out: &mut Mesh, brush: &Brush, [a, b, c]: [u32; 3]) {
+    let max_triangle_size = if let Some(max_triangle_size) = brush.max_triangle_size() {
+        max_triangle_size
+    } else {
+        out.add_triangle(a, b, c);
+        return;
+    };
+
+    let pa = out.vertices[a as usize].pos;
+    let pb = out.vertices[b as usize].pos;
+    let pc = out.vertices[c as usize].pos;
+    let longest_edge = pa.distance(pb).max(pb.distance(pc)).max(pc.distance(pa));
+    let k = (longest_edge / max_triangle_size).ceil().clamp(1.0, 16.0) as u32;
+    if k == 1 {
+        out.add_triangle(a, b, c);
+        return;
+    }
+
+    // A triangular grid, with row `i` having `k + 1 - i` vertices:
+    let idx = out.vertices.len() as u32;
+    let (ab, ac) = ((pb - pa) / k as f32, (pc - pa) / k as f32);
+    for i in 0..=k {
+        for j in 0..=(k - i) {
+            out.vertices
+                .push(brush.vertex(pa + i as f32 * ab + j as f32 * ac));
+        }
+    }
+    let row_start = |i: u32| idx + i * (k + 1) - i * (i.saturating_sub(1)) / 2;
+    for i in 0..k {
+        let (row, next_row) = (row_start(i), row_start(i + 1));
+        for j in 0..(k - i) {
+            out.add_triangle(row + j, row + j + 1, next_row + j);
+            if j + 1 < k - i {
+                out.add_triangle(row + j + 1, next_row + j + 1, next_row + j);
+            }
+        }
+    }
+}
+
+/// Fill any polygon, concave or self-intersecting, possibly with holes.
+///
+/// Every sub-path is implicitly closed.
+///
+/// The area is cut into horizontal bands at every vertex and edge intersection.
+/// Within each band no edges cross, so the inside is a set of trapezoids.
+///
+/// The edges are sorted by their top, so that only edges that overlap vertically
+/// are tested for intersections, and each band only looks at the edges that span it.
+fn fill_polygon(sub_paths: &[Vec<Pos2>], fill_rule: FillRule, brush: &Brush, out: &mut Mesh) {
+    #[derive(Clone, Copy)]
+    struct Edge {
+        top: Pos2,
+        bottom: Pos2,
+        winding: i32,
+    }
+
+    impl Edge {
+        fn x_at(&self, y: f32) -> f32 {
+            let t = (y - self.top.y) / (self.bottom.y - self.top.y);
+            lerp(self.top.x..=self.bottom.x, t)
+        }
+    }
+
+    if brush.is_transparent() {
+        return;
+    }
+
+    if out.is_empty() {
+        out.texture_id = brush.texture_id();
+    } else {
+        crate::epaint_assert!(
+            out.texture_id == brush.texture_id(),
+            "Can't fill using a different texture than the rest of the Mesh"
+        );
+    }
+
+    let mut edges = vec![];
+    for points in sub_paths {
+        for (i, &a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            if a.y < b.y {
+                edges.push(Edge {
+                    top: a,
+                    bottom: b,
+                    winding: 1,
+                });
+            } else if b.y < a.y {
+                edges.push(Edge {
+                    top: b,
+                    bottom: a,
+                    winding: -1,
+                });
+            }
+            // Horizontal edges don't matter.
+        }
+    }
+
+    edges.sort_by(|a, b| {
+        a.top
+            .y
+            .partial_cmp(&b.top.y)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut ys: Vec<f32> = edges
+        .iter()
+        .flat_map(|edge| [edge.top.y, edge.bottom.y])
+        .collect();
+    for (i, a) in edges.iter().enumerate() {
+        for b in &edges[i + 1..] {
+            if a.bottom.y <= b.top.y {
+                break; // This and all following edges start below `a`.
+            }
+            if let Some(y) = segment_intersection_y([a.top, a.bottom], [b.top, b.bottom]) {
+                ys.push(y);
+            }
         }
     }
+    ys.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    ys.dedup();
+
+    let mut next_edge = 0;
+    let mut active: Vec<Edge> = vec![];
+    let mut sorted: Vec<(f32, Edge)> = vec![];
+    for band in ys.windows(2) {
+        let (y0, y1) = (band[0], band[1]);
+        if y1 - y0 <= f32::EPSILON * y0.abs().max(1.0) {
+            continue;
+        }
+        let y_mid = 0.5 * (y0 + y1);
+
+        while next_edge < edges.len() && edges[next_edge].top.y <= y0 {
+            active.push(edges[next_edge]);
+            next_edge += 1;
+        }
+        active.retain(|edge| y1 <= edge.bottom.y);
+
+        sorted.clear();
+        sorted.extend(active.iter().map(|edge| (edge.x_at(y_mid), *edge)));
+        sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut winding = 0;
+        for pair in sorted.windows(2) {
+            let (left, right) = (pair[0].1, pair[1].1);
+            winding += left.winding;
+            if !is_inside(fill_rule, winding) {
+                continue;
+            }
+
+            let idx = out.vertices.len() as u32;
+            for pos in [
+                pos2(left.x_at(y0), y0),
+                pos2(right.x_at(y0), y0),
+                pos2(right.x_at(y1), y1),
+                pos2(left.x_at(y1), y1),
+            ] {
+                out.vertices.push(brush.vertex(pos));
+            }
+            add_fill_triangle(out, brush, [idx, idx + 1, idx + 2]);
+            add_fill_triangle(out, brush, [idx, idx + 2, idx + 3]);
+        }
+    }
+}
+
+fn is_inside(fill_rule: FillRule, winding: i32) -> bool {
+    match fill_rule {
+        FillRule::NonZero => winding != 0,
+        FillRule::EvenOdd => winding % 2 != 0,
+    }
+}
+
+/// How many times the sub-paths wind around `pos`.
+fn winding_number(sub_paths: &[Vec<Pos2>], pos: Pos2) -> i32 {
+    let mut winding = 0;
+    for points in sub_paths {
+        for (i, &a) in points.iter().enumerate() {
+            let b = points[(i + 1) % points.len()];
+            if (a.y <= pos.y) != (b.y <= pos.y) {
+                let x = a.x + (pos.y - a.y) / (b.y - a.y) * (b.x - a.x);
+                if pos.x < x {
+                    winding += if a.y < b.y { 1 } else { -1 };
+                }
+            }
+        }
+    }
+    winding
+}
+
+/// Is the fill to the right of the edges of `points` (where the normals of [`Path::add_line_loop`] point)?
+///
+/// Looks just to the right of the longest edge.
+fn is_fill_to_the_right(sub_paths: &[Vec<Pos2>], fill_rule: FillRule, points: &[Pos2]) -> bool {
+    let longest_edge = (0..points.len())
+        .map(|i| [points[i], points[(i + 1) % points.len()]])
+        .max_by(|a, b| {
+            let (a, b) = (a[0].distance_sq(a[1]), b[0].distance_sq(b[1]));
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+    let [a, b] = match longest_edge {
+        Some(edge) if edge[0] != edge[1] => edge,
+        _ => return false,
+    };
+    let length = a.distance(b);
+    let normal = (b - a).normalized().rot90();
+    let pos = a + 0.5 * (b - a) + 0.01 * length.min(1.0) * normal;
+    is_inside(fill_rule, winding_number(sub_paths, pos))
+}
+
+/// The y coordinate where two line segments cross, if they do.
+fn segment_intersection_y(a: [Pos2; 2], b: [Pos2; 2]) -> Option<f32> {
+    let da = a[1] - a[0];
+    let db = b[1] - b[0];
+    let denominator = da.x * db.y - da.y * db.x;
+    if denominator == 0.0 {
+        return None; // parallel
+    }
+    let ab = b[0] - a[0];
+    let t = (ab.x * db.y - ab.y * db.x) / denominator;
+    let u = (ab.x * da.y - ab.y * da.x) / denominator;
+    if 0.0 < t && t < 1.0 && 0.0 < u && u < 1.0 {
+        Some(a[0].y + t * da.y)
+    } else {
+        None
+    }
+}
+
+/// Smooth the edges of a [`fill_polygon`] by fading out the brush outwards from the outline.
+///
+/// The normals of `path` must point away from the fill.
+/// The feather is only on the outside, so that it doesn't overlap the fill
+/// (which would show as a darker rim on translucent fills).
+fn feather_outline(
+    path: &[PathPoint],
+    brush: &Brush,
+    options: &TessellationOptions,
+    out: &mut Mesh,
+) {
+    let n = path.len() as u32;
+    let idx = out.vertices.len() as u32;
+    out.reserve_triangles(2 * n as usize);
+    out.reserve_vertices(2 * n as usize);
+
+    let mut i0 = n - 1;
+    for i1 in 0..n {
+        let p1 = &path[i1 as usize];
+        let inner = brush.vertex(p1.pos);
+        out.vertices.push(inner);
+        out.vertices.push(Vertex {
+            pos: p1.pos + options.aa_size * p1.normal,
+            uv: inner.uv,
+            color: Color32::TRANSPARENT,
+        });
+
+        out.add_triangle(idx + 2 * i0, idx + 2 * i0 + 1, idx + 2 * i1);
+        out.add_triangle(idx + 2 * i0 + 1, idx + 2 * i1, idx + 2 * i1 + 1);
+        i0 = i1;
+    }
 }
 
 /// Tessellate the given path as a stroke with thickness.
@@ -604,6 +888,262 @@ fn mul_color(color: Color32, factor: f32) -> Color32 {
 
 // ----------------------------------------------------------------------------
 
+/// Tessellate a stroke that uses [`Stroke::join`], [`Stroke::cap`] or a [`DashPattern`].
+///
+/// `path` is only used as a scratch-pad.
+fn stroke_styled(
+    points: &[Pos2],
+    path_type: PathType,
+    stroke: Stroke,
+    dash: Option<&DashPattern>,
+    options: &TessellationOptions,
+    path: &mut Path,
+    out: &mut Mesh,
+) {
+    if stroke.is_empty() || points.len() < 2 {
+        return;
+    }
+
+    let dashes = dash.and_then(|dash| split_into_dashes(points, path_type, dash));
+
+    if let Some(dashes) = dashes {
+        for dash in dashes {
+            path.clear();
+            add_styled_points(path, &dash.points, PathType::Open, &stroke, dash.direction);
+            stroke_path(&path.0, PathType::Open, stroke, options, out);
+        }
+    } else {
+        let direction = (points[1] - points[0]).normalized();
+        path.clear();
+        add_styled_points(path, points, path_type, &stroke, direction);
+        stroke_path(&path.0, path_type, stroke, options, out);
+    }
+}
+
+struct Dash {
+    points: Vec<Pos2>,
+
+    /// Direction of the line where the dash starts,
+    /// so that dashes of zero length can still get caps.
+    direction: Vec2,
+}
+
+/// Returns `None` if the line isn't broken up by the pattern.
+fn split_into_dashes(
+    points: &[Pos2],
+    path_type: PathType,
+    dash: &DashPattern,
+) -> Option<Vec<Dash>> {
+    let lengths: Vec<f32> = dash.dashes_and_gaps().collect();
+    let period = dash.period();
+    if lengths.is_empty() || period <= 0.0 {
+        return None;
+    }
+
+    let mut segments: Vec<[Pos2; 2]> = points.windows(2).map(|w| [w[0], w[1]]).collect();
+    if path_type == PathType::Closed {
+        segments.push([points[points.len() - 1], points[0]]);
+    }
+
+    // Find where in the pattern we start:
+    let mut index = 0;
+    let mut into_pattern = dash.offset.rem_euclid(period);
+    while into_pattern > 0.0 && into_pattern >= lengths[index] {
+        into_pattern -= lengths[index];
+        index = (index + 1) % lengths.len();
+    }
+    let mut remaining = lengths[index] - into_pattern;
+    let mut on = index % 2 == 0;
+    let starts_on = on;
+
+    let mut dashes = vec![];
+    let mut current = Dash {
+        points: vec![],
+        direction: (points[1] - points[0]).normalized(),
+    };
+    if on {
+        current.points.push(points[0]);
+    }
+
+    for [a, b] in segments {
+        let segment_length = a.distance(b);
+        if segment_length <= 0.0 {
+            continue;
+        }
+        let direction = (b - a) / segment_length;
+
+        let mut t = 0.0;
+        while segment_length - t > remaining {
+            t += remaining;
+            let pos = a + t * direction;
+            current.points.push(pos);
+            if on {
+                dashes.push(std::mem::replace(
+                    &mut current,
+                    Dash {
+                        points: vec![],
+                        direction,
+                    },
+                ));
+            } else {
+                current.direction = direction;
+            }
+            on = !on;
+            index = (index + 1) % lengths.len();
+            remaining = lengths[index];
+        }
+        remaining -= segment_length - t;
+
+        if on {
+            current.points.push(b);
+        }
+    }
+
+    if dashes.is_empty() && on {
+        return None; // One long dash
+    }
+
+    if on {
+        if path_type == PathType::Closed && starts_on && !dashes.is_empty() {
+            // Continue the last dash into the first one:
+            let first = dashes.remove(0);
+            current.points.extend(first.points.into_iter().skip(1));
+        }
+        dashes.push(current);
+    }
+
+    Some(dashes)
+}
+
+/// Adds the points of a stroke to `path`, with normals that give it the corners and ends of the stroke.
+///
+/// `direction` is used for the caps if all points are at the same position.
+fn add_styled_points(
+    path: &mut Path,
+    points: &[Pos2],
+    path_type: PathType,
+    stroke: &Stroke,
+    direction: Vec2,
+) {
+    let mut points: Vec<Pos2> = points.to_vec();
+    points.dedup();
+    if path_type == PathType::Closed {
+        while points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+    }
+
+    let n = points.len();
+    let half_width = stroke.width / 2.0;
+
+    if n == 0 || (n == 1 && (path_type == PathType::Closed || stroke.cap == LineCap::Butt)) {
+        return;
+    }
+
+    let dir = |i: usize| (points[(i + 1) % n] - points[i]).normalized();
+
+    match path_type {
+        PathType::Closed => {
+            for (i, &pos) in points.iter().enumerate() {
+                let incoming = dir((i + n - 1) % n);
+                add_join(path, pos, incoming, dir(i), stroke.join, half_width);
+            }
+        }
+        PathType::Open => {
+            let first_dir = if n == 1 { direction } else { dir(0) };
+            add_start_cap(path, points[0], first_dir, stroke.cap, half_width);
+            for (i, &pos) in points.iter().enumerate().take(n - 1).skip(1) {
+                add_join(path, pos, dir(i - 1), dir(i), stroke.join, half_width);
+            }
+            let last_dir = if n == 1 { direction } else { dir(n - 2) };
+            add_end_cap(path, points[n - 1], last_dir, stroke.cap, half_width);
+        }
+    }
+}
+
+/// Number of segments for an arc of this angle.
+fn num_arc_segments(angle: f32, radius: f32) -> usize {
+    (angle.abs() * radius).ceil().clamp(1.0, 32.0) as usize
+}
+
+/// Ends with a point at `pos` with the normal of the line.
+fn add_start_cap(path: &mut Path, pos: Pos2, dir: Vec2, cap: LineCap, half_width: f32) {
+    let normal = dir.rot90();
+    match cap {
+        LineCap::Butt => path.add_point(pos, normal),
+        LineCap::Square => path.add_point(pos - half_width * dir, normal),
+        LineCap::Round => {
+            // Sweep the normal around the back of the end point:
+            let n = num_arc_segments(TAU / 2.0, half_width).max(2);
+            for i in 0..=n {
+                let angle = remap(i as f32, 0.0..=n as f32, 0.0..=TAU / 2.0);
+                path.add_point(pos, -normal * angle.cos() - dir * angle.sin());
+            }
+        }
+    }
+}
+
+/// Starts with a point at `pos` with the normal of the line.
+fn add_end_cap(path: &mut Path, pos: Pos2, dir: Vec2, cap: LineCap, half_width: f32) {
+    let normal = dir.rot90();
+    match cap {
+        LineCap::Butt => path.add_point(pos, normal),
+        LineCap::Square => path.add_point(pos + half_width * dir, normal),
+        LineCap::Round => {
+            // Sweep the normal around the front of the end point:
+            let n = num_arc_segments(TAU / 2.0, half_width).max(2);
+            for i in 0..=n {
+                let angle = remap(i as f32, 0.0..=n as f32, 0.0..=TAU / 2.0);
+                path.add_point(pos, normal * angle.cos() + dir * angle.sin());
+            }
+        }
+    }
+}
+
+/// A corner at `pos`, between a line going in direction `dir0` and one going in direction `dir1`.
+fn add_join(path: &mut Path, pos: Pos2, dir0: Vec2, dir1: Vec2, join: LineJoin, half_width: f32) {
+    let n0 = dir0.rot90();
+    let n1 = dir1.rot90();
+
+    let dot = n0.x * n1.x + n0.y * n1.y;
+    let cross = n0.x * n1.y - n0.y * n1.x;
+    if dot > 0.9999 {
+        // (almost) straight
+        path.add_point(pos, (n0 + n1).normalized());
+        return;
+    }
+
+    match join {
+        LineJoin::Miter { limit } => {
+            let normal = (n0 + n1) / 2.0;
+            let length_sq = normal.length_sq();
+            // How far the corner sticks out, relative to half the width:
+            let miter_length = 1.0 / length_sq.sqrt();
+            if length_sq > 0.0 && miter_length <= limit {
+                path.add_point(pos, normal / length_sq);
+            } else {
+                path.add_point(pos, n0);
+                path.add_point(pos, n1);
+            }
+        }
+        LineJoin::Bevel => {
+            path.add_point(pos, n0);
+            path.add_point(pos, n1);
+        }
+        LineJoin::Round => {
+            let angle = cross.atan2(dot);
+            let perpendicular = vec2(-n0.y, n0.x);
+            let n = num_arc_segments(angle, half_width);
+            for i in 0..=n {
+                let a = remap(i as f32, 0.0..=n as f32, 0.0..=angle);
+                path.add_point(pos, n0 * a.cos() + perpendicular * a.sin());
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Converts [`Shape`]s into triangles ([`Mesh`]).
 ///
 /// For performance reasons it is smart to reuse the same `Tessellator`.
@@ -644,11 +1184,17 @@ impl Tessellator {
                     self.tessellate_shape(tex_size, shape, out);
                 }
             }
+            Shape::Group(group) => {
+                for ClippedShape(_, shape) in crate::group::approximate_group(clip_rect, group) {
+                    self.tessellate_shape(tex_size, shape, out);
+                }
+            }
             Shape::Circle(CircleShape {
                 center,
                 radius,
                 fill,
                 stroke,
+                dash,
             }) => {
                 if radius <= 0.0 {
                     return;
@@ -663,7 +1209,22 @@ impl Tessellator {
                 self.scratchpad_path.clear();
                 self.scratchpad_path.add_circle(center, radius);
                 self.scratchpad_path.fill(fill, options, out);
-                self.scratchpad_path.stroke_closed(stroke, options, out);
+                if stroke.is_simple() && dash.is_none() {
+                    self.scratchpad_path.stroke_closed(stroke, options, out);
+                } else {
+                    self.scratchpad_points.clear();
+                    self.scratchpad_points
+                        .extend(self.scratchpad_path.0.iter().map(|p| p.pos));
+                    stroke_styled(
+                        &self.scratchpad_points,
+                        PathType::Closed,
+                        stroke,
+                        dash.as_ref(),
+                        options,
+                        &mut self.scratchpad_path,
+                        out,
+                    );
+                }
             }
             Shape::Mesh(mesh) => {
                 if !mesh.is_valid() {
@@ -690,13 +1251,28 @@ impl Tessellator {
                     return;
                 }
 
-                self.scratchpad_path.clear();
-                self.scratchpad_path.add_line_segment(points);
-                self.scratchpad_path.stroke_open(stroke, options, out);
+                if stroke.is_simple() {
+                    self.scratchpad_path.clear();
+                    self.scratchpad_path.add_line_segment(points);
+                    self.scratchpad_path.stroke_open(stroke, options, out);
+                } else {
+                    stroke_styled(
+                        &points,
+                        PathType::Open,
+                        stroke,
+                        None,
+                        options,
+                        &mut self.scratchpad_path,
+                        out,
+                    );
+                }
             }
             Shape::Path(path_shape) => {
                 self.tessellate_path(path_shape, out);
             }
+            Shape::ComplexPath(path_shape) => {
+                self.tessellate_complex_path(&path_shape, out);
+            }
             Shape::Rect(rect_shape) => {
                 self.tessellate_rect(&rect_shape, out);
             }
@@ -729,6 +1305,7 @@ impl Tessellator {
             closed,
             fill,
             stroke,
+            dash,
         } = path_shape;
 
         self.scratchpad_path.clear();
@@ -738,7 +1315,7 @@ impl Tessellator {
             self.scratchpad_path.add_open_points(&points);
         }
 
-        if fill != Color32::TRANSPARENT {
+        if !fill.is_transparent() {
             crate::epaint_assert!(
                 closed,
                 "You asked to fill a path that is not closed. That makes no sense."
@@ -750,7 +1327,94 @@ impl Tessellator {
         } else {
             PathType::Open
         };
-        self.scratchpad_path.stroke(typ, stroke, &self.options, out);
+        if stroke.is_simple() && dash.is_none() {
+            self.scratchpad_path.stroke(typ, stroke, &self.options, out);
+        } else {
+            stroke_styled(
+                &points,
+                typ,
+                stroke,
+                dash.as_ref(),
+                &self.options,
+                &mut self.scratchpad_path,
+                out,
+            );
+        }
+    }
+
+    pub(crate) fn tessellate_complex_path(
+        &mut self,
+        path_shape: &ComplexPathShape,
+        out: &mut Mesh,
+    ) {
+        if self.options.coarse_tessellation_culling
+            && !path_shape.bounding_rect().intersects(self.clip_rect)
+        {
+            return;
+        }
+
+        let tolerance = self.options.bezier_tolerance / self.options.pixels_per_point;
+        let sub_paths = path_shape.flatten(tolerance);
+
+        if !path_shape.fill.is_transparent() {
+            let polygons: Vec<Vec<Pos2>> = sub_paths
+                .iter()
+                .map(|(points, _closed)| points.clone())
+                .collect();
+            fill_polygon(&polygons, path_shape.fill_rule, &path_shape.fill, out);
+
+            if self.options.anti_alias {
+                for points in &polygons {
+                    if points.len() < 3 {
+                        continue;
+                    }
+                    self.scratchpad_path.clear();
+                    self.scratchpad_path.add_line_loop(points);
+                    if is_fill_to_the_right(&polygons, path_shape.fill_rule, points) {
+                        for point in &mut self.scratchpad_path.0 {
+                            point.normal = -point.normal;
+                        }
+                    }
+                    feather_outline(
+                        &self.scratchpad_path.0,
+                        &path_shape.fill,
+                        &self.options,
+                        out,
+                    );
+                }
+            }
+        }
+
+        let stroke = path_shape.stroke;
+        for (points, closed) in &sub_paths {
+            if points.len() < 2 {
+                continue;
+            }
+            let typ = if *closed {
+                PathType::Closed
+            } else {
+                PathType::Open
+            };
+            if stroke.is_simple() && path_shape.dash.is_none() {
+                self.scratchpad_path.clear();
+                if *closed {
+                    self.scratchpad_path.add_line_loop(points);
+                } else {
+                    self.scratchpad_path.add_open_points(points);
+                }
+                self.scratchpad_path.stroke(typ, stroke, &self.options, out);
+            } else {
+                stroke_styled(
+                    points,
+                    typ,
+                    stroke,
+                    path_shape.dash.as_ref(),
+                    &self.options,
+                    &mut self.scratchpad_path,
+                    out,
+                );
+            }
+        }
     }
 
     pub(crate) fn tessellate_rect(&mut self, rect: &RectShape, out: &mut Mesh) {
@@ -759,6 +1423,7 @@ impl Tessellator {
             corner_radius,
             fill,
             stroke,
+            dash,
         } = *rect;
 
         if self.options.coarse_tessellation_culling
@@ -780,7 +1445,19 @@ impl Tessellator {
         path::rounded_rectangle(&mut self.scratchpad_points, rect, corner_radius);
         path.add_line_loop(&self.scratchpad_points);
         path.fill(fill, &self.options, out);
-        path.stroke_closed(stroke, &self.options, out);
+        if stroke.is_simple() && dash.is_none() {
+            path.stroke_closed(stroke, &self.options, out);
+        } else {
+            stroke_styled(
+                &self.scratchpad_points,
+                PathType::Closed,
+                stroke,
+                dash.as_ref(),
+                &self.options,
+                path,
+                out,
+            );
+        }
     }
 
     pub fn tessellate_text(&mut self, tex_size: [usize; 2], text_shape: TextShape, out: &mut Mesh) {
@@ -877,6 +1554,72 @@ impl Tessellator {
     }
 }
 
+/// A [`Mesh`] only has one texture, so the stroke of a shape with a textured [`Brush`]
+/// (which uses the egui texture) needs to go into a separate shape.
+///
+/// A [`Shape::Vec`] is flattened, since its shapes may need different meshes.
+fn split_textured_fill(clipped_shape: ClippedShape) -> Vec<ClippedShape> {
+    let ClippedShape(clip_rect, shape) = clipped_shape;
+    if let Shape::Vec(shapes) = shape {
+        return shapes
+            .into_iter()
+            .flat_map(|shape| split_textured_fill(ClippedShape(clip_rect, shape)))
+            .collect();
+    }
+    if shape.texture_id() == TextureId::Egui {
+        return vec![ClippedShape(clip_rect, shape)];
+    }
+
+    let stroke_shape = match &shape {
+        Shape::Circle(circle_shape) if !circle_shape.stroke.is_empty() => {
+            Shape::Circle(CircleShape {
+                fill: Brush::default(),
+                ..*circle_shape
+            })
+        }
+        Shape::Path(path_shape) if !path_shape.stroke.is_empty() => Shape::Path(PathShape {
+            fill: Brush::default(),
+            ..path_shape.clone()
+        }),
+        Shape::ComplexPath(path_shape) if !path_shape.stroke.is_empty() => {
+            Shape::ComplexPath(ComplexPathShape {
+                fill: Brush::default(),
+                ..path_shape.clone()
+            })
+        }
+        Shape::Rect(rect_shape) if !rect_shape.stroke.is_empty() => Shape::Rect(RectShape {
+            fill: Brush::default(),
+            ..*rect_shape
+        }),
+        _ => return vec![ClippedShape(clip_rect, shape)],
+    };
+
+    let fill_shape = match shape {
+        Shape::Circle(circle_shape) => Shape::Circle(CircleShape {
+            stroke: Stroke::none(),
+            ..circle_shape
+        }),
+        Shape::Path(path_shape) => Shape::Path(PathShape {
+            stroke: Stroke::none(),
+            ..path_shape
+        }),
+        Shape::ComplexPath(path_shape) => Shape::ComplexPath(ComplexPathShape {
+            stroke: Stroke::none(),
+            ..path_shape
+        }),
+        Shape::Rect(rect_shape) => Shape::Rect(RectShape {
+            stroke: Stroke::none(),
+            ..rect_shape
+        }),
+        shape => shape,
+    };
+
+    vec![
+        ClippedShape(clip_rect, fill_shape),
+        ClippedShape(clip_rect, stroke_shape),
+    ]
+}
+
 /// Turns [`Shape`]:s into sets of triangles.
 ///
 /// The given shapes will tessellated in the same order as they are given.
@@ -888,6 +1631,9 @@ impl Tessellator {
 ///
 /// The implementation uses a [`Tessellator`].
 ///
+/// The effects of a [`Shape::Group`] are approximated (see [`GroupEffects`]).
+/// Use [`tessellate_primitives`] if your backend can paint groups.
+///
 /// ## Returns
 /// A list of clip rectangles with matching [`Mesh`].
 pub fn tessellate_shapes(
@@ -899,7 +1645,7 @@ pub fn tessellate_shapes(
 
     let mut clipped_meshes: Vec<ClippedMesh> = Vec::default();
 
-    for ClippedShape(clip_rect, shape) in shapes {
+    for ClippedShape(clip_rect, shape) in flatten_groups(shapes).flat_map(split_textured_fill) {
         if !clip_rect.is_positive() {
             continue; // skip empty clip rectangles
         }
@@ -945,3 +1691,531 @@ pub fn tessellate_shapes(
 
     clipped_meshes
 }
+
+/// Replace groups (also nested in [`Shape::Vec`]) with their approximations.
+fn flatten_groups(shapes: Vec<ClippedShape>) -> impl Iterator<Item = ClippedShape> {
+    shapes
+        .into_iter()
+        .flat_map(|ClippedShape(clip_rect, shape)| {
+            if !shape.contains_group() {
+                return vec![ClippedShape(clip_rect, shape)];
+            }
+            let shapes = match shape {
+                Shape::Group(group) => crate::group::approximate_group(clip_rect, group),
+                Shape::Vec(shapes) => shapes
+                    .into_iter()
+                    .map(|shape| ClippedShape(clip_rect, shape))
+                    .collect(),
+                shape => vec![ClippedShape(clip_rect, shape)],
+            };
+            flatten_groups(shapes).collect()
+        })
+}
+
+/// Like [`tessellate_shapes`], but [`Shape::Group`]s become [`Primitive::Group`]s,
+/// for backends that can composite them with offscreen render targets.
+pub fn tessellate_primitives(
+    shapes: Vec<ClippedShape>,
+    options: TessellationOptions,
+    tex_size: [usize; 2],
+) -> Vec<ClippedPrimitive> {
+    let mut tessellator = Tessellator::from_options(options);
+    let mut clipped_primitives = Vec::default();
+    tessellate_into_primitives(
+        &mut tessellator,
+        tex_size,
+        shapes,
+        0.0,
+        &mut clipped_primitives,
+    );
+
+    if options.debug_paint_clip_rects {
+        for ClippedPrimitive(clip_rect, primitive) in &mut clipped_primitives {
+            if let Primitive::Mesh(mesh) = primitive {
+                tessellator.clip_rect = Rect::EVERYTHING;
+                tessellator.tessellate_shape(
+                    tex_size,
+                    Shape::rect_stroke(
+                        *clip_rect,
+                        0.0,
+                        Stroke::new(2.0, Color32::from_rgb(150, 255, 150)),
+                    ),
+                    mesh,
+                );
+            }
+        }
+    }
+
+    if options.debug_ignore_clip_rects {
+        set_clip_rects(&mut clipped_primitives, Rect::EVERYTHING);
+    }
+
+    clipped_primitives
+}
+
+/// `cull_margin`: how far outside the clip rectangle shapes can still be visible
+/// (because they are moved or blurred by the effects of a group).
+fn tessellate_into_primitives(
+    tessellator: &mut Tessellator,
+    tex_size: [usize; 2],
+    shapes: Vec<ClippedShape>,
+    cull_margin: f32,
+    out: &mut Vec<ClippedPrimitive>,
+) {
+    for ClippedShape(clip_rect, shape) in shapes.into_iter().flat_map(split_textured_fill) {
+        if !clip_rect.is_positive() {
+            continue; // skip empty clip rectangles
+        }
+
+        match shape {
+            Shape::Group(GroupShape { shapes, effects }) => {
+                let shapes = shapes
+                    .into_iter()
+                    .map(|shape| ClippedShape(clip_rect, shape))
+                    .collect();
+                let mut primitives = Vec::default();
+                tessellate_into_primitives(
+                    tessellator,
+                    tex_size,
+                    shapes,
+                    cull_margin + effects.margin(),
+                    &mut primitives,
+                );
+                out.push(ClippedPrimitive(
+                    clip_rect,
+                    Primitive::Group(GroupPrimitive {
+                        primitives,
+                        effects,
+                    }),
+                ));
+            }
+            shape => {
+                let start_new_mesh = match out.last() {
+                    Some(ClippedPrimitive(last_clip_rect, Primitive::Mesh(mesh))) => {
+                        *last_clip_rect != clip_rect || mesh.texture_id != shape.texture_id()
+                    }
+                    _ => true,
+                };
+
+                if start_new_mesh {
+                    out.push(ClippedPrimitive(
+                        clip_rect,
+                        Primitive::Mesh(Mesh::default()),
+                    ));
+                }
+
+                if let Some(ClippedPrimitive(_, Primitive::Mesh(mesh))) = out.last_mut() {
+                    tessellator.clip_rect = clip_rect.expand(cull_margin);
+                    tessellator.tessellate_shape(tex_size, shape, mesh);
+                    crate::epaint_assert!(mesh.is_valid(), "Tessellator generated invalid Mesh");
+                }
+            }
+        }
+    }
+}
+
+fn set_clip_rects(clipped_primitives: &mut [ClippedPrimitive], rect: Rect) {
+    for ClippedPrimitive(clip_rect, primitive) in clipped_primitives {
+        *clip_rect = rect;
+        if let Primitive::Group(group) = primitive {
+            set_clip_rects(&mut group.primitives, rect);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn textured_rect() -> Shape {
+        Shape::Rect(RectShape {
+            rect: Rect::from_min_size(pos2(1.0, 1.0), vec2(8.0, 8.0)),
+            corner_radius: 0.0,
+            fill: Brush::Texture {
+                texture_id: TextureId::User(7),
+                rect: Rect::from_min_size(pos2(1.0, 1.0), vec2(8.0, 8.0)),
+                uv: Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+                tint: Color32::WHITE,
+            },
+            stroke: Stroke::new(1.0, Color32::RED),
+            dash: None,
+        })
+    }
+
+    fn texture_ids(shapes: Vec<Shape>) -> Vec<TextureId> {
+        let clip_rect = Rect::from_min_size(Pos2::ZERO, vec2(10.0, 10.0));
+        let shapes = shapes
+            .into_iter()
+            .map(|shape| ClippedShape(clip_rect, shape))
+            .collect();
+        tessellate_shapes(shapes, Default::default(), [1, 1])
+            .into_iter()
+            .map(|ClippedMesh(_, mesh)| mesh.texture_id)
+            .collect()
+    }
+
+    #[test]
+    fn textured_fill_and_stroke_are_split() {
+        let split = split_textured_fill(ClippedShape(Rect::EVERYTHING, textured_rect()));
+        assert_eq!(split.len(), 2);
+        assert_eq!(split[0].1.texture_id(), TextureId::User(7));
+        assert_eq!(split[1].1.texture_id(), TextureId::Egui);
+
+        assert_eq!(
+            texture_ids(vec![textured_rect()]),
+            vec![TextureId::User(7), TextureId::Egui]
+        );
+    }
+
+    #[test]
+    fn untextured_shapes_are_not_split() {
+        let shape = Shape::rect_filled(Rect::EVERYTHING, 0.0, Color32::RED);
+        assert_eq!(
+            split_textured_fill(ClippedShape(Rect::EVERYTHING, shape)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn textured_fill_in_vec_is_split() {
+        let circle = Shape::circle_filled(pos2(5.0, 5.0), 2.0, Color32::RED);
+        let shapes = vec![Shape::Vec(vec![
+            circle.clone(),
+            Shape::Vec(vec![textured_rect()]),
+            circle,
+        ])];
+
+        let split = split_textured_fill(ClippedShape(Rect::EVERYTHING, shapes[0].clone()));
+        assert_eq!(split.len(), 4);
+        assert!(split
+            .iter()
+            .all(|ClippedShape(_, shape)| !matches!(shape, Shape::Vec(_))));
+
+        assert_eq!(
+            texture_ids(shapes),
+            vec![TextureId::Egui, TextureId::User(7), TextureId::Egui]
+        );
+    }
+
+    fn square(shape: ComplexPathShape, min: f32, max: f32, clockwise: bool) -> ComplexPathShape {
+        let corners = [
+            pos2(min, min),
+            pos2(max, min),
+            pos2(max, max),
+            pos2(min, max),
+        ];
+        let mut shape = shape.move_to(corners[0]);
+        if clockwise {
+            for &corner in &corners[1..] {
+                shape = shape.line_to(corner);
+            }
+        } else {
+            for &corner in corners[1..].iter().rev() {
+                shape = shape.line_to(corner);
+            }
+        }
+        shape.close()
+    }
+
+    fn tessellate_complex(shape: &ComplexPathShape, anti_alias: bool) -> Mesh {
+        let mut tessellator = Tessellator::from_options(TessellationOptions {
+            anti_alias,
+            ..Default::default()
+        });
+        let mut mesh = Mesh::default();
+        tessellator.tessellate_complex_path(shape, &mut mesh);
+        assert!(mesh.is_valid());
+        mesh
+    }
+
+    fn fill_area(shape: &ComplexPathShape) -> f32 {
+        let mesh = tessellate_complex(shape, false);
+        mesh.indices
+            .chunks(3)
+            .map(|triangle| {
+                let [a, b, c] = [0, 1, 2].map(|i| mesh.vertices[triangle[i] as usize].pos);
+                let (ab, ac) = (b - a, c - a);
+                0.5 * (ab.x * ac.y - ab.y * ac.x).abs()
+            })
+            .sum()
+    }
+
+    fn assert_area(shape: &ComplexPathShape, expected: f32) {
+        let area = fill_area(shape);
+        assert!(
+            (area - expected).abs() < 1e-3,
+            "Expected area {}, got {}",
+            expected,
+            area
+        );
+    }
+
+    #[test]
+    fn overlapping_sub_paths() {
+        for fill_rule in [FillRule::NonZero, FillRule::EvenOdd] {
+            let shape = ComplexPathShape::default()
+                .fill_rule(fill_rule)
+                .fill(Color32::RED);
+            let shape = square(square(shape, 0.0, 10.0, true), 5.0, 15.0, true);
+            let expected = match fill_rule {
+                FillRule::NonZero => 175.0,
+                FillRule::EvenOdd => 150.0, // without the overlap
+            };
+            assert_area(&shape, expected);
+        }
+    }
+
+    #[test]
+    fn self_intersecting_path() {
+        // A bow tie, crossing itself in the middle:
+        let bow_tie = |fill_rule| {
+            ComplexPathShape::default()
+                .move_to(pos2(0.0, 0.0))
+                .line_to(pos2(10.0, 10.0))
+                .line_to(pos2(10.0, 0.0))
+                .line_to(pos2(0.0, 10.0))
+                .close()
+                .fill_rule(fill_rule)
+                .fill(Color32::RED)
+        };
+        assert_area(&bow_tie(FillRule::NonZero), 50.0);
+        assert_area(&bow_tie(FillRule::EvenOdd), 50.0);
+    }
+
+    #[test]
+    fn holes() {
+        for fill_rule in [FillRule::NonZero, FillRule::EvenOdd] {
+            for hole_clockwise in [true, false] {
+                let shape = ComplexPathShape::default()
+                    .fill_rule(fill_rule)
+                    .fill(Color32::RED);
+                let shape = square(square(shape, 0.0, 10.0, true), 3.0, 7.0, hole_clockwise);
+                let expected = if fill_rule == FillRule::NonZero && hole_clockwise {
+                    100.0 // winding twice around the middle is still inside
+                } else {
+                    84.0
+                };
+                assert_area(&shape, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn feather_is_outside_the_fill() {
+        let in_square = |pos: Pos2, min: f32, max: f32| {
+            (min..=max).contains(&pos.x) && (min..=max).contains(&pos.y)
+        };
+        let strictly_in_square = |pos: Pos2, min: f32, max: f32| {
+            min < pos.x && pos.x < max && min < pos.y && pos.y < max
+        };
+
+        // Whatever the orientation of the sub-paths, the feather must fade out away from the fill:
+        for outer_clockwise in [true, false] {
+            for hole_clockwise in [true, false] {
+                let shape = ComplexPathShape::default()
+                    .fill_rule(FillRule::EvenOdd)
+                    .fill(Color32::from_rgba_unmultiplied(255, 0, 0, 128));
+                let shape = square(shape, 0.0, 10.0, outer_clockwise);
+                let shape = square(shape, 3.0, 7.0, hole_clockwise);
+                for vertex in &tessellate_complex(&shape, true).vertices {
+                    let pos = vertex.pos;
+                    if vertex.color == Color32::TRANSPARENT {
+                        assert!(
+                            !strictly_in_square(pos, 0.0, 10.0) || in_square(pos, 3.0, 7.0),
+                            "The feather at {:?} overlaps the fill",
+                            pos
+                        );
+                    } else {
+                        assert!(
+                            in_square(pos, 0.0, 10.0) && !strictly_in_square(pos, 3.0, 7.0),
+                            "{:?} is outside the fill",
+                            pos
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    fn dashes(points: &[Pos2], path_type: PathType, dash: &DashPattern) -> Option<Vec<Vec<Pos2>>> {
+        split_into_dashes(points, path_type, dash)
+            .map(|dashes| dashes.into_iter().map(|dash| dash.points).collect())
+    }
+
+    fn line_dashes(dash: DashPattern) -> Vec<[f32; 2]> {
+        dashes(&[pos2(0.0, 0.0), pos2(10.0, 0.0)], PathType::Open, &dash)
+            .unwrap()
+            .into_iter()
+            .map(|points| {
+                assert_eq!(points.len(), 2);
+                [points[0].x, points[1].x]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn dash_segmentation() {
+        assert_eq!(
+            line_dashes(DashPattern::new(&[2.0, 1.0])),
+            vec![[0.0, 2.0], [3.0, 5.0], [6.0, 8.0], [9.0, 10.0]]
+        );
+        assert_eq!(
+            line_dashes(DashPattern::new(&[2.0, 1.0]).offset(1.0)),
+            vec![[0.0, 1.0], [2.0, 4.0], [5.0, 7.0], [8.0, 10.0]]
+        );
+        // An odd number of lengths is repeated:
+        assert_eq!(
+            line_dashes(DashPattern::new(&[2.0])),
+            vec![[0.0, 2.0], [4.0, 6.0], [8.0, 10.0]]
+        );
+    }
+
+    #[test]
+    fn dashes_that_do_not_break_the_line() {
+        let line = [pos2(0.0, 0.0), pos2(10.0, 0.0)];
+        assert!(dashes(&line, PathType::Open, &DashPattern::new(&[])).is_none());
+        assert!(dashes(&line, PathType::Open, &DashPattern::new(&[0.0, 0.0])).is_none());
+        assert!(dashes(&line, PathType::Open, &DashPattern::new(&[20.0, 1.0])).is_none());
+    }
+
+    #[test]
+    fn dashes_go_around_corners_of_closed_paths() {
+        let square = [
+            pos2(0.0, 0.0),
+            pos2(4.0, 0.0),
+            pos2(4.0, 4.0),
+            pos2(0.0, 4.0),
+        ];
+        let dashes = dashes(
+            &square,
+            PathType::Closed,
+            &DashPattern::new(&[3.0, 1.0]).offset(2.0),
+        )
+        .unwrap();
+        assert_eq!(dashes.len(), 4);
+        assert_eq!(
+            dashes[0],
+            vec![pos2(2.0, 0.0), pos2(4.0, 0.0), pos2(4.0, 1.0)]
+        );
+        // The last dash continues into the first one:
+        assert_eq!(
+            dashes[3],
+            vec![pos2(0.0, 2.0), pos2(0.0, 0.0), pos2(1.0, 0.0)]
+        );
+    }
+
+    #[test]
+    fn dashed_path_has_gaps() {
+        let path = PathShape::line(
+            vec![pos2(0.0, 0.0), pos2(10.0, 0.0)],
+            Stroke::new(1.0, Color32::RED),
+        )
+        .dashed(DashPattern::new(&[2.0, 1.0]));
+        let mut mesh = Mesh::default();
+        let mut tessellator = Tessellator::from_options(TessellationOptions {
+            anti_alias: false,
+            ..Default::default()
+        });
+        tessellator.tessellate_path(path, &mut mesh);
+        assert!(!mesh.is_empty());
+        for vertex in &mesh.vertices {
+            let x = vertex.pos.x;
+            assert!(
+                !(2.0 < x && x < 3.0) && !(5.0 < x && x < 6.0) && !(8.0 < x && x < 9.0),
+                "Vertex in a gap: {:?}",
+                vertex.pos
+            );
+        }
+    }
+
+    #[test]
+    fn dashed_rect_and_circle() {
+        let tessellate = |shape: Shape| {
+            let mut mesh = Mesh::default();
+            let mut tessellator = Tessellator::from_options(TessellationOptions {
+                anti_alias: false,
+                ..Default::default()
+            });
+            tessellator.tessellate_shape([1, 1], shape, &mut mesh);
+            assert!(mesh.is_valid());
+            mesh
+        };
+        let stroke = Stroke::new(1.0, Color32::RED);
+        let dash = DashPattern::new(&[2.0, 1.0]);
+
+        // A dash ends somewhere along the top edge, so there are vertices between the corners:
+        let rect = RectShape::stroke(
+            Rect::from_min_size(pos2(0.0, 0.0), vec2(10.0, 10.0)),
+            0.0,
+            stroke,
+        );
+        let has_dash_ends = |mesh: &Mesh| {
+            mesh.vertices
+                .iter()
+                .any(|vertex| vertex.pos.y.abs() <= 0.5 && 1.0 < vertex.pos.x && vertex.pos.x < 9.0)
+        };
+        assert!(!has_dash_ends(&tessellate(rect.into())));
+        assert!(has_dash_ends(&tessellate(rect.dashed(dash).into())));
+
+        let circle = CircleShape::stroke(pos2(0.0, 0.0), 10.0, stroke);
+        let solid = tessellate(circle.into());
+        let dashed = tessellate(circle.dashed(dash).into());
+        assert!(dashed.indices.len() > solid.indices.len());
+    }
+
+    #[test]
+    fn dash_pattern_ignores_extra_lengths() {
+        let lengths = [1.0; DashPattern::MAX_LENGTHS + 2];
+        assert_eq!(
+            DashPattern::new(&lengths).lengths(),
+            &lengths[..DashPattern::MAX_LENGTHS]
+        );
+    }
+
+    fn stroke_mesh(points: Vec<Pos2>, stroke: Stroke) -> Mesh {
+        let mut mesh = Mesh::default();
+        let mut tessellator = Tessellator::from_options(TessellationOptions {
+            anti_alias: false,
+            ..Default::default()
+        });
+        tessellator.tessellate_path(PathShape::line(points, stroke), &mut mesh);
+        assert!(mesh.is_valid());
+        mesh
+    }
+
+    #[test]
+    fn line_caps() {
+        let x_range = |cap| {
+            let stroke = Stroke::new(2.0, Color32::RED).cap(cap);
+            let bounds = stroke_mesh(vec![pos2(0.0, 0.0), pos2(10.0, 0.0)], stroke).calc_bounds();
+            assert!((bounds.min.y + 1.0).abs() < 1e-4 && (bounds.max.y - 1.0).abs() < 1e-4);
+            [bounds.min.x, bounds.max.x]
+        };
+        let approx_eq =
+            |[a, b]: [f32; 2], [c, d]: [f32; 2]| (a - c).abs() < 1e-4 && (b - d).abs() < 1e-4;
+
+        assert!(approx_eq(x_range(LineCap::Butt), [0.0, 10.0]));
+        assert!(approx_eq(x_range(LineCap::Square), [-1.0, 11.0]));
+        assert!(approx_eq(x_range(LineCap::Round), [-1.0, 11.0]));
+    }
+
+    #[test]
+    fn line_joins() {
+        // How far the outer corner of a right angle sticks out:
+        let corner = |join| {
+            let stroke = Stroke::new(2.0, Color32::RED).join(join);
+            let points = vec![pos2(0.0, 0.0), pos2(10.0, 0.0), pos2(10.0, 10.0)];
+            stroke_mesh(points, stroke)
+                .vertices
+                .iter()
+                .map(|vertex| vertex.pos.x - vertex.pos.y)
+                .fold(f32::NEG_INFINITY, f32::max)
+        };
+
+        assert!((corner(LineJoin::Miter { limit: 2.0 }) - 12.0).abs() < 1e-4);
+        assert!((corner(LineJoin::Round) - (10.0 + std::f32::consts::SQRT_2)).abs() < 1e-4);
+        assert!((corner(LineJoin::Bevel) - 11.0).abs() < 1e-4);
+        // The miter of a right angle is √2 times half the width, so this limit cuts it off:
+        assert!((corner(LineJoin::Miter { limit: 1.2 }) - 11.0).abs() < 1e-4);
+    }
+}
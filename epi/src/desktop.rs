@@ -0,0 +1,329 @@
+//! File dialogs, notifications and other requests to the desktop environment.
+//!
+//! Requests are made with [`crate::Frame::desktop_request`] (or one of its shorthands,
+//! like [`crate::Frame::open_file_dialog`]) and never block the app.
+//! The response is picked up with [`crate::Frame::take_desktop_response`] in a later call to [`crate::App::update`].
+//!
+//! Each integration provides its own [`Desktop`].
+//! Use [`MockDesktop`] to test how an app handles the responses.
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Identifies a request made with [`crate::Frame::desktop_request`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RequestId(u64);
+
+impl RequestId {
+    fn next() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// Options for a file open or save dialog.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FileDialog {
+    /// The title of the dialog window.
+    pub title: Option<String>,
+
+    /// The directory to start in.
+    pub directory: Option<PathBuf>,
+
+    /// The suggested file name, for save dialogs.
+    pub file_name: Option<String>,
+
+    /// Only show files with these extensions, e.g. `("Images", ["png", "jpg"])`.
+    pub filters: Vec<(String, Vec<String>)>,
+
+    /// Allow picking more than one file (open dialogs only).
+    pub multiple: bool,
+}
+
+impl FileDialog {
+    /// A dialog with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The title of the dialog window.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// The directory to start in.
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// The suggested file name, for save dialogs.
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Only show files with one of these extensions (without the dot).
+    pub fn add_filter(mut self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.filters.push((
+            name.into(),
+            extensions.iter().map(|ext| (*ext).to_owned()).collect(),
+        ));
+        self
+    }
+
+    /// Allow picking more than one file (open dialogs only).
+    pub fn multiple(mut self, multiple: bool) -> Self {
+        self.multiple = multiple;
+        self
+    }
+}
+
+/// A desktop notification.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Notification {
+    /// A short summary, usually shown in bold.
+    pub summary: String,
+
+    /// More details.
+    pub body: String,
+}
+
+impl Notification {
+    /// A notification with the given summary and body.
+    pub fn new(summary: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            summary: summary.into(),
+            body: body.into(),
+        }
+    }
+}
+
+/// Something the app wants the desktop environment to do.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DesktopRequest {
+    /// Let the user pick one or more files to open.
+    OpenFile(FileDialog),
+
+    /// Let the user pick where to save a file.
+    SaveFile(FileDialog),
+
+    /// Show a desktop notification.
+    Notify(Notification),
+
+    /// Show the file or directory in the file manager (Finder, Explorer, …).
+    RevealInFileManager(PathBuf),
+}
+
+/// How a [`DesktopRequest`] went.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DesktopResponse {
+    /// The files the user picked in a file dialog. Empty if the dialog was cancelled.
+    Files(Vec<PathBuf>),
+
+    /// A notification was shown, or the file manager was opened.
+    Done,
+
+    /// The request failed, or is not supported by this integration.
+    Failed(String),
+}
+
+/// Used by a [`Desktop`] to deliver the response to a request.
+pub struct Responder {
+    id: RequestId,
+    frame: crate::Frame,
+}
+
+impl Responder {
+    /// The id of the request to respond to.
+    pub fn id(&self) -> RequestId {
+        self.id
+    }
+
+    /// Deliver the response, and wake up the app so that it can pick it up.
+    ///
+    /// Can be called from any thread.
+    pub fn respond(self, response: DesktopResponse) {
+        self.frame
+            .lock()
+            .desktop_responses
+            .insert(self.id, response);
+        self.frame.request_repaint();
+    }
+}
+
+/// Carries out [`DesktopRequest`]s. Implemented by the integration.
+pub trait Desktop: Send + Sync {
+    /// Start carrying out the request.
+    ///
+    /// This must not block: show dialogs etc. on another thread,
+    /// and call [`Responder::respond`] when done.
+    fn request(&self, request: DesktopRequest, responder: Responder);
+}
+
+/// A [`Desktop`] that just collects the requests, so that a test can respond to them.
+///
+/// ```
+/// use epi::desktop::*;
+/// let desktop = std::sync::Arc::new(MockDesktop::default());
+/// # struct NoRepaint;
+/// # impl epi::backend::RepaintSignal for NoRepaint { fn request_repaint(&self) {} }
+/// # let frame = epi::Frame::new(epi::backend::FrameData {
+/// #     info: epi::IntegrationInfo { name: "test", web_info: None, prefer_dark_mode: None, cpu_usage: None, native_pixels_per_point: None, window_info: None, monitors: vec![] },
+/// #     output: Default::default(),
+/// #     repaint_signal: std::sync::Arc::new(NoRepaint),
+/// #     windows: None,
+/// #     desktop: Some(desktop.clone()),
+/// #     desktop_responses: Default::default(),
+/// # });
+///
+/// // In the app:
+/// let id = frame.open_file_dialog(FileDialog::new().add_filter("Text", &["txt"]));
+/// assert_eq!(frame.take_desktop_response(id), None);
+///
+/// // In the test:
+/// for (request, responder) in desktop.take_requests() {
+///     assert!(matches!(request, DesktopRequest::OpenFile(_)));
+///     responder.respond(DesktopResponse::Files(vec!["notes.txt".into()]));
+/// }
+///
+/// // In a later frame of the app:
+/// assert_eq!(
+///     frame.take_desktop_response(id),
+///     Some(DesktopResponse::Files(vec!["notes.txt".into()]))
+/// );
+/// ```
+#[derive(Default)]
+pub struct MockDesktop {
+    requests: Mutex<Vec<(DesktopRequest, Responder)>>,
+}
+
+impl MockDesktop {
+    /// The requests that haven't been responded to yet, oldest first.
+    pub fn take_requests(&self) -> Vec<(DesktopRequest, Responder)> {
+        std::mem::take(&mut *self.requests.lock().unwrap())
+    }
+}
+
+impl Desktop for MockDesktop {
+    fn request(&self, request: DesktopRequest, responder: Responder) {
+        self.requests.lock().unwrap().push((request, responder));
+    }
+}
+
+/// Called by [`crate::Frame::desktop_request`].
+pub(crate) fn request(frame: &crate::Frame, request: DesktopRequest) -> RequestId {
+    let id = RequestId::next();
+    let desktop = frame.lock().desktop.clone();
+    let responder = Responder {
+        id,
+        frame: frame.clone(),
+    };
+    match desktop {
+        Some(desktop) => desktop.request(request, responder),
+        None => responder.respond(DesktopResponse::Failed(
+            "Not supported by this integration".to_owned(),
+        )),
+    }
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    struct NoRepaint;
+
+    impl crate::backend::RepaintSignal for NoRepaint {
+        fn request_repaint(&self) {}
+    }
+
+    fn frame(desktop: Option<Arc<dyn Desktop>>) -> crate::Frame {
+        crate::Frame::new(crate::backend::FrameData {
+            info: crate::IntegrationInfo {
+                name: "test",
+                web_info: None,
+                prefer_dark_mode: None,
+                cpu_usage: None,
+                native_pixels_per_point: None,
+                window_info: None,
+                monitors: vec![],
+            },
+            output: Default::default(),
+            repaint_signal: Arc::new(NoRepaint),
+            windows: None,
+            desktop,
+            desktop_responses: Default::default(),
+        })
+    }
+
+    #[test]
+    fn without_desktop() {
+        let frame = frame(None);
+        let id = frame.notify(Notification::new("Summary", "Body"));
+        assert!(matches!(
+            frame.take_desktop_response(id),
+            Some(DesktopResponse::Failed(_))
+        ));
+        assert_eq!(frame.take_desktop_response(id), None);
+    }
+
+    #[test]
+    fn respond_from_another_thread() {
+        let desktop = Arc::new(MockDesktop::default());
+        let frame = frame(Some(desktop.clone()));
+        let first = frame.save_file_dialog(FileDialog::new());
+        let second = frame.reveal_in_file_manager("notes.txt");
+        assert_ne!(first, second);
+
+        let requests = desktop.take_requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].1.id(), first);
+        assert_eq!(
+            requests[1].0,
+            DesktopRequest::RevealInFileManager("notes.txt".into())
+        );
+        assert!(desktop.take_requests().is_empty());
+
+        std::thread::spawn(move || {
+            for (_, responder) in requests.into_iter().rev() {
+                responder.respond(DesktopResponse::Done);
+            }
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(
+            frame.take_desktop_response(first),
+            Some(DesktopResponse::Done)
+        );
+        assert_eq!(
+            frame.take_desktop_response(second),
+            Some(DesktopResponse::Done)
+        );
+    }
+
+    #[test]
+    fn file_dialog_builder() {
+        let dialog = FileDialog::new()
+            .title("Open image")
+            .directory("/tmp")
+            .file_name("image.png")
+            .add_filter("Images", &["png", "jpg"])
+            .multiple(true);
+        assert_eq!(dialog.title.as_deref(), Some("Open image"));
+        assert_eq!(dialog.directory, Some("/tmp".into()));
+        assert_eq!(dialog.file_name.as_deref(), Some("image.png"));
+        assert_eq!(
+            dialog.filters,
+            vec![(
+                "Images".to_owned(),
+                vec!["png".to_owned(), "jpg".to_owned()]
+            )]
+        );
+        assert!(dialog.multiple);
+    }
+}
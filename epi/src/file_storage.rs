@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
 };
 
@@ -7,10 +7,14 @@ use std::{
 
 /// A key-value store backed by a [RON](https://github.com/ron-rs/ron) file on disk.
 /// Used to restore egui state, glium window position/size and app state.
+///
+/// The whole file is rewritten on [`crate::Storage::flush`], but never left half-written.
+/// See [`DirectoryStorage`] for storing each key in its own file.
 pub struct FileStorage {
     ron_filepath: PathBuf,
     kv: HashMap<String, String>,
     dirty: bool,
+    compress: bool,
 }
 
 impl FileStorage {
@@ -21,26 +25,22 @@ impl FileStorage {
             kv: read_ron(&ron_filepath).unwrap_or_default(),
             ron_filepath,
             dirty: false,
+            compress: false,
         }
     }
 
     /// Find a good place to put the files that the OS likes.
     pub fn from_app_name(app_name: &str) -> Option<Self> {
-        if let Some(proj_dirs) = directories_next::ProjectDirs::from("", "", app_name) {
-            let data_dir = proj_dirs.data_dir().to_path_buf();
-            if let Err(err) = std::fs::create_dir_all(&data_dir) {
-                eprintln!(
-                    "Saving disabled: Failed to create app path at {:?}: {}",
-                    data_dir, err
-                );
-                None
-            } else {
-                Some(Self::from_ron_filepath(data_dir.join("app.ron")))
-            }
-        } else {
-            eprintln!("Saving disabled: Failed to find path to data_dir.");
-            None
-        }
+        data_dir(app_name).map(|data_dir| Self::from_ron_filepath(data_dir.join("app.ron")))
+    }
+
+    /// Gzip the file when writing it.
+    ///
+    /// Compressed and uncompressed files can both be read regardless of this setting.
+    #[cfg(feature = "compression")]
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
     }
 }
 
@@ -58,34 +58,248 @@ impl crate::Storage for FileStorage {
 
     fn flush(&mut self) {
         if self.dirty {
-            // eprintln!("Persisted to {}", self.path.display());
-            let file = std::fs::File::create(&self.ron_filepath).unwrap();
             let config = Default::default();
-            ron::ser::to_writer_pretty(file, &self.kv, config).unwrap();
-            self.dirty = false;
+            let result = ron::ser::to_string_pretty(&self.kv, config)
+                .map_err(|err| err.into())
+                .and_then(|ron| write_atomically(&self.ron_filepath, &ron, self.compress));
+            match result {
+                Ok(()) => self.dirty = false,
+                Err(err) => eprintln!("Failed to save to {:?}: {}", self.ron_filepath, err),
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A key-value store with one [RON](https://github.com/ron-rs/ron) file per key, in one directory.
+///
+/// Only the keys that changed are written on [`crate::Storage::flush`],
+/// so saving stays fast when some of the values are large.
+pub struct DirectoryStorage {
+    directory: PathBuf,
+    kv: HashMap<String, String>,
+    dirty: HashSet<String>,
+    compress: bool,
+}
+
+impl DirectoryStorage {
+    /// Store the state in this directory, which is created if needed.
+    ///
+    /// # Errors
+    /// When failing to create or read the directory.
+    pub fn from_directory(directory: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let directory: PathBuf = directory.into();
+        std::fs::create_dir_all(&directory)?;
+
+        let mut kv = HashMap::new();
+        for entry in std::fs::read_dir(&directory)? {
+            let path = entry?.path();
+            if path
+                .extension()
+                .map_or(true, |extension| extension != "ron")
+            {
+                continue; // e.g. a temporary file from a failed write
+            }
+            let key = path
+                .file_stem()
+                .and_then(|file_stem| file_stem.to_str())
+                .and_then(decode_key);
+            match (key, read_string(&path)) {
+                (Some(key), Ok(value)) => {
+                    kv.insert(key, value);
+                }
+                (_, Err(err)) => eprintln!("Failed to read {:?}: {}", path, err),
+                (None, _) => {}
+            }
+        }
+
+        Ok(Self {
+            directory,
+            kv,
+            dirty: Default::default(),
+            compress: false,
+        })
+    }
+
+    /// Find a good place to put the files that the OS likes.
+    pub fn from_app_name(app_name: &str) -> Option<Self> {
+        let directory = data_dir(app_name)?.join("app");
+        match Self::from_directory(&directory) {
+            Ok(storage) => Some(storage),
+            Err(err) => {
+                eprintln!("Saving disabled: Failed to read {:?}: {}", directory, err);
+                None
+            }
+        }
+    }
+
+    /// Gzip the files when writing them.
+    ///
+    /// Compressed and uncompressed files can both be read regardless of this setting.
+    #[cfg(feature = "compression")]
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.directory.join(format!("{}.ron", encode_key(key)))
+    }
+}
+
+impl crate::Storage for DirectoryStorage {
+    fn get_string(&self, key: &str) -> Option<String> {
+        self.kv.get(key).cloned()
+    }
+
+    fn set_string(&mut self, key: &str, value: String) {
+        if self.kv.get(key) != Some(&value) {
+            self.kv.insert(key.to_owned(), value);
+            self.dirty.insert(key.to_owned());
+        }
+    }
+
+    fn flush(&mut self) {
+        let dirty = std::mem::take(&mut self.dirty);
+        for key in dirty {
+            let path = self.path(&key);
+            if let Err(err) = write_atomically(&path, &self.kv[&key], self.compress) {
+                eprintln!("Failed to save to {:?}: {}", path, err);
+                self.dirty.insert(key); // try again next time
+            }
+        }
+    }
+}
+
+/// Keys can contain any character, so escape everything that might not be allowed in a file name.
+///
+/// Upper case letters are escaped too, so that keys that only differ in case
+/// (e.g. `"App"` and `"app"`) don't end up in the same file on case-insensitive file systems.
+fn encode_key(key: &str) -> String {
+    let mut encoded = String::new();
+    for byte in key.bytes() {
+        if byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'_' || byte == b'-' {
+            encoded.push(byte as char);
+        } else {
+            encoded += &format!("%{:02X}", byte);
+        }
+    }
+    encoded
+}
+
+fn decode_key(encoded: &str) -> Option<String> {
+    let mut bytes = vec![];
+    let mut rest = encoded.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
         }
     }
+    String::from_utf8(bytes).ok()
 }
 
 // ----------------------------------------------------------------------------
 
+fn data_dir(app_name: &str) -> Option<PathBuf> {
+    if let Some(proj_dirs) = directories_next::ProjectDirs::from("", "", app_name) {
+        let data_dir = proj_dirs.data_dir().to_path_buf();
+        if let Err(err) = std::fs::create_dir_all(&data_dir) {
+            eprintln!(
+                "Saving disabled: Failed to create app path at {:?}: {}",
+                data_dir, err
+            );
+            None
+        } else {
+            Some(data_dir)
+        }
+    } else {
+        eprintln!("Saving disabled: Failed to find path to data_dir.");
+        None
+    }
+}
+
+/// Write to a temporary file first, and then replace the old file with it,
+/// so that a crash while writing can't leave a half-written file behind.
+fn write_atomically(
+    path: &Path,
+    contents: &str,
+    compress: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let bytes = if compress {
+        compress_bytes(contents.as_bytes())?
+    } else {
+        contents.as_bytes().to_vec()
+    };
+    {
+        use std::io::Write as _;
+        let mut file = std::fs::File::create(&tmp_path)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?; // Make sure the contents are on disk before the rename is.
+    }
+    std::fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+/// The first bytes of a gzip file.
+#[cfg(feature = "compression")]
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+#[cfg(feature = "compression")]
+fn compress_bytes(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    use std::io::Write as _;
+    let mut encoder = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish()
+}
+
+#[cfg(not(feature = "compression"))]
+fn compress_bytes(bytes: &[u8]) -> std::io::Result<Vec<u8>> {
+    Ok(bytes.to_vec()) // `compress` can't be set without the feature
+}
+
+/// Read a file that may be compressed.
+fn read_string(path: &Path) -> Result<String, Box<dyn std::error::Error>> {
+    let bytes = std::fs::read(path)?;
+
+    #[cfg(feature = "compression")]
+    if bytes.starts_with(&GZIP_MAGIC) {
+        use std::io::Read as _;
+        let mut string = String::new();
+        flate2::read::GzDecoder::new(&bytes[..]).read_to_string(&mut string)?;
+        return Ok(string);
+    }
+
+    Ok(String::from_utf8(bytes)?)
+}
+
 fn read_ron<T>(ron_path: impl AsRef<Path>) -> Option<T>
 where
     T: serde::de::DeserializeOwned,
 {
-    match std::fs::File::open(ron_path) {
-        Ok(file) => {
-            let reader = std::io::BufReader::new(file);
-            match ron::de::from_reader(reader) {
-                Ok(value) => Some(value),
-                Err(err) => {
-                    eprintln!("ERROR: Failed to parse RON: {}", err);
-                    None
-                }
+    let ron_path = ron_path.as_ref();
+    if !ron_path.exists() {
+        return None; // That's fine
+    }
+    match read_string(ron_path) {
+        Ok(ron) => match ron::de::from_str(&ron) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                eprintln!("ERROR: Failed to parse RON: {}", err);
+                None
             }
-        }
-        Err(_err) => {
-            // File probably doesn't exist. That's fine.
+        },
+        Err(err) => {
+            eprintln!("ERROR: Failed to read {:?}: {}", ron_path, err);
             None
         }
     }
@@ -114,3 +328,121 @@ pub fn write_memory(
     ron::ser::to_writer_pretty(file, &*ctx.memory(), ron_config)?;
     Ok(())
 }
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Storage as _;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("epi-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn file_storage_round_trip() {
+        let dir = temp_dir("file_storage");
+        let path = dir.join("app.ron");
+
+        let mut storage = FileStorage::from_ron_filepath(&path);
+        storage.set_string("key", "value".to_owned());
+        storage.flush();
+        assert!(!dir.join("app.ron.tmp").exists());
+
+        let storage = FileStorage::from_ron_filepath(&path);
+        assert_eq!(storage.get_string("key").as_deref(), Some("value"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn directory_storage_round_trip() {
+        let dir = temp_dir("directory_storage");
+
+        let mut storage = DirectoryStorage::from_directory(&dir).unwrap();
+        storage.set_string("app", "1".to_owned());
+        storage.set_string("window/position: ü", "2".to_owned());
+        storage.flush();
+        storage.set_string("App", "3".to_owned());
+        storage.flush();
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 3);
+
+        let storage = DirectoryStorage::from_directory(&dir).unwrap();
+        assert_eq!(storage.get_string("app").as_deref(), Some("1"));
+        assert_eq!(storage.get_string("App").as_deref(), Some("3"));
+        assert_eq!(
+            storage.get_string("window/position: ü").as_deref(),
+            Some("2")
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn versioned_value_migration() {
+        #[derive(serde::Deserialize, serde::Serialize)]
+        struct OldSettings {
+            volume_percent: u32,
+        }
+
+        #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+        struct Settings {
+            volume: f32,
+        }
+
+        let dir = temp_dir("versioned_value");
+        let mut storage = FileStorage::from_ron_filepath(dir.join("app.ron"));
+        crate::set_value(
+            &mut storage,
+            "settings",
+            &OldSettings { volume_percent: 50 },
+        );
+
+        let migrate = |version: u32, ron: &str| {
+            assert_eq!(version, 0);
+            let old: OldSettings = ron::from_str(ron).ok()?;
+            Some(Settings {
+                volume: old.volume_percent as f32 / 100.0,
+            })
+        };
+        let settings: Settings =
+            crate::get_versioned_value(&storage, "settings", 1, migrate).unwrap();
+        assert_eq!(settings, Settings { volume: 0.5 });
+
+        crate::set_versioned_value(&mut storage, "settings", 1, &settings);
+        let reloaded: Option<Settings> =
+            crate::get_versioned_value(&storage, "settings", 1, |_, _| unreachable!());
+        assert_eq!(reloaded, Some(settings));
+        assert!(
+            crate::get_versioned_value(&storage, "settings", 0, |_, _| None::<Settings>).is_none()
+        );
+        assert_eq!(storage.kv.len(), 1, "the version is stored with the value");
+        assert_eq!(
+            crate::get_value::<Settings>(&storage, "settings"),
+            Some(Settings { volume: 0.5 })
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn compressed_file_storage() {
+        let dir = temp_dir("compressed_storage");
+        let path = dir.join("app.ron");
+
+        let mut storage = FileStorage::from_ron_filepath(&path).with_compression(true);
+        storage.set_string("key", "value".repeat(100));
+        storage.flush();
+        assert!(std::fs::read(&path).unwrap().starts_with(&GZIP_MAGIC));
+
+        let storage = FileStorage::from_ron_filepath(&path);
+        assert_eq!(storage.get_string("key"), Some("value".repeat(100)));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
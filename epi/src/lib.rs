@@ -89,9 +89,12 @@
 #![allow(clippy::manual_range_contains)]
 #![warn(missing_docs)] // Let's keep `epi` well-documented.
 
+pub mod desktop;
 /// File storage which can be used by native backends.
 #[cfg(feature = "file_storage")]
 pub mod file_storage;
+#[cfg(feature = "recording")]
+pub mod recording;
 
 pub use egui; // Re-export for user convenience
 
@@ -187,9 +190,9 @@ pub trait App {
     }
 }
 
-/// Options controlling the behavior of a native window.
+/// Options controlling the behavior of the main native window.
 ///
-/// Only a single native window is currently supported.
+/// Use [`Frame::show_window`] to open more native windows.
 #[derive(Clone)]
 pub struct NativeOptions {
     /// Sets whether or not the window will always be on top of other windows.
@@ -198,6 +201,9 @@ pub struct NativeOptions {
     /// Show window in maximized mode
     pub maximized: bool,
 
+    /// Start in (borderless) fullscreen on the current monitor.
+    pub fullscreen: bool,
+
     /// On desktop: add window decorations (i.e. a frame around your app)?
     /// If false it will be difficult to move and resize the app.
     pub decorated: bool,
@@ -214,6 +220,16 @@ pub struct NativeOptions {
     /// The initial size of the native window in points (logical pixels).
     pub initial_window_size: Option<egui::Vec2>,
 
+    /// The initial position of the native window in points (logical pixels),
+    /// relative to the top left corner of the desktop.
+    pub initial_window_pos: Option<egui::Pos2>,
+
+    /// The window can't be resized to be smaller than this (in points).
+    pub min_window_size: Option<egui::Vec2>,
+
+    /// The window can't be resized to be larger than this (in points).
+    pub max_window_size: Option<egui::Vec2>,
+
     /// Should the app window be resizable?
     pub resizable: bool,
 
@@ -221,6 +237,31 @@ pub struct NativeOptions {
     /// You control the transparency with [`App::clear_color()`].
     /// You should avoid having a [`egui::CentralPanel`], or make sure its frame is also transparent.
     pub transparent: bool,
+
+    /// Record the input of every frame to a file, so that the session can be replayed with [`Self::replay_session`].
+    ///
+    /// Requires the `recording` feature of `eframe`.
+    #[cfg(feature = "recording")]
+    pub record_session: Option<recording::RecordOptions>,
+
+    /// Instead of the user input, give the app the input of a session recorded with [`Self::record_session`].
+    ///
+    /// The replay starts paused. Press F9 to play or pause, and F10 to step one frame at a time.
+    /// Nothing is saved while replaying.
+    ///
+    /// Requires the `recording` feature of `eframe`.
+    #[cfg(feature = "recording")]
+    pub replay_session: Option<std::path::PathBuf>,
+
+    /// Load an [`egui::Theme`] from this file, and re-apply it whenever the file changes.
+    ///
+    /// This overrides the style the app sets in [`App::setup`].
+    /// Use it to tweak a theme in a text editor and see the result right away,
+    /// or save one with the "Save as theme" button of [`egui::Context::style_ui`].
+    ///
+    /// Requires the `theme_files` feature of `eframe`.
+    #[cfg(feature = "theme_files")]
+    pub theme_file: Option<std::path::PathBuf>,
 }
 
 impl Default for NativeOptions {
@@ -228,12 +269,65 @@ impl Default for NativeOptions {
         Self {
             always_on_top: false,
             maximized: false,
+            fullscreen: false,
             decorated: true,
             drag_and_drop_support: false,
             icon_data: None,
             initial_window_size: None,
+            initial_window_pos: None,
+            min_window_size: None,
+            max_window_size: None,
             resizable: true,
             transparent: false,
+            #[cfg(feature = "recording")]
+            record_session: None,
+            #[cfg(feature = "recording")]
+            replay_session: None,
+            #[cfg(feature = "theme_files")]
+            theme_file: None,
+        }
+    }
+}
+
+/// Options for an extra native window, shown with [`Frame::show_window`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowOptions {
+    /// The title of the window.
+    pub title: String,
+
+    /// The initial size of the window in points (logical pixels).
+    /// If `None`, [`Self::DEFAULT_SIZE`].
+    pub initial_size: Option<egui::Vec2>,
+
+    /// Should the window be resizable?
+    pub resizable: bool,
+
+    /// Add window decorations (i.e. a frame around the window)?
+    pub decorated: bool,
+
+    /// Keep the window on top of other windows.
+    pub always_on_top: bool,
+
+    /// While a modal window is open the main window gets no input, like for a native dialog.
+    ///
+    /// This is not modal to the OS: the main window can still be moved, resized and focused.
+    /// When it is focused, the modal window is brought back to the front.
+    pub modal: bool,
+}
+
+impl WindowOptions {
+    /// The size of the window if [`Self::initial_size`] is not set, in points.
+    pub const DEFAULT_SIZE: egui::Vec2 = egui::Vec2::new(480.0, 360.0);
+
+    /// A resizable, decorated window with the given title.
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            initial_size: None,
+            resizable: true,
+            decorated: true,
+            always_on_top: false,
+            modal: false,
         }
     }
 }
@@ -306,6 +400,49 @@ impl Frame {
         self.lock().output.decorated = Some(decorated);
     }
 
+    /// Go to (borderless) fullscreen on the current monitor, or back to a normal window.
+    ///
+    /// Does not work on the web.
+    pub fn set_fullscreen(&self, fullscreen: bool) {
+        self.lock().output.fullscreen = Some(fullscreen);
+    }
+
+    /// Maximize or restore the window.
+    ///
+    /// Does not work on the web.
+    pub fn set_maximized(&self, maximized: bool) {
+        self.lock().output.maximized = Some(maximized);
+    }
+
+    /// Minimize (iconify) or restore the window.
+    ///
+    /// Does not work on the web.
+    pub fn set_minimized(&self, minimized: bool) {
+        self.lock().output.minimized = Some(minimized);
+    }
+
+    /// Move the window so that its top left corner is at this position (in points),
+    /// relative to the top left corner of the desktop. See [`IntegrationInfo::monitors`].
+    ///
+    /// Does not work on the web.
+    pub fn set_window_pos(&self, pos: egui::Pos2) {
+        self.lock().output.window_pos = Some(pos);
+    }
+
+    /// Set the smallest size the user can resize the window to (in points), or `None` for no limit.
+    ///
+    /// Does not work on the web.
+    pub fn set_min_window_size(&self, size: Option<egui::Vec2>) {
+        self.lock().output.min_window_size = Some(size.into());
+    }
+
+    /// Set the largest size the user can resize the window to (in points), or `None` for no limit.
+    ///
+    /// Does not work on the web.
+    pub fn set_max_window_size(&self, size: Option<egui::Vec2>) {
+        self.lock().output.max_window_size = Some(size.into());
+    }
+
     /// When called, the native window will follow the
     /// movement of the cursor while the primary mouse button is down.
     ///
@@ -321,6 +458,114 @@ impl Frame {
         self.lock().repaint_signal.request_repaint();
     }
 
+    /// Ask the desktop environment to show a file dialog, a notification etc, without blocking.
+    ///
+    /// Pick up the response with [`Self::take_desktop_response`] in a later frame.
+    /// See [`desktop`] for more.
+    pub fn desktop_request(&self, request: desktop::DesktopRequest) -> desktop::RequestId {
+        desktop::request(self, request)
+    }
+
+    /// Let the user pick one or more files to open.
+    /// The response is a [`desktop::DesktopResponse::Files`].
+    pub fn open_file_dialog(&self, dialog: desktop::FileDialog) -> desktop::RequestId {
+        self.desktop_request(desktop::DesktopRequest::OpenFile(dialog))
+    }
+
+    /// Let the user pick where to save a file.
+    /// The response is a [`desktop::DesktopResponse::Files`].
+    pub fn save_file_dialog(&self, dialog: desktop::FileDialog) -> desktop::RequestId {
+        self.desktop_request(desktop::DesktopRequest::SaveFile(dialog))
+    }
+
+    /// Show a desktop notification.
+    pub fn notify(&self, notification: desktop::Notification) -> desktop::RequestId {
+        self.desktop_request(desktop::DesktopRequest::Notify(notification))
+    }
+
+    /// Show the file or directory in the file manager (Finder, Explorer, …).
+    pub fn reveal_in_file_manager(
+        &self,
+        path: impl Into<std::path::PathBuf>,
+    ) -> desktop::RequestId {
+        self.desktop_request(desktop::DesktopRequest::RevealInFileManager(path.into()))
+    }
+
+    /// The response to a [`Self::desktop_request`], once it has arrived.
+    ///
+    /// Returns `None` while waiting for the response, and after the response has been taken.
+    pub fn take_desktop_response(
+        &self,
+        id: desktop::RequestId,
+    ) -> Option<desktop::DesktopResponse> {
+        self.lock().desktop_responses.remove(&id)
+    }
+
+    /// Show an extra native window, e.g. a detachable inspector or a preview on a second monitor.
+    ///
+    /// Call this from [`App::update`] every frame the window should be open, and stop calling it to close the window.
+    /// `id` identifies the window from one frame to the next.
+    ///
+    /// The window has its own [`egui::CtxRef`] (with its own input), which is passed to `add_contents`.
+    /// It uses the same fonts and style as the main window, and the same user textures.
+    ///
+    /// Returns `false` if the user has asked to close the window (e.g. by clicking the close button),
+    /// or if the integration doesn't support extra native windows,
+    /// in which case `add_contents` is not called.
+    /// Only `egui_glow` supports them; `egui_glium` and `egui_web` don't.
+    pub fn show_window(
+        &self,
+        id: egui::Id,
+        options: &WindowOptions,
+        add_contents: impl FnOnce(&egui::CtxRef),
+    ) -> bool {
+        let (mut egui_ctx, raw_input) = {
+            let mut lock = self.lock();
+            let pixels_per_point = lock.info.native_pixels_per_point;
+            let windows = match &mut lock.windows {
+                Some(windows) => windows,
+                None => return false,
+            };
+            let window = windows
+                .entry(id)
+                .or_insert_with(|| backend::NativeWindow::new(options.clone(), pixels_per_point));
+            if window.close_requested {
+                return false;
+            }
+            window.options = options.clone();
+            window.shown = true;
+            (
+                window.egui_ctx.clone(),
+                std::mem::take(&mut window.raw_input),
+            )
+        };
+
+        egui_ctx.begin_frame(raw_input);
+        add_contents(&egui_ctx);
+        let output = egui_ctx.end_frame();
+
+        if let Some(window) = self
+            .lock()
+            .windows
+            .as_mut()
+            .and_then(|windows| windows.get_mut(&id))
+        {
+            window.egui_ctx = egui_ctx;
+            window.output = Some(output);
+        }
+        true
+    }
+
+    /// Is the extra native window with the given id open?
+    ///
+    /// See [`Self::show_window`].
+    pub fn is_window_open(&self, id: egui::Id) -> bool {
+        self.lock()
+            .windows
+            .as_ref()
+            .map_or(false, |windows| windows.contains_key(&id))
+    }
+
     /// for integrations only: call once per frame
     pub fn take_app_output(&self) -> crate::backend::AppOutput {
         let mut lock = self.lock();
@@ -379,6 +624,52 @@ pub struct IntegrationInfo {
 
     /// The OS native pixels-per-point
     pub native_pixels_per_point: Option<f32>,
+
+    /// The position, size and state of the native window.
+    /// `None` on the web.
+    pub window_info: Option<WindowInfo>,
+
+    /// The monitors connected to the computer. Empty on the web.
+    pub monitors: Vec<MonitorInfo>,
+}
+
+/// Information about the native window, see [`IntegrationInfo::window_info`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindowInfo {
+    /// The position of the top left corner of the window (including decorations)
+    /// in points, relative to the top left corner of the desktop.
+    /// `None` if the platform doesn't know (e.g. on Wayland).
+    pub position: Option<egui::Pos2>,
+
+    /// The size of the inside of the window, in points.
+    pub size: egui::Vec2,
+
+    /// Is the window fullscreen?
+    pub fullscreen: bool,
+
+    /// Is the window maximized?
+    pub maximized: bool,
+}
+
+/// A monitor (display), see [`IntegrationInfo::monitors`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct MonitorInfo {
+    /// A human-readable name of the monitor, if known.
+    pub name: Option<String>,
+
+    /// The top left corner of the monitor, in points relative to the top left corner of the desktop,
+    /// like [`WindowInfo::position`] and [`Frame::set_window_pos`].
+    pub position: egui::Pos2,
+
+    /// The size of the monitor, in points.
+    /// Multiply by [`Self::scale_factor`] to get the resolution in physical pixels.
+    pub size: egui::Vec2,
+
+    /// The number of physical pixels per point on this monitor.
+    pub scale_factor: f32,
+
+    /// Is the native window (mostly) on this monitor?
+    pub is_current: bool,
 }
 
 /// How to allocate textures (images) to use in [`egui`].
@@ -470,6 +761,94 @@ pub fn set_value<T: serde::Serialize>(storage: &mut dyn Storage, key: &str, valu
     storage.set_string(key, ron::ser::to_string(value).unwrap());
 }
 
+/// Get a value stored with [`set_versioned_value`], migrating it if it was stored by an older version of the app.
+///
+/// If the stored value has an older `version`, `migrate` is called with that version and the stored
+/// [RON](https://github.com/ron-rs/ron), and should return the value converted to `T` (or `None` to give up).
+/// Values stored with [`set_value`] have version `0`.
+///
+/// Unlike [`get_value`], failures are logged to stderr,
+/// so that changing the type of the value without adding a migration doesn't go unnoticed.
+///
+/// ```
+/// # #[derive(serde::Deserialize)] struct Settings { volume: f32 }
+/// # #[derive(serde::Deserialize)] struct OldSettings { volume_percent: u32 }
+/// # fn load(storage: &dyn epi::Storage) -> Option<Settings> {
+/// epi::get_versioned_value(storage, epi::APP_KEY, 2, |version, ron| match version {
+///     0 | 1 => {
+///         let old: OldSettings = ron::from_str(ron).ok()?;
+///         Some(Settings { volume: old.volume_percent as f32 / 100.0 })
+///     }
+///     _ => None,
+/// })
+/// # }
+/// ```
+#[cfg(feature = "ron")]
+pub fn get_versioned_value<T: serde::de::DeserializeOwned>(
+    storage: &dyn Storage,
+    key: &str,
+    version: u32,
+    migrate: impl FnOnce(u32, &str) -> Option<T>,
+) -> Option<T> {
+    let stored = storage.get_string(key)?;
+    let (stored_version, ron) = split_version(&stored);
+
+    match stored_version.cmp(&version) {
+        std::cmp::Ordering::Equal => match ron::from_str(ron) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                eprintln!("Failed to read {:?} (version {}): {}", key, version, err);
+                None
+            }
+        },
+        std::cmp::Ordering::Less => {
+            let value = migrate(stored_version, ron);
+            if value.is_none() {
+                eprintln!(
+                    "Failed to migrate {:?} from version {} to {}",
+                    key, stored_version, version
+                );
+            }
+            value
+        }
+        std::cmp::Ordering::Greater => {
+            eprintln!(
+                "{:?} was stored with version {}, which is newer than {}",
+                key, stored_version, version
+            );
+            None
+        }
+    }
+}
+
+/// Like [`set_value`], but also stores the version of the type, for [`get_versioned_value`].
+///
+/// The version is stored in the same string as the value, as a RON comment on the first line,
+/// so the two can't get out of sync, and [`get_value`] can still read the value.
+#[cfg(feature = "ron")]
+pub fn set_versioned_value<T: serde::Serialize>(
+    storage: &mut dyn Storage,
+    key: &str,
+    version: u32,
+    value: &T,
+) {
+    let ron = ron::ser::to_string(value).unwrap();
+    storage.set_string(key, format!("{}{}\n{}", VERSION_PREFIX, version, ron));
+}
+
+#[cfg(feature = "ron")]
+const VERSION_PREFIX: &str = "// version: ";
+
+/// The version and the RON of a value stored with [`set_versioned_value`] or [`set_value`] (version `0`).
+#[cfg(feature = "ron")]
+fn split_version(stored: &str) -> (u32, &str) {
+    stored
+        .strip_prefix(VERSION_PREFIX)
+        .and_then(|rest| rest.split_once('\n'))
+        .and_then(|(version, ron)| Some((version.parse().ok()?, ron)))
+        .unwrap_or((0, stored))
+}
+
 /// [`Storage`] key used for app
 pub const APP_KEY: &str = "app";
 
@@ -497,6 +876,65 @@ pub mod backend {
         pub output: AppOutput,
         /// If you need to request a repaint from another thread, clone this and send it to that other thread.
         pub repaint_signal: std::sync::Arc<dyn RepaintSignal>,
+        /// The extra native windows opened with [`Frame::show_window`].
+        /// `None` if the integration doesn't support them.
+        pub windows: Option<HashMap<egui::Id, NativeWindow>>,
+        /// Carries out [`Frame::desktop_request`]. `None` if not supported.
+        pub desktop: Option<std::sync::Arc<dyn crate::desktop::Desktop>>,
+        /// Responses to [`Frame::desktop_request`] that the app hasn't taken yet.
+        pub desktop_responses: HashMap<crate::desktop::RequestId, crate::desktop::DesktopResponse>,
+    }
+
+    /// An extra native window, see [`Frame::show_window`].
+    pub struct NativeWindow {
+        /// As given to [`Frame::show_window`] the last time.
+        pub options: WindowOptions,
+
+        /// The egui context of the window. Separate from the one of the main window.
+        pub egui_ctx: egui::CtxRef,
+
+        /// The input for the next frame, set by the integration.
+        pub raw_input: egui::RawInput,
+
+        /// Set by the integration when the user tries to close the window.
+        pub close_requested: bool,
+
+        /// Was [`Frame::show_window`] called for the window this frame?
+        /// The integration should reset this at the start of each frame,
+        /// and close the window if it is still `false` at the end of the frame.
+        pub shown: bool,
+
+        /// What to paint, from the last call to [`Frame::show_window`].
+        pub output: Option<(egui::Output, Vec<egui::epaint::ClippedShape>)>,
+    }
+
+    impl NativeWindow {
+        pub(crate) fn new(options: WindowOptions, pixels_per_point: Option<f32>) -> Self {
+            Self {
+                raw_input: Self::initial_input(&options, pixels_per_point),
+                options,
+                egui_ctx: Default::default(),
+                close_requested: false,
+                shown: false,
+                output: None,
+            }
+        }
+
+        /// The input to use until the integration has created the native window:
+        /// just the initial size of the window.
+        ///
+        /// `pixels_per_point` is a guess, e.g. the scale of the main window.
+        pub fn initial_input(
+            options: &WindowOptions,
+            pixels_per_point: Option<f32>,
+        ) -> egui::RawInput {
+            let size = options.initial_size.unwrap_or(WindowOptions::DEFAULT_SIZE);
+            egui::RawInput {
+                screen_rect: Some(egui::Rect::from_min_size(Default::default(), size)),
+                pixels_per_point,
+                ..Default::default()
+            }
+        }
     }
 
     /// The data needed in order to allocate and free textures/images.
@@ -536,6 +974,32 @@ pub mod backend {
         }
     }
 
+    /// A new smallest or largest size of the window, see [`AppOutput::min_window_size`].
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum WindowSizeLimit {
+        /// The window can be resized freely.
+        Unlimited,
+
+        /// The limit, in points.
+        Size(egui::Vec2),
+    }
+
+    impl WindowSizeLimit {
+        /// The limit, if any.
+        pub fn size(self) -> Option<egui::Vec2> {
+            match self {
+                Self::Unlimited => None,
+                Self::Size(size) => Some(size),
+            }
+        }
+    }
+
+    impl From<Option<egui::Vec2>> for WindowSizeLimit {
+        fn from(size: Option<egui::Vec2>) -> Self {
+            size.map_or(Self::Unlimited, Self::Size)
+        }
+    }
+
     /// Action that can be taken by the user app.
     #[derive(Default)]
     #[must_use]
@@ -553,6 +1017,24 @@ pub mod backend {
         /// Set to some bool to change window decorations.
         pub decorated: Option<bool>,
 
+        /// Set to some bool to go to or leave fullscreen.
+        pub fullscreen: Option<bool>,
+
+        /// Set to some bool to maximize or restore the window.
+        pub maximized: Option<bool>,
+
+        /// Set to some bool to minimize or restore the window.
+        pub minimized: Option<bool>,
+
+        /// Set to some position (in points) to move the window.
+        pub window_pos: Option<egui::Pos2>,
+
+        /// Set to change the smallest size of the window.
+        pub min_window_size: Option<WindowSizeLimit>,
+
+        /// Set to change the largest size of the window.
+        pub max_window_size: Option<WindowSizeLimit>,
+
         /// Set to true to drag window while primary mouse button is down.
         pub drag_window: bool,
 
@@ -560,3 +1042,101 @@ pub mod backend {
         pub tex_allocation_data: TexAllocationData,
     }
 }
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoRepaint;
+
+    impl backend::RepaintSignal for NoRepaint {
+        fn request_repaint(&self) {}
+    }
+
+    fn frame(supports_extra_windows: bool) -> Frame {
+        Frame::new(backend::FrameData {
+            info: IntegrationInfo {
+                name: "test",
+                web_info: None,
+                prefer_dark_mode: None,
+                cpu_usage: None,
+                native_pixels_per_point: Some(2.0),
+                window_info: None,
+                monitors: vec![],
+            },
+            output: Default::default(),
+            repaint_signal: Arc::new(NoRepaint),
+            windows: supports_extra_windows.then(Default::default),
+            desktop: None,
+            desktop_responses: Default::default(),
+        })
+    }
+
+    #[test]
+    fn show_window_without_support() {
+        let frame = frame(false);
+        let id = egui::Id::new("window");
+        let shown = frame.show_window(id, &WindowOptions::new("Window"), |_| {
+            panic!("The window should not be shown");
+        });
+        assert!(!shown);
+        assert!(!frame.is_window_open(id));
+    }
+
+    #[test]
+    fn first_frame_of_a_window() {
+        let frame = frame(true);
+        let id = egui::Id::new("window");
+        let mut options = WindowOptions::new("Window");
+        options.initial_size = Some(egui::vec2(300.0, 200.0));
+
+        let shown = frame.show_window(id, &options, |ctx| {
+            // The size of the window, not of the main window or of an empty `RawInput`:
+            assert_eq!(ctx.input().screen_rect().size(), egui::vec2(300.0, 200.0));
+            assert_eq!(ctx.pixels_per_point(), 2.0);
+        });
+        assert!(shown);
+        assert!(frame.is_window_open(id));
+
+        let lock = frame.lock();
+        let window = &lock.windows.as_ref().unwrap()[&id];
+        assert!(window.shown);
+        assert!(window.output.is_some());
+    }
+
+    #[test]
+    fn initial_input_without_size() {
+        let options = WindowOptions::new("Window");
+        let raw_input = backend::NativeWindow::initial_input(&options, None);
+        assert_eq!(
+            raw_input.screen_rect.unwrap().size(),
+            WindowOptions::DEFAULT_SIZE
+        );
+        assert_eq!(raw_input.pixels_per_point, None);
+    }
+
+    #[test]
+    fn closed_window_is_not_shown() {
+        let frame = frame(true);
+        let id = egui::Id::new("window");
+        let options = WindowOptions::new("Window");
+        assert!(frame.show_window(id, &options, |_| {}));
+
+        // The integration got a close event:
+        frame
+            .lock()
+            .windows
+            .as_mut()
+            .unwrap()
+            .get_mut(&id)
+            .unwrap()
+            .close_requested = true;
+
+        let shown = frame.show_window(id, &options, |_| {
+            panic!("The window should not be shown");
+        });
+        assert!(!shown);
+    }
+}
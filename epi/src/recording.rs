@@ -0,0 +1,389 @@
+//! Record the input of a session to a file, and replay it later to reproduce a bug.
+//!
+//! Enable the `recording` feature and set [`crate::NativeOptions::record_session`]
+//! to record every frame, and [`crate::NativeOptions::replay_session`] to replay it.
+//!
+//! A replay is only deterministic if the app only depends on the [`egui::RawInput`]
+//! (including [`egui::RawInput::time`]) and on the state it had when the recording started.
+//! Clocks, randomness, background threads, the file system and
+//! [`crate::IntegrationInfo::cpu_usage`] are not replayed.
+//!
+//! A recording file has the [`egui::Memory`] on its first line,
+//! followed by one [`RecordedFrame`] per line, all in [RON](https://github.com/ron-rs/ron).
+//! This lets a [`Recorder`] append each frame as it happens.
+
+use std::io::{BufRead, Write};
+use std::path::Path;
+
+/// One frame of a [`Recording`].
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct RecordedFrame {
+    /// The input given to egui this frame.
+    pub raw_input: egui::RawInput,
+
+    /// What egui output this frame, if [`RecordOptions::include_output`] was set.
+    pub output: Option<egui::Output>,
+}
+
+/// Borrowed version of [`RecordedFrame`], so that recording doesn't need to clone the frame.
+#[derive(serde::Serialize)]
+#[serde(rename = "RecordedFrame")]
+struct RecordedFrameRef<'a> {
+    raw_input: &'a egui::RawInput,
+    output: Option<&'a egui::Output>,
+}
+
+/// The input (and optionally output) of every frame of a session.
+#[derive(Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct Recording {
+    /// The egui memory at the start of the recording (window positions etc).
+    pub memory: egui::Memory,
+
+    /// Every frame of the session, in order.
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl Recording {
+    /// Read a recording written by [`Self::save`].
+    ///
+    /// # Errors
+    /// When failing to open or parse the file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let reader = std::io::BufReader::new(std::fs::File::open(path)?);
+        let mut lines = reader.lines();
+        let memory = match lines.next() {
+            Some(line) => ron::de::from_str(&line?)?,
+            None => return Err("Empty recording".into()),
+        };
+        let mut frames = vec![];
+        for line in lines {
+            let line = line?;
+            if !line.trim().is_empty() {
+                frames.push(ron::de::from_str(&line)?);
+            }
+        }
+        Ok(Self { memory, frames })
+    }
+
+    /// Write the whole recording, in the same format as a [`Recorder`].
+    ///
+    /// # Errors
+    /// When failing to serialize or create the file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        write_line(&mut writer, &self.memory)?;
+        for frame in &self.frames {
+            write_line(&mut writer, frame)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+fn write_line(
+    writer: &mut impl Write,
+    value: &impl serde::Serialize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Newlines in strings are escaped, so each value stays on one line.
+    writeln!(writer, "{}", ron::ser::to_string(value)?)?;
+    Ok(())
+}
+
+/// Where and what to record. See [`crate::NativeOptions::record_session`].
+#[derive(Clone, Debug)]
+pub struct RecordOptions {
+    /// The recording is written here, one frame at a time.
+    /// It is flushed to disk every [`crate::App::auto_save_interval`] and when the app exits.
+    pub path: std::path::PathBuf,
+
+    /// Also record the [`egui::Output`] of each frame,
+    /// so that the replay can tell when it stops matching the recording.
+    pub include_output: bool,
+}
+
+impl RecordOptions {
+    /// Record input and output to the given file.
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            include_output: true,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Appends the frames of a session to a recording file as they happen.
+///
+/// Only the frames not yet flushed are kept in memory.
+pub struct Recorder {
+    options: RecordOptions,
+    writer: std::io::BufWriter<std::fs::File>,
+    num_frames: usize,
+}
+
+impl Recorder {
+    /// Create (or truncate) the file at [`RecordOptions::path`]
+    /// and write the egui memory at the start of the recording.
+    ///
+    /// # Errors
+    /// When failing to create or write to the file.
+    pub fn new(
+        options: RecordOptions,
+        memory: &egui::Memory,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(&options.path)?);
+        write_line(&mut writer, memory)?;
+        Ok(Self {
+            options,
+            writer,
+            num_frames: 0,
+        })
+    }
+
+    /// Where and what we are recording.
+    pub fn options(&self) -> &RecordOptions {
+        &self.options
+    }
+
+    /// How many frames have been recorded so far.
+    pub fn num_frames(&self) -> usize {
+        self.num_frames
+    }
+
+    /// Append one frame.
+    /// The output is only recorded if [`RecordOptions::include_output`] is set.
+    ///
+    /// # Errors
+    /// When failing to serialize or write the frame.
+    pub fn record_frame(
+        &mut self,
+        raw_input: &egui::RawInput,
+        output: &egui::Output,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let frame = RecordedFrameRef {
+            raw_input,
+            output: self.options.include_output.then(|| output),
+        };
+        write_line(&mut self.writer, &frame)?;
+        self.num_frames += 1;
+        Ok(())
+    }
+
+    /// Write the buffered frames to the file.
+    ///
+    /// # Errors
+    /// When failing to write to the file.
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Feeds the frames of a [`Recording`] to an app, one by one.
+///
+/// The replay starts paused, so that you can step through it from the first frame.
+pub struct Replayer {
+    recording: Recording,
+    next_frame: usize,
+    playing: bool,
+    step_requested: bool,
+    diverged_frames: Vec<usize>,
+}
+
+impl Replayer {
+    /// Start a paused replay at the first frame.
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            next_frame: 0,
+            playing: false,
+            step_requested: false,
+            diverged_frames: vec![],
+        }
+    }
+
+    /// The recording being replayed.
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// How many frames have been replayed so far.
+    pub fn num_replayed_frames(&self) -> usize {
+        self.next_frame
+    }
+
+    /// Have all frames been replayed?
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.recording.frames.len()
+    }
+
+    /// Playing (not paused) and not yet finished.
+    pub fn is_playing(&self) -> bool {
+        self.playing && !self.is_finished()
+    }
+
+    /// Play or pause.
+    pub fn set_playing(&mut self, playing: bool) {
+        self.playing = playing;
+    }
+
+    /// Replay one more frame while paused.
+    pub fn step(&mut self) {
+        self.step_requested = true;
+    }
+
+    /// The input for the next frame, if we are playing or a step was requested.
+    ///
+    /// Give it to [`egui::CtxRef::run`] and then pass the output to [`Self::check_output`].
+    pub fn next_input(&mut self) -> Option<egui::RawInput> {
+        if !self.playing && !self.step_requested {
+            return None;
+        }
+        self.step_requested = false;
+        let frame = self.recording.frames.get(self.next_frame)?;
+        self.next_frame += 1;
+        Some(frame.raw_input.clone())
+    }
+
+    /// The frame that was last returned by [`Self::next_input`].
+    pub fn current_frame(&self) -> Option<&RecordedFrame> {
+        self.recording.frames.get(self.next_frame.checked_sub(1)?)
+    }
+
+    /// Compare the output of the last replayed frame with the recording, if the output was recorded.
+    ///
+    /// Returns `false` if they differ, i.e. the replay is no longer the same as the recorded session.
+    pub fn check_output(&mut self, output: &egui::Output) -> bool {
+        let frame_index = self.next_frame.saturating_sub(1);
+        let same = match self.current_frame().and_then(|frame| frame.output.as_ref()) {
+            Some(recorded_output) => recorded_output == output,
+            None => true,
+        };
+        if !same && !self.diverged_frames.contains(&frame_index) {
+            self.diverged_frames.push(frame_index);
+        }
+        same
+    }
+
+    /// Frames (zero-based) where the replayed output differed from the recorded output.
+    pub fn diverged_frames(&self) -> &[usize] {
+        &self.diverged_frames
+    }
+
+    /// A description of the replay and of the current frame, e.g. to show in an inspector.
+    pub fn describe(&self) -> String {
+        let mut description = format!(
+            "Replaying frame {}/{}",
+            self.num_replayed_frames(),
+            self.recording.frames.len()
+        );
+        if self.is_finished() {
+            description += " (finished)";
+        } else if !self.playing {
+            description += " (paused)";
+        }
+        if let Some(&first) = self.diverged_frames.first() {
+            description += &format!(
+                "\nThe output differs from the recording since frame {}",
+                first
+            );
+        }
+        if let Some(frame) = self.current_frame() {
+            let raw_input = &frame.raw_input;
+            if let Some(time) = raw_input.time {
+                description += &format!("\nTime: {:.3} s", time);
+            }
+            for event in &raw_input.events {
+                description += &format!("\n{:?}", event);
+            }
+        }
+        description
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(time: f64, text: &str) -> egui::RawInput {
+        egui::RawInput {
+            time: Some(time),
+            events: vec![egui::Event::Text(text.to_owned())],
+            ..Default::default()
+        }
+    }
+
+    fn output(text: &str) -> egui::Output {
+        egui::Output {
+            copied_text: text.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn record_then_replay() {
+        let path =
+            std::env::temp_dir().join(format!("epi_record_then_replay_{}.ron", std::process::id()));
+
+        let mut memory = egui::Memory::default();
+        memory.options.tessellation_options.anti_alias = false;
+
+        let mut recorder = Recorder::new(RecordOptions::new(&path), &memory).unwrap();
+        recorder
+            .record_frame(&input(0.0, "a"), &output("1"))
+            .unwrap();
+        recorder
+            .record_frame(&input(0.1, "multi\nline"), &output("2"))
+            .unwrap();
+        recorder
+            .record_frame(&input(0.2, "c"), &output("3"))
+            .unwrap();
+        assert_eq!(recorder.num_frames(), 3);
+        drop(recorder);
+
+        let recording = Recording::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(!recording.memory.options.tessellation_options.anti_alias);
+        assert_eq!(recording.frames.len(), 3);
+
+        let mut replayer = Replayer::new(recording);
+        assert_eq!(replayer.next_input(), None, "Replays start paused");
+
+        replayer.step();
+        assert_eq!(replayer.next_input(), Some(input(0.0, "a")));
+        assert!(replayer.check_output(&output("1")));
+        assert_eq!(replayer.next_input(), None);
+
+        replayer.set_playing(true);
+        assert_eq!(replayer.next_input(), Some(input(0.1, "multi\nline")));
+        assert!(!replayer.check_output(&output("something else")));
+        assert_eq!(replayer.next_input(), Some(input(0.2, "c")));
+        assert!(replayer.check_output(&output("3")));
+        assert_eq!(replayer.next_input(), None);
+        assert!(replayer.is_finished());
+        assert_eq!(replayer.diverged_frames(), &[1]);
+    }
+
+    #[test]
+    fn save_and_load() {
+        let path =
+            std::env::temp_dir().join(format!("epi_save_and_load_{}.ron", std::process::id()));
+        let recording = Recording {
+            memory: Default::default(),
+            frames: vec![RecordedFrame {
+                raw_input: input(1.0, "x"),
+                output: None,
+            }],
+        };
+        recording.save(&path).unwrap();
+        let loaded = Recording::load(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert_eq!(loaded.frames.len(), 1);
+        assert_eq!(loaded.frames[0].raw_input, input(1.0, "x"));
+        assert!(loaded.frames[0].output.is_none());
+    }
+}